@@ -0,0 +1,450 @@
+//! Package ABI: the public surface of a Move package in the same normalized
+//! form the fullnode returns from `sui_getNormalizedMoveModulesByPackage`.
+//!
+//! On-chain packages are fetched as-is. Local packages are derived from the
+//! parsed sources, resolving type names through `use` declarations, the
+//! implicit Sui 2024 aliases and the named addresses from `Move.toml`, so
+//! both sides can be compared or rendered with the same code.
+
+use crate::error::{Result, SuiForgeError};
+use crate::manifest::Manifest;
+use crate::move_syntax::{self, Ability, Module, Type, TypeParam, Visibility};
+use crate::rpc::types::{
+    AbilitySet, NormalizedField, NormalizedFunction, NormalizedModule, NormalizedStruct,
+    NormalizedType, StructTypeParameter,
+};
+use crate::rpc::RpcClient;
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+use std::path::Path;
+
+/// Modules of one package keyed by module name.
+pub type PackageAbi = BTreeMap<String, NormalizedModule>;
+
+/// Aliases every Sui Move 2024 module gets without a `use`.
+const IMPLICIT_USES: [(&str, &str, &str, Option<&str>); 8] = [
+    ("vector", "0x1", "vector", None),
+    ("option", "0x1", "option", None),
+    ("Option", "0x1", "option", Some("Option")),
+    ("object", "0x2", "object", None),
+    ("UID", "0x2", "object", Some("UID")),
+    ("ID", "0x2", "object", Some("ID")),
+    ("transfer", "0x2", "transfer", None),
+    ("TxContext", "0x2", "tx_context", Some("TxContext")),
+];
+
+pub async fn fetch(client: &RpcClient, package_id: &str) -> Result<PackageAbi> {
+    client.get_normalized_move_modules_by_package(package_id).await
+}
+
+/// Load the local package ABI from `<root>/sources`, failing if any file
+/// does not parse.
+pub fn load_local(root: &Path, self_address: &str) -> Result<PackageAbi> {
+    let package = move_syntax::Package::load(&root.join("sources"))?;
+    if let Some(error) = package.errors.first() {
+        return Err(SuiForgeError::MoveParse(error.clone()));
+    }
+    let addresses = Manifest::load(root)?.addresses()?;
+    Ok(from_source(&package, &addresses, self_address))
+}
+
+/// Derive the ABI of a local package from its sources.
+///
+/// `addresses` holds named addresses from `Move.toml`; `std` and `sui` are
+/// always known. The package's own named address is replaced by
+/// `self_address`, which is usually `0x0` locally but should be the deployed
+/// package ID when comparing against chain. Test-only modules and functions,
+/// and macros (which do not exist in bytecode), are left out.
+pub fn from_source(
+    package: &move_syntax::Package,
+    addresses: &BTreeMap<String, String>,
+    self_address: &str,
+) -> PackageAbi {
+    let self_names: HashSet<&str> = package.modules.iter().map(|m| m.address.as_str()).collect();
+    let local_modules: HashSet<&str> = package.modules.iter().map(|m| m.name.as_str()).collect();
+
+    let resolver = AddressResolver {
+        addresses,
+        self_names: &self_names,
+        self_address: canonical_address(self_address),
+    };
+
+    package
+        .modules
+        .iter()
+        .filter(|m| !m.attributes.iter().any(|a| a == "test_only"))
+        .map(|module| {
+            let scope = Scope {
+                module,
+                resolver: &resolver,
+                local_modules: &local_modules,
+                type_params: Vec::new(),
+            };
+            (module.name.clone(), scope.normalize_module())
+        })
+        .collect()
+}
+
+struct AddressResolver<'a> {
+    addresses: &'a BTreeMap<String, String>,
+    self_names: &'a HashSet<&'a str>,
+    self_address: String,
+}
+
+impl AddressResolver<'_> {
+    /// Resolve a named or numeric address. Names that cannot be resolved
+    /// (addresses of dependencies not listed in `Move.toml`) are kept as is.
+    fn resolve(&self, name: &str) -> String {
+        if is_hex_address(name) {
+            return canonical_address(name);
+        }
+        if self.self_names.contains(name) {
+            return self.self_address.clone();
+        }
+        match (name, self.addresses.get(name)) {
+            (_, Some(value)) if is_hex_address(value) => canonical_address(value),
+            ("std", _) => canonical_address("0x1"),
+            ("sui", _) => canonical_address("0x2"),
+            _ => name.to_string(),
+        }
+    }
+}
+
+struct Scope<'a> {
+    module: &'a Module,
+    resolver: &'a AddressResolver<'a>,
+    local_modules: &'a HashSet<&'a str>,
+    type_params: Vec<String>,
+}
+
+impl Scope<'_> {
+    fn normalize_module(&self) -> NormalizedModule {
+        let structs = self
+            .module
+            .structs
+            .iter()
+            .map(|s| {
+                let scope = self.with_type_params(&s.type_params);
+                let normalized = NormalizedStruct {
+                    abilities: ability_set(&s.abilities),
+                    type_parameters: s
+                        .type_params
+                        .iter()
+                        .map(|tp| StructTypeParameter {
+                            constraints: ability_set(&tp.constraints),
+                            is_phantom: tp.is_phantom,
+                        })
+                        .collect(),
+                    fields: s
+                        .fields
+                        .iter()
+                        .map(|f| NormalizedField {
+                            name: f.name.clone(),
+                            type_: scope.normalize_type(&f.ty),
+                        })
+                        .collect(),
+                };
+                (s.name.clone(), normalized)
+            })
+            .collect();
+
+        let exposed_functions = self
+            .module
+            .functions
+            .iter()
+            .filter(|f| !f.is_macro && !f.is_test() && !f.is_test_only())
+            .filter(|f| f.visibility != Visibility::Private || f.is_entry)
+            .map(|f| {
+                let scope = self.with_type_params(&f.type_params);
+                let normalized = NormalizedFunction {
+                    visibility: match f.visibility {
+                        Visibility::Public => "Public",
+                        Visibility::Private => "Private",
+                        Visibility::Package | Visibility::Friend => "Friend",
+                    }
+                    .to_string(),
+                    is_entry: f.is_entry,
+                    type_parameters: f
+                        .type_params
+                        .iter()
+                        .map(|tp| ability_set(&tp.constraints))
+                        .collect(),
+                    parameters: f.params.iter().map(|p| scope.normalize_type(&p.ty)).collect(),
+                    return_: f.returns.iter().map(|t| scope.normalize_type(t)).collect(),
+                };
+                (f.name.clone(), normalized)
+            })
+            .collect();
+
+        NormalizedModule {
+            file_format_version: 6,
+            address: self.resolver.resolve(&self.module.address),
+            name: self.module.name.clone(),
+            friends: Vec::new(),
+            structs,
+            exposed_functions,
+        }
+    }
+
+    fn with_type_params(&self, params: &[TypeParam]) -> Scope<'_> {
+        Scope {
+            module: self.module,
+            resolver: self.resolver,
+            local_modules: self.local_modules,
+            type_params: params.iter().map(|p| p.name.clone()).collect(),
+        }
+    }
+
+    fn normalize_type(&self, ty: &Type) -> NormalizedType {
+        match ty {
+            Type::Ref { mutable: true, inner } => {
+                NormalizedType::MutableReference(Box::new(self.normalize_type(inner)))
+            }
+            Type::Ref { mutable: false, inner } => {
+                NormalizedType::Reference(Box::new(self.normalize_type(inner)))
+            }
+            // Tuples only appear as return types, which the parser flattens
+            Type::Tuple(_) => self.unresolved(&ty.to_string()),
+            Type::Named { path, args } => {
+                let args: Vec<NormalizedType> = args.iter().map(|a| self.normalize_type(a)).collect();
+                match path.as_slice() {
+                    [name] => self.normalize_name(name, args),
+                    [module, name] => match self.module_alias(module) {
+                        Some((address, module)) => struct_type(address, module, name, args),
+                        None => self.unresolved(&ty.to_string()),
+                    },
+                    [address, module, name] => {
+                        struct_type(self.resolver.resolve(address), module.clone(), name, args)
+                    }
+                    _ => self.unresolved(&ty.to_string()),
+                }
+            }
+        }
+    }
+
+    fn normalize_name(&self, name: &str, mut args: Vec<NormalizedType>) -> NormalizedType {
+        match name {
+            "bool" => return NormalizedType::Bool,
+            "u8" => return NormalizedType::U8,
+            "u16" => return NormalizedType::U16,
+            "u32" => return NormalizedType::U32,
+            "u64" => return NormalizedType::U64,
+            "u128" => return NormalizedType::U128,
+            "u256" => return NormalizedType::U256,
+            "address" => return NormalizedType::Address,
+            "signer" => return NormalizedType::Signer,
+            "vector" if args.len() == 1 => return NormalizedType::Vector(Box::new(args.remove(0))),
+            _ => {}
+        }
+
+        if let Some(index) = self.type_params.iter().position(|p| p == name) {
+            return NormalizedType::TypeParameter(index as u16);
+        }
+
+        if let Some(use_decl) = self
+            .module
+            .uses
+            .iter()
+            .find(|u| u.alias == name && u.member.is_some())
+        {
+            return struct_type(
+                self.resolver.resolve(&use_decl.address),
+                use_decl.module.clone(),
+                use_decl.member.as_deref().unwrap_or_default(),
+                args,
+            );
+        }
+
+        if self.module.structs.iter().any(|s| s.name == name) {
+            return struct_type(
+                self.resolver.resolve(&self.module.address),
+                self.module.name.clone(),
+                name,
+                args,
+            );
+        }
+
+        match IMPLICIT_USES
+            .iter()
+            .find(|(alias, _, _, member)| *alias == name && member.is_some())
+        {
+            Some((_, address, module, Some(member))) => {
+                struct_type(canonical_address(address), module.to_string(), member, args)
+            }
+            _ => self.unresolved(name),
+        }
+    }
+
+    /// Resolve a module qualifier such as `coin` in `coin::Coin`.
+    fn module_alias(&self, alias: &str) -> Option<(String, String)> {
+        if let Some(use_decl) = self
+            .module
+            .uses
+            .iter()
+            .find(|u| u.alias == alias && u.member.is_none())
+        {
+            return Some((
+                self.resolver.resolve(&use_decl.address),
+                use_decl.module.clone(),
+            ));
+        }
+        if self.local_modules.contains(alias) {
+            return Some((self.resolver.resolve(&self.module.address), alias.to_string()));
+        }
+        IMPLICIT_USES
+            .iter()
+            .find(|(a, _, _, member)| *a == alias && member.is_none())
+            .map(|(_, address, module, _)| (canonical_address(address), module.to_string()))
+    }
+
+    /// A type the resolver could not place. It is kept as a struct in the
+    /// current module with an empty address so it never matches by accident.
+    fn unresolved(&self, name: &str) -> NormalizedType {
+        struct_type(String::new(), self.module.name.clone(), name, Vec::new())
+    }
+}
+
+fn struct_type(
+    address: String,
+    module: String,
+    name: &str,
+    type_arguments: Vec<NormalizedType>,
+) -> NormalizedType {
+    NormalizedType::Struct {
+        address,
+        module,
+        name: name.to_string(),
+        type_arguments,
+    }
+}
+
+fn ability_set(abilities: &[Ability]) -> AbilitySet {
+    let mut abilities = abilities.to_vec();
+    abilities.sort();
+    AbilitySet {
+        abilities: abilities
+            .iter()
+            .map(|a| {
+                let name = a.as_str();
+                format!("{}{}", name[..1].to_uppercase(), &name[1..])
+            })
+            .collect(),
+    }
+}
+
+pub fn is_hex_address(s: &str) -> bool {
+    s.strip_prefix("0x")
+        .is_some_and(|hex| !hex.is_empty() && hex.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+/// Full-width lowercase form of a hex address (`0x2` becomes `0x000...002`).
+/// Non-hex input is returned unchanged.
+pub fn canonical_address(address: &str) -> String {
+    if !is_hex_address(address) {
+        return address.to_string();
+    }
+    format!("0x{:0>64}", address[2..].to_lowercase())
+}
+
+/// Shortest form of a hex address (`0x000...002` becomes `0x2`).
+pub fn short_address(address: &str) -> String {
+    if !is_hex_address(address) {
+        return address.to_string();
+    }
+    let trimmed = address[2..].trim_start_matches('0');
+    format!("0x{}", if trimmed.is_empty() { "0" } else { trimmed })
+}
+
+/// Whether two addresses refer to the same account. Named addresses only
+/// match the same name, and the empty address of an unresolved type
+/// matches nothing.
+pub fn same_address(a: &str, b: &str) -> bool {
+    if is_hex_address(a) && is_hex_address(b) {
+        canonical_address(a) == canonical_address(b)
+    } else {
+        !a.is_empty() && a == b
+    }
+}
+
+/// Whether every struct in `ty` has a numeric address, i.e. the type could be
+/// placed when the ABI was derived from source.
+pub fn is_resolved(ty: &NormalizedType) -> bool {
+    match ty {
+        NormalizedType::Struct {
+            address,
+            type_arguments,
+            ..
+        } => is_hex_address(address) && type_arguments.iter().all(is_resolved),
+        NormalizedType::Vector(inner)
+        | NormalizedType::Reference(inner)
+        | NormalizedType::MutableReference(inner) => is_resolved(inner),
+        _ => true,
+    }
+}
+
+/// Structural type equality using [`same_address`] for struct addresses.
+pub fn same_type(a: &NormalizedType, b: &NormalizedType) -> bool {
+    use NormalizedType::*;
+    match (a, b) {
+        (
+            Struct {
+                address: a_addr,
+                module: a_mod,
+                name: a_name,
+                type_arguments: a_args,
+            },
+            Struct {
+                address: b_addr,
+                module: b_mod,
+                name: b_name,
+                type_arguments: b_args,
+            },
+        ) => {
+            same_address(a_addr, b_addr)
+                && a_mod == b_mod
+                && a_name == b_name
+                && a_args.len() == b_args.len()
+                && a_args.iter().zip(b_args).all(|(a, b)| same_type(a, b))
+        }
+        (Vector(a), Vector(b))
+        | (Reference(a), Reference(b))
+        | (MutableReference(a), MutableReference(b)) => same_type(a, b),
+        _ => a == b,
+    }
+}
+
+impl fmt::Display for NormalizedType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NormalizedType::Bool => write!(f, "bool"),
+            NormalizedType::U8 => write!(f, "u8"),
+            NormalizedType::U16 => write!(f, "u16"),
+            NormalizedType::U32 => write!(f, "u32"),
+            NormalizedType::U64 => write!(f, "u64"),
+            NormalizedType::U128 => write!(f, "u128"),
+            NormalizedType::U256 => write!(f, "u256"),
+            NormalizedType::Address => write!(f, "address"),
+            NormalizedType::Signer => write!(f, "signer"),
+            NormalizedType::Vector(inner) => write!(f, "vector<{}>", inner),
+            NormalizedType::TypeParameter(index) => write!(f, "T{}", index),
+            NormalizedType::Reference(inner) => write!(f, "&{}", inner),
+            NormalizedType::MutableReference(inner) => write!(f, "&mut {}", inner),
+            NormalizedType::Struct {
+                address,
+                module,
+                name,
+                type_arguments,
+            } => {
+                if !address.is_empty() {
+                    write!(f, "{}::", short_address(address))?;
+                }
+                write!(f, "{}::{}", module, name)?;
+                if !type_arguments.is_empty() {
+                    let args: Vec<String> = type_arguments.iter().map(|a| a.to_string()).collect();
+                    write!(f, "<{}>", args.join(", "))?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
@@ -0,0 +1,531 @@
+//! Decoding Move aborts back to the project's sources.
+//!
+//! Aborts surface in execution errors as
+//! `MoveAbort(MoveLocation { module: ModuleId { address: .., name:
+//! Identifier("shop") }, .., function_name: Some("buy") }, 2) in command 0`,
+//! and in `sui move test` failures as `aborted with code 2 originating in the
+//! module 0x0::shop`. When the aborting module belongs to the current
+//! project, the code is matched against the module's constants and the
+//! `assert!` and `abort` sites that use them: plain codes by the value of a
+//! `u64` constant, Move 2024 `#[error]` codes by the line they were raised on.
+
+use crate::abi::same_address;
+use crate::config::find_project_root;
+use crate::error::Result;
+use crate::lock::LockFile;
+use crate::manifest::Manifest;
+use crate::move_lock::is_system_address;
+use crate::move_syntax::{Constant, Package, Type};
+use colored::Colorize;
+use regex::Regex;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// A `MoveAbort` parsed from an execution error or test failure.
+#[derive(Debug, Clone)]
+pub struct MoveAbort {
+    pub address: String,
+    pub module: String,
+    pub function: Option<String>,
+    pub code: u64,
+    /// Index of the programmable transaction command that aborted.
+    pub command: Option<usize>,
+}
+
+impl MoveAbort {
+    /// The first abort in `error`.
+    pub fn parse(error: &str) -> Option<Self> {
+        Self::find_all(error).into_iter().next()
+    }
+
+    /// Every abort in `text`, such as CLI or test runner output.
+    pub fn find_all(text: &str) -> Vec<Self> {
+        static EXECUTION: OnceLock<Regex> = OnceLock::new();
+        static TEST: OnceLock<Regex> = OnceLock::new();
+        let execution = EXECUTION.get_or_init(|| {
+            Regex::new(
+                r#"MoveAbort\(MoveLocation \{ module: ModuleId \{ address: (\w+), name: Identifier\("(\w+)"\) \}, function: \d+, instruction: \d+, function_name: (?:Some\("(\w+)"\)|None) \}, (\d+)\)(?: in command (\d+))?"#,
+            )
+            .expect("valid MoveAbort pattern")
+        });
+        let test = TEST.get_or_init(|| {
+            Regex::new(r"aborted with code (\d+) originating in the module (\w+)::(\w+)")
+                .expect("valid test failure pattern")
+        });
+
+        let mut aborts: Vec<Self> = execution
+            .captures_iter(text)
+            .filter_map(|captures| {
+                Some(Self {
+                    address: hex_address(&captures[1]),
+                    module: captures[2].to_string(),
+                    function: captures.get(3).map(|m| m.as_str().to_string()),
+                    code: captures[4].parse().ok()?,
+                    command: captures.get(5).and_then(|m| m.as_str().parse().ok()),
+                })
+            })
+            .collect();
+        aborts.extend(test.captures_iter(text).filter_map(|captures| {
+            Some(Self {
+                address: hex_address(&captures[2]),
+                module: captures[3].to_string(),
+                function: None,
+                code: captures[1].parse().ok()?,
+                command: None,
+            })
+        }));
+        aborts
+    }
+
+    /// Source line encoded in a Move 2024 `#[error]` abort code, which sets
+    /// the top bit and stores the line in bits 32..48.
+    pub fn clever_line(&self) -> Option<usize> {
+        (self.code >> 63 == 1).then_some(((self.code >> 32) & 0xffff) as usize)
+    }
+}
+
+fn hex_address(address: &str) -> String {
+    if address.starts_with("0x") {
+        address.to_string()
+    } else {
+        format!("0x{}", address)
+    }
+}
+
+/// An `assert!` or `abort` that raises a constant.
+#[derive(Debug, Clone)]
+pub struct AbortUse {
+    pub function: String,
+    pub line: usize,
+}
+
+/// A constant that is, or can be, raised as an abort code.
+#[derive(Debug, Clone)]
+pub struct AbortConstant {
+    pub module: String,
+    pub name: String,
+    /// Value of a `u64` constant; `#[error]` constants have none.
+    pub code: Option<u64>,
+    /// Message of a byte-string `#[error]` constant.
+    pub message: Option<String>,
+    pub path: PathBuf,
+    pub line: usize,
+    pub doc: Option<String>,
+    /// Where the constant is raised.
+    pub uses: Vec<AbortUse>,
+}
+
+impl AbortConstant {
+    /// The `#[error]` message and the doc comment, whichever exist.
+    pub fn notes(&self) -> impl Iterator<Item = &str> {
+        self.message
+            .as_deref()
+            .into_iter()
+            .chain(self.doc.as_deref())
+    }
+}
+
+/// What an abort means in terms of the project's sources.
+#[derive(Debug, Clone)]
+pub struct DecodedAbort {
+    pub module: String,
+    /// The constant with the abort's code, if one exists.
+    pub constant: Option<AbortConstant>,
+    /// `path:line` of the `assert!` or `abort`, when it can be pinned down.
+    pub location: Option<String>,
+}
+
+impl DecodedAbort {
+    /// One-line summary, e.g. `shop::EInsufficientFunds (sources/shop.move:7)`.
+    pub fn describe(&self) -> String {
+        let name = match &self.constant {
+            Some(constant) => format!("{}::{}", self.module, constant.name),
+            None => format!("{}::<unnamed>", self.module),
+        };
+        let location = self.location.clone().or_else(|| {
+            self.constant
+                .as_ref()
+                .map(|c| format!("{}:{}", c.path.display(), c.line))
+        });
+        match location {
+            Some(location) => format!("{} ({})", name, location),
+            None => name,
+        }
+    }
+}
+
+/// Abort constants of the project, and the package IDs it is deployed at.
+pub struct AbortCatalog {
+    package_ids: Vec<String>,
+    module_paths: Vec<(String, PathBuf)>,
+    constants: Vec<AbortConstant>,
+}
+
+impl AbortCatalog {
+    /// Build the catalog for the project at `root`.
+    pub fn load(root: &Path) -> Result<Self> {
+        let package = Package::load(&root.join("sources"))?;
+        let lock = LockFile::load(root)?;
+
+        let mut constants = Vec::new();
+        for module in &package.modules {
+            for constant in &module.constants {
+                let code = constant_code(constant);
+                let raises = |code: &str| code.trim() == constant.name;
+                let mut uses = Vec::new();
+                for function in &module.functions {
+                    let Some(body) = &function.body else { continue };
+                    let asserts = body
+                        .asserts
+                        .iter()
+                        .filter(|a| a.code.as_deref().is_some_and(raises))
+                        .map(|a| a.line);
+                    let aborts = body
+                        .aborts
+                        .iter()
+                        .filter(|a| raises(&a.code))
+                        .map(|a| a.line);
+                    uses.extend(asserts.chain(aborts).map(|line| AbortUse {
+                        function: function.name.clone(),
+                        line,
+                    }));
+                }
+                uses.sort_by_key(|u| u.line);
+
+                // Unused `u64` constants may still be raised by value, but
+                // other constants only matter where they are raised.
+                if code.is_none() && uses.is_empty() {
+                    continue;
+                }
+
+                constants.push(AbortConstant {
+                    module: module.name.clone(),
+                    name: constant.name.clone(),
+                    code,
+                    message: parse_message(&constant.value),
+                    path: relative(root, &module.path),
+                    line: constant.line,
+                    doc: constant.doc.clone(),
+                    uses,
+                });
+            }
+        }
+
+        // Tests run the package at its own named address, usually 0x0.
+        let mut package_ids = lock.package_ids();
+        if let Ok(manifest) = Manifest::load(root) {
+            let addresses = manifest.addresses().unwrap_or_default();
+            if let Some(own) = manifest
+                .package_name()
+                .ok()
+                .flatten()
+                .and_then(|name| addresses.get(&name.to_lowercase()).cloned())
+            {
+                package_ids.push(own);
+            }
+        }
+
+        Ok(Self {
+            package_ids,
+            module_paths: package
+                .modules
+                .iter()
+                .map(|m| (m.name.clone(), relative(root, &m.path)))
+                .collect(),
+            constants,
+        })
+    }
+
+    /// Decode `abort` if it was raised by one of the project's modules.
+    pub fn decode(&self, abort: &MoveAbort) -> Option<DecodedAbort> {
+        if !self
+            .package_ids
+            .iter()
+            .any(|id| same_address(id, &abort.address))
+        {
+            return None;
+        }
+        self.decode_unpublished(abort)
+    }
+
+    /// Decode `abort` by module name alone, for transactions that publish or
+    /// upgrade the project, whose new package ID is not known beforehand.
+    pub fn decode_unpublished(&self, abort: &MoveAbort) -> Option<DecodedAbort> {
+        if is_system_address(&abort.address) {
+            return None;
+        }
+        let (_, path) = self.module_paths.iter().find(|(m, _)| *m == abort.module)?;
+        let module = self.constants.iter().filter(|c| c.module == abort.module);
+
+        // Clever errors carry the line of the abort; the constant is the one
+        // raised there. Plain codes are matched by value, and pinned to a line
+        // when the aborting function raises the constant exactly once.
+        let (constant, line) = match abort.clever_line() {
+            Some(line) => (
+                module
+                    .into_iter()
+                    .find(|c| c.uses.iter().any(|u| u.line == line)),
+                Some(line),
+            ),
+            None => {
+                let constant = module.into_iter().find(|c| c.code == Some(abort.code));
+                let line = constant.and_then(|c| {
+                    let function = abort.function.as_deref()?;
+                    let mut uses = c.uses.iter().filter(|u| u.function == function);
+                    match (uses.next(), uses.next()) {
+                        (Some(only), None) => Some(only.line),
+                        _ => None,
+                    }
+                });
+                (constant, line)
+            }
+        };
+
+        Some(DecodedAbort {
+            module: abort.module.clone(),
+            constant: constant.cloned(),
+            location: line.map(|line| {
+                let path = constant.map_or(path, |c| &c.path);
+                format!("{}:{}", path.display(), line)
+            }),
+        })
+    }
+}
+
+/// Print what the aborts in `output` mean when they come from the current
+/// project. `publishing` is set for the output of a publish or upgrade, see
+/// [`AbortCatalog::decode_unpublished`]. Outside a project, or if the
+/// sources cannot be read, nothing is printed.
+pub fn explain(output: &str, publishing: bool) {
+    let aborts = MoveAbort::find_all(output);
+    if aborts.is_empty() {
+        return;
+    }
+    let Some(catalog) = find_project_root()
+        .ok()
+        .and_then(|root| AbortCatalog::load(&root).ok())
+    else {
+        return;
+    };
+
+    let mut seen = Vec::new();
+    for abort in &aborts {
+        let decoded = if publishing {
+            catalog.decode_unpublished(abort)
+        } else {
+            catalog.decode(abort)
+        };
+        let Some(decoded) = decoded else { continue };
+        let line = decoded.describe();
+        if seen.contains(&line) {
+            continue;
+        }
+        println!(
+            "  {} abort code {} is {}",
+            "→".cyan(),
+            abort.code.to_string().yellow(),
+            line.red().bold()
+        );
+        if let Some(constant) = &decoded.constant {
+            for note in constant.notes() {
+                println!("    {}", note.dimmed());
+            }
+        }
+        seen.push(line);
+    }
+}
+
+fn relative(root: &Path, path: &Path) -> PathBuf {
+    path.strip_prefix(root).unwrap_or(path).to_path_buf()
+}
+
+/// The value of a `u64` constant.
+fn constant_code(constant: &Constant) -> Option<u64> {
+    let is_u64 =
+        matches!(&constant.ty, Type::Named { path, .. } if path.len() == 1 && path[0] == "u64");
+    is_u64.then(|| parse_integer(&constant.value)).flatten()
+}
+
+/// The text of a `b"..."` or `x"..."` literal.
+fn parse_message(literal: &str) -> Option<String> {
+    let literal = literal.trim();
+    if let Some(text) = literal
+        .strip_prefix("b\"")
+        .and_then(|l| l.strip_suffix('"'))
+    {
+        return Some(unescape(text));
+    }
+    let hex = literal.strip_prefix("x\"")?.strip_suffix('"')?;
+    let bytes = (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect::<Option<Vec<u8>>>()?;
+    String::from_utf8(bytes).ok()
+}
+
+/// Resolve the escapes of a byte string literal.
+fn unescape(text: &str) -> String {
+    let mut out = String::new();
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some('r') => out.push('\r'),
+            Some('t') => out.push('\t'),
+            Some('0') => out.push('\0'),
+            Some('x') => {
+                let hex: String = chars.by_ref().take(2).collect();
+                if let Ok(byte) = u8::from_str_radix(&hex, 16) {
+                    out.push(byte as char);
+                }
+            }
+            Some(other) => out.push(other),
+            None => {}
+        }
+    }
+    out
+}
+
+/// Parse a Move integer literal such as `1`, `0x10`, `1_000` or `7u64`.
+fn parse_integer(literal: &str) -> Option<u64> {
+    let literal = literal.trim().replace('_', "");
+    let literal = ["u8", "u16", "u32", "u64", "u128", "u256"]
+        .iter()
+        .find_map(|suffix| literal.strip_suffix(suffix))
+        .unwrap_or(&literal);
+    match literal.strip_prefix("0x") {
+        Some(hex) => u64::from_str_radix(hex, 16).ok(),
+        None => literal.parse().ok(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    const SHOP: &str = r#"module shop::shop {
+    /// Not enough coins.
+    const EInsufficientFunds: u64 = 0x1;
+    #[error]
+    const ENotOwner: vector<u8> = b"Only the owner can close the \"shop\"";
+    #[error]
+    const EUnused: vector<u8> = b"Never raised";
+
+    public fun buy(paid: u64, price: u64) {
+        assert!(paid >= price, EInsufficientFunds);
+    }
+
+    public fun close(owner: bool) {
+        if (!owner) abort ENotOwner;
+    }
+}
+"#;
+
+    fn catalog() -> (tempfile::TempDir, AbortCatalog) {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir(dir.path().join("sources")).unwrap();
+        fs::write(dir.path().join("sources/shop.move"), SHOP).unwrap();
+        let catalog = AbortCatalog::load(dir.path()).unwrap();
+        (dir, catalog)
+    }
+
+    fn abort(code: u64) -> MoveAbort {
+        MoveAbort {
+            address: "0x0".to_string(),
+            module: "shop".to_string(),
+            function: Some("buy".to_string()),
+            code,
+            command: None,
+        }
+    }
+
+    #[test]
+    fn find_all_reads_execution_errors_and_test_failures() {
+        let text = r#"MoveAbort(MoveLocation { module: ModuleId { address: 2a, name: Identifier("shop") }, function: 1, instruction: 9, function_name: Some("buy") }, 2) in command 1
+┌── test_buy ──────
+│ error[E11001]: test failure
+│ Test was not expected to error, but it aborted with code 3 originating in the module 0x0::shop rooted here"#;
+
+        let aborts = MoveAbort::find_all(text);
+        assert_eq!(aborts.len(), 2);
+
+        assert_eq!(aborts[0].address, "0x2a");
+        assert_eq!(aborts[0].module, "shop");
+        assert_eq!(aborts[0].function.as_deref(), Some("buy"));
+        assert_eq!(aborts[0].code, 2);
+        assert_eq!(aborts[0].command, Some(1));
+
+        assert_eq!(aborts[1].address, "0x0");
+        assert_eq!(aborts[1].function, None);
+        assert_eq!(aborts[1].code, 3);
+        assert_eq!(aborts[1].command, None);
+    }
+
+    #[test]
+    fn parse_integer_accepts_move_literals() {
+        assert_eq!(parse_integer("1"), Some(1));
+        assert_eq!(parse_integer("0x10"), Some(16));
+        assert_eq!(parse_integer("1_000"), Some(1000));
+        assert_eq!(parse_integer("7u64"), Some(7));
+        assert_eq!(parse_integer("b\"oops\""), None);
+    }
+
+    #[test]
+    fn clever_line_is_read_from_tagged_codes_only() {
+        assert_eq!(abort(2).clever_line(), None);
+        assert_eq!(
+            abort((1 << 63) | (14 << 32) | (2 << 16)).clever_line(),
+            Some(14)
+        );
+    }
+
+    #[test]
+    fn parse_message_reads_byte_and_hex_strings() {
+        assert_eq!(
+            parse_message(r#"b"a \"b\"\n""#).as_deref(),
+            Some("a \"b\"\n")
+        );
+        assert_eq!(parse_message(r#"x"6869""#).as_deref(), Some("hi"));
+        assert_eq!(parse_message("1"), None);
+    }
+
+    #[test]
+    fn plain_codes_are_matched_by_value() {
+        let (_dir, catalog) = catalog();
+        let decoded = catalog.decode_unpublished(&abort(1)).unwrap();
+
+        let constant = decoded.constant.unwrap();
+        assert_eq!(constant.name, "EInsufficientFunds");
+        assert_eq!(constant.notes().collect::<Vec<_>>(), ["Not enough coins."]);
+        assert_eq!(decoded.location.as_deref(), Some("sources/shop.move:10"));
+    }
+
+    #[test]
+    fn error_constants_are_matched_by_abort_line() {
+        let (_dir, catalog) = catalog();
+        let mut abort = abort((1 << 63) | (14 << 32));
+        abort.function = Some("close".to_string());
+        let decoded = catalog.decode_unpublished(&abort).unwrap();
+
+        let constant = decoded.constant.unwrap();
+        assert_eq!(constant.name, "ENotOwner");
+        assert_eq!(constant.code, None);
+        assert_eq!(
+            constant.message.as_deref(),
+            Some("Only the owner can close the \"shop\"")
+        );
+        assert_eq!(decoded.location.as_deref(), Some("sources/shop.move:14"));
+    }
+
+    #[test]
+    fn unraised_error_constants_are_not_catalogued() {
+        let (_dir, catalog) = catalog();
+        let names: Vec<_> = catalog.constants.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["EInsufficientFunds", "ENotOwner"]);
+    }
+}
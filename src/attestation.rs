@@ -0,0 +1,266 @@
+//! Verification attestations: a record of a successful `verify` that can be
+//! handed to auditors and checked again against the chain later.
+//!
+//! Module hashes are SHA-256 over the on-chain bytecode with the package's
+//! self addresses zeroed, so the attestation alone is enough to re-check the
+//! deployed package. The source hash covers the inputs of the build:
+//! it is the SHA-256 of `sha256sum`-style lines (`<hash>  <path>\n`) for
+//! every `.move` file under `sources/` plus `Move.toml` and `Move.lock`,
+//! sorted by relative path.
+
+use crate::error::{Result, SuiForgeError};
+use crate::manifest::MANIFEST_FILE;
+use crate::move_lock::MOVE_LOCK_FILE;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const SCHEMA_VERSION: u32 = 1;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Attestation {
+    pub schema_version: u32,
+    pub generated_at: String,
+    pub package_id: String,
+    pub network: String,
+    pub rpc: String,
+    /// Addresses zeroed in the bytecode before hashing.
+    pub self_addresses: Vec<String>,
+    pub modules: Vec<ModuleHash>,
+    /// The package's on-chain linkage at the time of verification.
+    pub dependencies: Vec<LinkedDependency>,
+    pub source_hash: String,
+    pub sources: Vec<SourceFile>,
+    pub compiler_version: String,
+    pub move_lock_compiler_version: Option<String>,
+    pub suiforge_version: String,
+    pub git_commit: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ModuleHash {
+    pub name: String,
+    pub sha256: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LinkedDependency {
+    pub name: String,
+    pub original_id: String,
+    pub linked_id: String,
+    pub linked_version: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SourceFile {
+    pub path: String,
+    pub sha256: String,
+}
+
+impl Attestation {
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path).map_err(|e| {
+            SuiForgeError::Custom(format!("Could not read {}: {}", path.display(), e))
+        })?;
+        let attestation: Attestation = serde_json::from_str(&content)?;
+        if attestation.schema_version > SCHEMA_VERSION {
+            return Err(SuiForgeError::Custom(format!(
+                "{} uses attestation schema {}, newer than this SuiForge supports ({})",
+                path.display(),
+                attestation.schema_version,
+                SCHEMA_VERSION
+            )));
+        }
+        Ok(attestation)
+    }
+
+    /// Write the attestation as `<path>.json` and `<path>.html`, returning
+    /// both paths.
+    pub fn write(&self, path: &Path) -> Result<(PathBuf, PathBuf)> {
+        let (json_path, html_path) = output_paths(path);
+        if let Some(parent) = json_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&json_path, serde_json::to_string_pretty(self)? + "\n")?;
+        fs::write(&html_path, self.to_html())?;
+        Ok((json_path, html_path))
+    }
+
+    fn to_html(&self) -> String {
+        let row = |cells: &[&str]| {
+            let cells: String = cells
+                .iter()
+                .map(|c| format!("<td>{}</td>", escape(c)))
+                .collect();
+            format!("            <tr>{}</tr>\n", cells)
+        };
+
+        let modules: String = self
+            .modules
+            .iter()
+            .map(|m| row(&[&m.name, &m.sha256]))
+            .collect();
+        let dependencies: String = self
+            .dependencies
+            .iter()
+            .map(|d| {
+                row(&[
+                    &d.name,
+                    &d.original_id,
+                    &d.linked_id,
+                    &d.linked_version.to_string(),
+                ])
+            })
+            .collect();
+        let sources: String = self
+            .sources
+            .iter()
+            .map(|s| row(&[&s.path, &s.sha256]))
+            .collect();
+        let summary: String = [
+            ("Package ID", self.package_id.as_str()),
+            ("Network", self.network.as_str()),
+            ("RPC", self.rpc.as_str()),
+            ("Source hash", self.source_hash.as_str()),
+            ("Compiler", self.compiler_version.as_str()),
+            (
+                "Move.lock compiler",
+                self.move_lock_compiler_version.as_deref().unwrap_or("-"),
+            ),
+            ("SuiForge", self.suiforge_version.as_str()),
+            ("Git commit", self.git_commit.as_deref().unwrap_or("-")),
+            ("Generated", self.generated_at.as_str()),
+        ]
+        .iter()
+        .map(|(label, value)| row(&[label, value]))
+        .collect();
+
+        format!(
+            r#"<!DOCTYPE html>
+<html>
+<head>
+    <title>SuiForge Verification Attestation</title>
+    <style>
+        body {{ font-family: system-ui; margin: 20px; background: #f5f5f5; }}
+        .container {{ max-width: 1200px; margin: 0 auto; background: white; padding: 30px; border-radius: 8px; }}
+        h1 {{ color: #333; }}
+        .verified {{ color: #28a745; font-weight: 600; }}
+        table {{ width: 100%; border-collapse: collapse; margin: 20px 0; }}
+        th, td {{ padding: 12px; text-align: left; border-bottom: 1px solid #ddd; font-family: monospace; }}
+        th {{ background: #f8f9fa; font-weight: 600; font-family: system-ui; }}
+    </style>
+</head>
+<body>
+    <div class="container">
+        <h1>Verification Attestation</h1>
+        <p class="verified">✓ All modules match the deployed bytecode</p>
+
+        <table>
+{summary}        </table>
+
+        <h2>Modules</h2>
+        <table>
+            <tr><th>Module</th><th>Bytecode SHA-256</th></tr>
+{modules}        </table>
+
+        <h2>Dependencies</h2>
+        <table>
+            <tr><th>Dependency</th><th>Original ID</th><th>Linked ID</th><th>Version</th></tr>
+{dependencies}        </table>
+
+        <h2>Sources</h2>
+        <table>
+            <tr><th>File</th><th>SHA-256</th></tr>
+{sources}        </table>
+    </div>
+</body>
+</html>
+"#
+        )
+    }
+}
+
+/// Hash every build input under `root`, sorted by relative path.
+pub fn source_files(root: &Path) -> Result<Vec<SourceFile>> {
+    let mut paths: Vec<PathBuf> = walkdir::WalkDir::new(root.join("sources"))
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().extension().is_some_and(|ext| ext == "move"))
+        .map(|e| e.into_path())
+        .collect();
+    for file in [MANIFEST_FILE, MOVE_LOCK_FILE] {
+        let path = root.join(file);
+        if path.exists() {
+            paths.push(path);
+        }
+    }
+
+    let mut files = Vec::new();
+    for path in paths {
+        let relative = path.strip_prefix(root).unwrap_or(&path);
+        let relative: Vec<String> = relative
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy().to_string())
+            .collect();
+        files.push(SourceFile {
+            path: relative.join("/"),
+            sha256: hex::encode(Sha256::digest(fs::read(&path)?)),
+        });
+    }
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(files)
+}
+
+/// The combined hash of `files`, which must be sorted by path.
+pub fn source_hash(files: &[SourceFile]) -> String {
+    let mut hasher = Sha256::new();
+    for file in files {
+        hasher.update(format!("{}  {}\n", file.sha256, file.path));
+    }
+    hex::encode(hasher.finalize())
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// `<path>.json` and `<path>.html`. A `.json` or `.html` suffix on `path` is
+/// dropped first; any other dot in the name (`v1.2`) is kept.
+fn output_paths(path: &Path) -> (PathBuf, PathBuf) {
+    let base = match path.extension().and_then(|e| e.to_str()) {
+        Some("json" | "html") => path.with_extension(""),
+        _ => path.to_path_buf(),
+    };
+    let with_suffix = |suffix: &str| {
+        let mut path = base.clone().into_os_string();
+        path.push(suffix);
+        PathBuf::from(path)
+    };
+    (with_suffix(".json"), with_suffix(".html"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn output_paths_append_to_the_base_name() {
+        let paths = |p: &str| {
+            let (json, html) = output_paths(Path::new(p));
+            (json.display().to_string(), html.display().to_string())
+        };
+        let both = |base: &str| (format!("{}.json", base), format!("{}.html", base));
+
+        assert_eq!(paths("out/attestation"), both("out/attestation"));
+        assert_eq!(paths("out/attestation.json"), both("out/attestation"));
+        assert_eq!(paths("out/attestation.html"), both("out/attestation"));
+        assert_eq!(paths("release-v1.2"), both("release-v1.2"));
+        assert_eq!(paths("report.txt"), both("report.txt"));
+    }
+}
@@ -1,3 +1,4 @@
+use crate::config::GasBudget;
 use clap::{Parser, Subcommand};
 
 #[derive(Parser)]
@@ -48,16 +49,70 @@ pub enum Commands {
 
     /// Deploy contracts to network
     Deploy {
-        /// Target network (devnet, testnet, mainnet)
-        network: String,
+        /// Target network or profile name (defaults to the active profile)
+        network: Option<String>,
 
-        /// Gas budget for deployment
+        /// Gas budget for deployment in MIST, or "auto" to derive it from a dry run
         #[arg(short, long)]
-        gas_budget: Option<u64>,
+        gas_budget: Option<GasBudget>,
 
         /// Skip post-deployment verification
         #[arg(long)]
         skip_verify: bool,
+
+        /// Simulate the publish and report gas costs without submitting it
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Upgrade the deployed package using its recorded UpgradeCap
+    Upgrade {
+        /// Target network or profile name (defaults to the active profile)
+        network: Option<String>,
+
+        /// Gas budget for the upgrade in MIST, or "auto" to derive it from a dry run
+        #[arg(short, long)]
+        gas_budget: Option<GasBudget>,
+    },
+
+    /// Check local sources for upgrade compatibility with the deployed package
+    ///
+    /// The local ABI comes from parsing the files under sources/, not from the
+    /// build output, so code that does not compile is not caught here.
+    Compat {
+        /// Network or profile name (defaults to the active profile)
+        network: Option<String>,
+
+        /// Deployed package to compare against (defaults to the lock file entry)
+        #[arg(short, long)]
+        package: Option<String>,
+
+        /// Upgrade policy (compatible, additive, dep_only); defaults to the UpgradeCap's
+        #[arg(long)]
+        policy: Option<String>,
+
+        /// Also fail on changes that could not be checked (unresolved types)
+        #[arg(long)]
+        strict: bool,
+
+        /// Output format (text, json)
+        #[arg(short, long, default_value = "text")]
+        format: String,
+    },
+
+    /// Show deployments recorded in suiforge.lock.json
+    Deployments {
+        /// Action (list, show)
+        #[arg(default_value = "list")]
+        action: String,
+
+        /// Network to show (defaults to the only or active network)
+        #[arg(short, long)]
+        network: Option<String>,
+
+        /// Output format (text, json)
+        #[arg(short, long, default_value = "text")]
+        format: String,
     },
 
     /// Generate client SDKs
@@ -68,6 +123,15 @@ pub enum Commands {
         /// Output directory
         #[arg(short, long)]
         output: Option<String>,
+
+        /// Read the ABI from a JSON file (e.g. `inspect package --format json`)
+        /// instead of the project's sources
+        #[arg(long)]
+        abi: Option<String>,
+
+        /// Network whose deployment the SDK calls (defaults to the active profile)
+        #[arg(short, long)]
+        network: Option<String>,
     },
 
     /// Manage local Sui node
@@ -99,11 +163,20 @@ pub enum Commands {
     /// Verify deployed contract
     Verify {
         /// Package ID to verify
-        package_id: String,
+        #[arg(required_unless_present = "attestation")]
+        package_id: Option<String>,
+
+        /// Network or profile to verify on (defaults to the active profile)
+        #[arg(short, long)]
+        network: Option<String>,
 
-        /// Network to verify on
-        #[arg(short, long, default_value = "devnet")]
-        network: String,
+        /// Write a JSON and HTML attestation to this path on success
+        #[arg(long, conflicts_with = "attestation")]
+        report: Option<String>,
+
+        /// Re-check the chain against a previously written attestation
+        #[arg(long)]
+        attestation: Option<String>,
     },
 
     /// Profile gas usage
@@ -147,16 +220,108 @@ pub enum Commands {
 
     /// Inspect contract state
     Inspect {
-        /// Object ID to inspect
-        object_id: String,
+        /// Object ID to inspect, `tx` to inspect a transaction, or `package` for a package ABI
+        target: String,
+
+        /// Transaction digest with `tx`; package ID with `package` (omit for the local sources)
+        id: Option<String>,
 
-        /// Network
-        #[arg(short, long, default_value = "devnet")]
-        network: String,
+        /// Network or profile name (defaults to the active profile)
+        #[arg(short, long)]
+        network: Option<String>,
 
         /// Output format (text, json, tree)
         #[arg(short, long, default_value = "tree")]
         format: String,
+
+        /// Levels of dynamic fields and Table/Bag entries to expand (0 to disable)
+        #[arg(long, default_value_t = 1)]
+        depth: usize,
+
+        /// Maximum entries to list per object, table or bag, or versions with --history
+        #[arg(long, default_value_t = 50)]
+        limit: usize,
+
+        /// List past versions and the transactions that produced them
+        #[arg(long, conflicts_with = "diff")]
+        history: bool,
+
+        /// Diff the fields of two versions, e.g. 12..15 (omit the end for the current version)
+        #[arg(long, value_name = "V1..V2")]
+        diff: Option<String>,
+    },
+
+    /// Query events, or follow new ones as they are emitted
+    Events {
+        /// Network or profile name (defaults to the active profile)
+        #[arg(short, long)]
+        network: Option<String>,
+
+        /// Only events emitted by this package
+        #[arg(long)]
+        package: Option<String>,
+
+        /// Only events emitted by this module (of --package, or of the deployed project)
+        #[arg(short, long)]
+        module: Option<String>,
+
+        /// Only events of this type, e.g. 0x..::nft::MintEvent, or nft::MintEvent in a project
+        #[arg(long = "type", value_name = "TYPE")]
+        event_type: Option<String>,
+
+        /// Only events from transactions sent by this address
+        #[arg(long)]
+        sender: Option<String>,
+
+        /// Only events emitted by this transaction
+        #[arg(long, value_name = "DIGEST")]
+        tx: Option<String>,
+
+        /// Keep polling for new events, resuming from the last one seen
+        #[arg(long)]
+        follow: bool,
+
+        /// Seconds between polls with --follow
+        #[arg(long, default_value_t = 2)]
+        interval: u64,
+
+        /// Number of recent events to show (default 20; required without a filter)
+        #[arg(long)]
+        limit: Option<usize>,
+
+        /// Output format (text, json, ndjson)
+        #[arg(short, long, default_value = "text")]
+        format: String,
+    },
+
+    /// List the objects and coin balances an address owns
+    Objects {
+        /// Address to list (defaults to the active sui address)
+        address: Option<String>,
+
+        /// Network or profile name (defaults to the active profile)
+        #[arg(short, long)]
+        network: Option<String>,
+
+        /// Only objects of this struct type, e.g. 0x2::coin::Coin
+        #[arg(long = "type", value_name = "TYPE")]
+        struct_type: Option<String>,
+
+        /// Only objects whose type is defined in this package
+        #[arg(long)]
+        package: Option<String>,
+
+        /// Group objects by type
+        #[arg(short, long)]
+        group: bool,
+
+        /// Maximum number of objects to list
+        #[arg(long, default_value_t = 500)]
+        limit: usize,
+
+        /// Output format (text, tree, json, yaml)
+        #[arg(short, long, default_value = "text")]
+        format: String,
     },
 
     /// Generate test coverage report
@@ -0,0 +1,306 @@
+//! BCS layouts of Move types, shared by the SDK generators.
+//!
+//! A layout describes how a value is encoded, which is all a decoder needs.
+//! Framework wrappers collapse to what they hold where the bytes allow it:
+//! `Balance<T>` is a `u64`, `ID` an address and `Url` a string, matching the
+//! JSON the fullnode renders for them.
+
+use super::{is_struct, PackageInterface};
+use crate::abi::same_address;
+use crate::rpc::types::{NormalizedModule, NormalizedStruct, NormalizedType};
+use std::collections::BTreeSet;
+
+pub enum Layout {
+    Bool,
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    U256,
+    Address,
+    String,
+    Vector(Box<Layout>),
+    Option(Box<Layout>),
+    /// A framework struct with a fixed layout, e.g. `UID` or `Table`.
+    Framework {
+        name: &'static str,
+        fields: Vec<(&'static str, Layout)>,
+    },
+    /// A struct of the package.
+    Struct {
+        module: String,
+        name: String,
+        type_arguments: Vec<TypeArgument>,
+    },
+    /// A type parameter of the enclosing struct.
+    Param(u16),
+}
+
+pub struct TypeArgument {
+    /// Phantom arguments need no layout, and may have none.
+    pub layout: Option<Layout>,
+    pub is_phantom: bool,
+}
+
+/// The layout of `ty`, or `None` if it holds a type whose layout is not
+/// known, such as a struct from another package.
+pub fn layout(interface: &PackageInterface, ty: &NormalizedType) -> Option<Layout> {
+    Some(match ty {
+        NormalizedType::Bool => Layout::Bool,
+        NormalizedType::U8 => Layout::U8,
+        NormalizedType::U16 => Layout::U16,
+        NormalizedType::U32 => Layout::U32,
+        NormalizedType::U64 => Layout::U64,
+        NormalizedType::U128 => Layout::U128,
+        NormalizedType::U256 => Layout::U256,
+        NormalizedType::Address => Layout::Address,
+        NormalizedType::Vector(inner) => Layout::Vector(Box::new(layout(interface, inner)?)),
+        NormalizedType::TypeParameter(i) => Layout::Param(*i),
+        NormalizedType::Signer
+        | NormalizedType::Reference(_)
+        | NormalizedType::MutableReference(_) => return None,
+        NormalizedType::Struct {
+            address,
+            module,
+            name,
+            type_arguments,
+        } => {
+            if let Some(framework) = framework_layout(interface, ty, type_arguments) {
+                return framework;
+            }
+            if !same_address(address, interface.type_address()) {
+                return None;
+            }
+            let def = interface.modules.get(module)?.structs.get(name)?;
+            if !def
+                .fields
+                .iter()
+                .all(|field| layout(interface, &field.type_).is_some())
+            {
+                return None;
+            }
+            let type_arguments = type_arguments
+                .iter()
+                .zip(&def.type_parameters)
+                .map(|(arg, param)| {
+                    let layout = layout(interface, arg);
+                    if layout.is_none() && !param.is_phantom {
+                        return None;
+                    }
+                    Some(TypeArgument {
+                        layout,
+                        is_phantom: param.is_phantom,
+                    })
+                })
+                .collect::<Option<Vec<_>>>()?;
+            Layout::Struct {
+                module: module.clone(),
+                name: name.clone(),
+                type_arguments,
+            }
+        }
+    })
+}
+
+/// Layouts of every field of `def`, or `None` if one is unknown.
+pub fn field_layouts(interface: &PackageInterface, def: &NormalizedStruct) -> Option<Vec<Layout>> {
+    def.fields
+        .iter()
+        .map(|field| layout(interface, &field.type_))
+        .collect()
+}
+
+/// Structs that can be events: `copy` and `drop` but not objects, and
+/// decodable without type arguments.
+pub fn event_structs(interface: &PackageInterface) -> Vec<(&str, &str)> {
+    let mut events = Vec::new();
+    for (module_name, module) in &interface.modules {
+        for (name, def) in &module.structs {
+            let abilities = &def.abilities.abilities;
+            let is_event = abilities.iter().any(|a| a == "Copy")
+                && abilities.iter().any(|a| a == "Drop")
+                && !abilities.iter().any(|a| a == "Key")
+                && def.type_parameters.iter().all(|p| p.is_phantom)
+                && field_layouts(interface, def).is_some();
+            if is_event {
+                events.push((module_name.as_str(), name.as_str()));
+            }
+        }
+    }
+    events
+}
+
+/// Layouts of the framework types SDKs commonly hold. The outer `Option` is
+/// `None` for any other type; the inner one for a known type whose
+/// arguments have no layout.
+fn framework_layout(
+    interface: &PackageInterface,
+    ty: &NormalizedType,
+    type_arguments: &[NormalizedType],
+) -> Option<Option<Layout>> {
+    let arg = |i: usize| type_arguments.get(i).and_then(|t| layout(interface, t));
+    let std = |module, name| is_struct(ty, "0x1", module, name);
+    let sui = |module, name| is_struct(ty, "0x2", module, name);
+
+    if std("string", "String") || std("ascii", "String") || sui("url", "Url") {
+        return Some(Some(Layout::String));
+    }
+    if sui("object", "ID") {
+        return Some(Some(Layout::Address));
+    }
+    if sui("object", "UID") {
+        return Some(Some(uid()));
+    }
+    if sui("balance", "Balance") {
+        return Some(Some(Layout::U64));
+    }
+    if std("option", "Option") {
+        return Some(arg(0).map(|inner| Layout::Option(Box::new(inner))));
+    }
+    if sui("coin", "Coin") {
+        return Some(Some(Layout::Framework {
+            name: "Coin",
+            fields: vec![("id", uid()), ("balance", Layout::U64)],
+        }));
+    }
+    for (module, name) in [
+        ("table", "Table"),
+        ("bag", "Bag"),
+        ("object_table", "ObjectTable"),
+        ("object_bag", "ObjectBag"),
+    ] {
+        if sui(module, name) {
+            return Some(Some(Layout::Framework {
+                name,
+                fields: vec![("id", uid()), ("size", Layout::U64)],
+            }));
+        }
+    }
+    if sui("vec_set", "VecSet") {
+        return Some(arg(0).map(|key| Layout::Framework {
+            name: "VecSet",
+            fields: vec![("contents", Layout::Vector(Box::new(key)))],
+        }));
+    }
+    if sui("vec_map", "VecMap") {
+        return Some(arg(0).zip(arg(1)).map(|(key, value)| {
+            let entry = Layout::Framework {
+                name: "Entry",
+                fields: vec![("key", key), ("value", value)],
+            };
+            Layout::Framework {
+                name: "VecMap",
+                fields: vec![("contents", Layout::Vector(Box::new(entry)))],
+            }
+        }));
+    }
+    None
+}
+
+impl Layout {
+    /// Whether values of this layout can be passed as pure arguments.
+    pub fn is_pure(&self) -> bool {
+        match self {
+            Layout::Vector(inner) | Layout::Option(inner) => inner.is_pure(),
+            Layout::Framework { .. } | Layout::Struct { .. } | Layout::Param(_) => false,
+            _ => true,
+        }
+    }
+}
+
+fn uid() -> Layout {
+    Layout::Framework {
+        name: "UID",
+        fields: vec![("id", Layout::Address)],
+    }
+}
+
+/// The module's structs, each after the structs of the same module it
+/// holds, so generated definitions can refer to earlier ones.
+pub fn struct_order(module: &NormalizedModule) -> Vec<&str> {
+    fn visit<'a>(
+        module: &'a NormalizedModule,
+        name: &'a str,
+        done: &mut BTreeSet<&'a str>,
+        order: &mut Vec<&'a str>,
+    ) {
+        if !done.insert(name) {
+            return;
+        }
+        if let Some(def) = module.structs.get(name) {
+            for field in &def.fields {
+                for dependency in local_structs(module, &field.type_) {
+                    visit(module, dependency, done, order);
+                }
+            }
+        }
+        order.push(name);
+    }
+
+    let mut done = BTreeSet::new();
+    let mut order = Vec::new();
+    for name in module.structs.keys() {
+        visit(module, name, &mut done, &mut order);
+    }
+    order
+}
+
+/// Structs of `module` that `ty` mentions.
+fn local_structs<'a>(module: &'a NormalizedModule, ty: &NormalizedType) -> Vec<&'a str> {
+    match ty {
+        NormalizedType::Vector(inner)
+        | NormalizedType::Reference(inner)
+        | NormalizedType::MutableReference(inner) => local_structs(module, inner),
+        NormalizedType::Struct {
+            address,
+            module: owner,
+            name,
+            type_arguments,
+        } => {
+            let mut found: Vec<&str> = type_arguments
+                .iter()
+                .flat_map(|arg| local_structs(module, arg))
+                .collect();
+            if *owner == module.name && same_address(address, &module.address) {
+                if let Some((name, _)) = module.structs.get_key_value(name) {
+                    found.push(name);
+                }
+            }
+            found
+        }
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codegen::tests::shop_interface;
+
+    #[test]
+    fn field_layouts_are_known_only_for_package_and_framework_types() {
+        let interface = shop_interface();
+        let structs = &interface.modules["shop"].structs;
+
+        let bought = field_layouts(&interface, &structs["Bought"]).unwrap();
+        assert!(matches!(
+            bought.as_slice(),
+            [Layout::Address, Layout::U64, Layout::Vector(item)] if matches!(**item, Layout::U8)
+        ));
+        let shop = field_layouts(&interface, &structs["Shop"]).unwrap();
+        assert!(matches!(shop[0], Layout::Framework { name: "UID", .. }));
+        let boxed = field_layouts(&interface, &structs["Boxed"]).unwrap();
+        assert!(matches!(boxed.as_slice(), [Layout::Param(0)]));
+        assert!(field_layouts(&interface, &structs["Foreign"]).is_none());
+    }
+
+    #[test]
+    fn events_are_copy_drop_structs_decodable_without_type_arguments() {
+        let interface = shop_interface();
+        assert_eq!(
+            event_structs(&interface),
+            [("shop", "Bought"), ("shop", "Tagged")]
+        );
+    }
+}
@@ -1,9 +1,368 @@
+pub mod bcs;
+pub mod python;
+pub mod rust;
+pub mod swift;
 pub mod typescript;
 
-use crate::error::Result;
+use crate::abi::{self, PackageAbi};
+use crate::config::resolve_network;
+use crate::error::{Result, SuiForgeError};
+use crate::lock::{LockFile, NetworkDeployment};
+use crate::manifest::Manifest;
+use crate::move_syntax;
+use crate::rpc::types::{NormalizedFunction, NormalizedType};
+use crate::utils;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs;
 use std::path::Path;
 
 #[allow(dead_code)]
 pub trait CodeGenerator {
     fn generate(&self, project_root: &Path, output_dir: &str) -> Result<()>;
 }
+
+/// Everything the SDK generators need to know about a package.
+pub struct PackageInterface {
+    /// The package calls go to, if it is deployed.
+    pub package_id: Option<String>,
+    /// Every deployment recorded in the lock file, by network.
+    pub deployments: BTreeMap<String, NetworkDeployment>,
+    pub modules: PackageAbi,
+    /// Parameter names by `module::function`. Normalized modules carry no
+    /// names, so these are only known when generating from sources.
+    pub param_names: BTreeMap<String, Vec<String>>,
+    /// Doc comments by `module::item`.
+    pub docs: BTreeMap<String, String>,
+}
+
+impl PackageInterface {
+    /// Read the interface from the project's sources. The package ID and the
+    /// address types are generated with come from the deployment on
+    /// `network` (the active profile by default), if there is one.
+    pub fn from_sources(root: &Path, network: Option<&str>) -> Result<Self> {
+        let deployments = LockFile::load(root)?.networks;
+        // An explicit network must resolve; without one, a broken profile
+        // only costs the deployed package ID.
+        let network = match network {
+            Some(name) => Some(resolve_network(Some(name))?),
+            None => match resolve_network(None) {
+                Ok(network) => Some(network),
+                Err(e) => {
+                    utils::warning(&format!(
+                        "Could not resolve the active network, generating without a package ID: {}",
+                        e
+                    ));
+                    None
+                }
+            },
+        };
+        let deployment = network.and_then(|network| deployments.get(&network.name).cloned());
+
+        let package = move_syntax::Package::load(&root.join("sources"))?;
+        if let Some(error) = package.errors.first() {
+            return Err(SuiForgeError::MoveParse(error.clone()));
+        }
+        let addresses = Manifest::load(root)?.addresses()?;
+        Ok(Self::from_package(
+            &package,
+            &addresses,
+            deployment,
+            deployments,
+        ))
+    }
+
+    /// The interface of parsed sources, typed at the original package ID of
+    /// `deployment`, or `0x0` when not deployed.
+    fn from_package(
+        package: &move_syntax::Package,
+        addresses: &BTreeMap<String, String>,
+        deployment: Option<NetworkDeployment>,
+        deployments: BTreeMap<String, NetworkDeployment>,
+    ) -> Self {
+        let self_address = deployment
+            .as_ref()
+            .map(|d| d.original_package_id.as_str())
+            .unwrap_or("0x0");
+        let modules = abi::from_source(package, addresses, self_address);
+
+        let mut param_names = BTreeMap::new();
+        let mut docs = BTreeMap::new();
+        for module in &package.modules {
+            for function in &module.functions {
+                let key = format!("{}::{}", module.name, function.name);
+                param_names.insert(
+                    key.clone(),
+                    function.params.iter().map(|p| p.name.clone()).collect(),
+                );
+                if let Some(doc) = &function.doc {
+                    docs.insert(key, doc.clone());
+                }
+            }
+            for def in &module.structs {
+                if let Some(doc) = &def.doc {
+                    docs.insert(format!("{}::{}", module.name, def.name), doc.clone());
+                }
+            }
+        }
+
+        Self {
+            package_id: deployment.map(|d| d.package_id),
+            deployments,
+            modules,
+            param_names,
+            docs,
+        }
+    }
+
+    /// Read the interface from an ABI file, either the output of `suiforge
+    /// inspect package --format json` or a raw
+    /// `sui_getNormalizedMoveModulesByPackage` result.
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path).map_err(|e| {
+            SuiForgeError::CodegenFailed(format!("Could not read {}: {}", path.display(), e))
+        })?;
+        let value: Value = serde_json::from_str(&content)?;
+        let (package_id, modules) = match value.get("modules") {
+            Some(modules) => (
+                value
+                    .get("packageId")
+                    .and_then(|id| id.as_str())
+                    .map(str::to_string),
+                serde_json::from_value(modules.clone())?,
+            ),
+            None => (None, serde_json::from_value(value)?),
+        };
+        Ok(Self {
+            package_id,
+            deployments: BTreeMap::new(),
+            modules,
+            param_names: BTreeMap::new(),
+            docs: BTreeMap::new(),
+        })
+    }
+
+    /// The address the package's types live at.
+    pub fn type_address(&self) -> &str {
+        self.modules
+            .values()
+            .next()
+            .map(|m| m.address.as_str())
+            .unwrap_or("0x0")
+    }
+
+    /// Names for the parameters of a function, `arg0`, `arg1`... when unknown.
+    pub fn param_names(&self, module: &str, function: &str, count: usize) -> Vec<String> {
+        match self.param_names.get(&format!("{}::{}", module, function)) {
+            Some(names) if names.len() == count => names.clone(),
+            _ => (0..count).map(|i| format!("arg{}", i)).collect(),
+        }
+    }
+
+    pub fn doc(&self, module: &str, item: &str) -> Option<&str> {
+        self.docs
+            .get(&format!("{}::{}", module, item))
+            .map(String::as_str)
+    }
+}
+
+/// Functions a client can call: public and entry ones.
+pub fn callable_functions(
+    functions: &BTreeMap<String, NormalizedFunction>,
+) -> impl Iterator<Item = (&String, &NormalizedFunction)> {
+    functions
+        .iter()
+        .filter(|(_, f)| f.visibility == "Public" || f.is_entry)
+}
+
+/// Whether `ty` is the `TxContext` the runtime passes in.
+pub fn is_tx_context(ty: &NormalizedType) -> bool {
+    let inner = match ty {
+        NormalizedType::Reference(inner) | NormalizedType::MutableReference(inner) => inner,
+        _ => return false,
+    };
+    is_struct(inner, "0x2", "tx_context", "TxContext")
+}
+
+/// Whether `ty` is the struct `address::module::name`, with any type
+/// arguments.
+pub fn is_struct(ty: &NormalizedType, address: &str, module: &str, name: &str) -> bool {
+    matches!(
+        ty,
+        NormalizedType::Struct { address: a, module: m, name: n, .. }
+            if abi::same_address(a, address) && m == module && n == name
+    )
+}
+
+/// Shared system objects that have a fixed ID, so callers need not pass them.
+pub fn system_object(ty: &NormalizedType) -> Option<&'static str> {
+    let inner = match ty {
+        NormalizedType::Reference(inner) | NormalizedType::MutableReference(inner) => inner,
+        _ => return None,
+    };
+    [
+        ("clock", "Clock", "0x6"),
+        ("random", "Random", "0x8"),
+        ("deny_list", "DenyList", "0x403"),
+    ]
+    .iter()
+    .find(|(module, name, _)| is_struct(inner, "0x2", module, name))
+    .map(|(_, _, id)| *id)
+}
+
+/// `mint_nft` as `mintNft`.
+pub fn camel_case(name: &str) -> String {
+    let pascal = pascal_case(name);
+    let mut chars = pascal.chars();
+    match chars.next() {
+        Some(first) => first.to_lowercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// `mint_nft` as `MintNft`.
+pub fn pascal_case(name: &str) -> String {
+    name.split('_')
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect::<String>(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+/// `MintNft` as `mint_nft`.
+pub fn snake_case(name: &str) -> String {
+    let mut out = String::new();
+    for (i, c) in name.chars().enumerate() {
+        if c.is_uppercase() {
+            if i > 0 && !out.ends_with('_') {
+                out.push('_');
+            }
+            out.extend(c.to_lowercase());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// `<A, B>`, or nothing for an empty list.
+pub fn type_list(items: &[String]) -> String {
+    if items.is_empty() {
+        String::new()
+    } else {
+        format!("<{}>", items.join(", "))
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// A package exercising builders, event layouts and name escaping.
+    const SHOP: &str = r#"module shop::shop {
+    use std::string::String;
+    use sui::balance::Balance;
+    use sui::coin::Coin;
+    use sui::sui::SUI;
+    use sui::vec_map::VecMap;
+    use sui::vec_set::VecSet;
+
+    /// A shop selling items.
+    public struct Shop has key {
+        id: UID,
+        price: u64,
+        owner: address,
+    }
+
+    /// Emitted on every sale.
+    public struct Bought has copy, drop {
+        buyer: address,
+        price: u64,
+        item: vector<u8>,
+    }
+
+    public struct Boxed<T: store> has copy, drop {
+        value: T,
+    }
+
+    public struct Tagged<phantom T> has copy, drop {
+        tag: Option<u8>,
+    }
+
+    public struct Foreign has copy, drop {
+        witness: SUI,
+    }
+
+    public struct Ledger has store {
+        name: String,
+        total: u256,
+        funds: Balance<SUI>,
+        entries: VecMap<address, Boxed<u64>>,
+        flags: VecSet<bool>,
+        last: Option<Tagged<SUI>>,
+    }
+
+    /// Buy an item.
+    public fun buy(shop: &mut Shop, payment: Coin<SUI>, type: u8, ctx: &mut TxContext) {
+        abort 0
+    }
+
+    public fun set_price(shop: &mut Shop, in: u64, from: vector<u8>) {
+        abort 0
+    }
+
+    entry fun delete(shop: Shop, clock: &sui::clock::Clock) {
+        abort 0
+    }
+
+    fun internal() {}
+}
+"#;
+
+    /// The interface of [`SHOP`], deployed at `0x5`.
+    pub(crate) fn shop_interface() -> PackageInterface {
+        let package = move_syntax::Package {
+            modules: move_syntax::parse_source(Path::new("sources/shop.move"), SHOP).unwrap(),
+            errors: Vec::new(),
+        };
+        let mut interface =
+            PackageInterface::from_package(&package, &BTreeMap::new(), None, BTreeMap::new());
+        interface.package_id = Some("0x5".to_string());
+        interface
+    }
+
+    #[test]
+    fn interface_keeps_callable_functions_names_and_docs() {
+        let interface = shop_interface();
+        let module = &interface.modules["shop"];
+
+        let callable: Vec<_> = callable_functions(&module.exposed_functions)
+            .map(|(name, _)| name.as_str())
+            .collect();
+        assert_eq!(callable, ["buy", "delete", "set_price"]);
+        assert_eq!(
+            interface.param_names("shop", "set_price", 3),
+            ["shop", "in", "from"]
+        );
+        assert_eq!(
+            interface.param_names("shop", "set_price", 2),
+            ["arg0", "arg1"]
+        );
+        assert_eq!(interface.doc("shop", "buy"), Some("Buy an item."));
+    }
+
+    #[test]
+    fn names_are_cased_and_type_lists_bracketed() {
+        assert_eq!(camel_case("set_price"), "setPrice");
+        assert_eq!(pascal_case("set__price_"), "SetPrice");
+        assert_eq!(snake_case("MintNft"), "mint_nft");
+        assert_eq!(snake_case("Pool_V2"), "pool_v2");
+        assert_eq!(type_list(&[]), "");
+        assert_eq!(type_list(&["A".to_string(), "B".to_string()]), "<A, B>");
+    }
+}
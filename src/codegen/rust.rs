@@ -0,0 +1,949 @@
+//! Rust SDK: a standalone crate with a serde/BCS struct for every Move
+//! struct, a call builder for every public or entry function, an enum of the
+//! package's events and the package IDs of every recorded deployment.
+//!
+//! Builders return a `MoveCall` holding BCS-encoded pure arguments and
+//! object IDs, which maps directly onto a programmable transaction, so the
+//! crate only depends on `serde` and `bcs`.
+
+use super::bcs::{event_structs, field_layouts, struct_order, Layout};
+use super::{
+    callable_functions, is_struct, is_tx_context, pascal_case, snake_case, system_object,
+    type_list, PackageInterface,
+};
+use crate::error::Result;
+use crate::rpc::types::{NormalizedFunction, NormalizedModule, NormalizedStruct, NormalizedType};
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+
+/// Rust keywords, which are escaped as raw identifiers.
+const KEYWORDS: [&str; 38] = [
+    "as", "async", "await", "box", "break", "const", "continue", "dyn", "else", "enum", "extern",
+    "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub",
+    "ref", "return", "static", "struct", "trait", "true", "type", "unsafe", "use", "where",
+    "while", "abstract", "final", "yield",
+];
+
+/// Modules and files of the generated crate itself, which Move modules must
+/// not shadow. A `main.rs` would also turn the crate into a binary.
+const CRATE_MODULES: [&str; 5] = ["events", "framework", "lib", "main", "networks"];
+
+pub fn generate(project_root: &Path, output_dir: &str, interface: &PackageInterface) -> Result<()> {
+    let output_path = project_root.join(output_dir);
+    fs::create_dir_all(&output_path)?;
+
+    // Generate Cargo.toml. The empty workspace keeps the crate standalone
+    // when the SDK directory sits inside another workspace.
+    let cargo_toml = r#"[package]
+name = "suiforge-sdk"
+version = "0.1.0"
+edition = "2021"
+description = "Auto-generated Rust SDK for Sui smart contracts"
+
+[dependencies]
+bcs = "0.1"
+serde = { version = "1", features = ["derive"] }
+
+[workspace]
+"#;
+    fs::write(output_path.join("Cargo.toml"), cargo_toml)?;
+
+    let src_path = output_path.join("src");
+    fs::create_dir_all(&src_path)?;
+
+    let events = events_rs(interface);
+    fs::write(src_path.join("lib.rs"), lib_rs(interface, events.is_some()))?;
+    fs::write(src_path.join("framework.rs"), FRAMEWORK_RS)?;
+    if let Some(events) = events {
+        fs::write(src_path.join("events.rs"), events)?;
+    }
+    for (name, module) in &interface.modules {
+        fs::write(
+            src_path.join(format!("{}.rs", module_file(name))),
+            module_rs(interface, module),
+        )?;
+    }
+
+    // Generate BCS round-trip tests
+    let tests_path = output_path.join("tests");
+    fs::create_dir_all(&tests_path)?;
+    for (name, module) in &interface.modules {
+        if let Some(test) = module_test_rs(interface, module) {
+            fs::write(tests_path.join(format!("{}.rs", module_file(name))), test)?;
+        }
+    }
+
+    // Generate README
+    let readme = r#"# SuiForge Rust SDK
+
+Auto-generated Rust SDK for interacting with your Sui smart contracts.
+
+## Usage
+
+Every Move module has a Rust module with a struct per Move struct and a
+builder per public or entry function. Builders return a `MoveCall` with the
+arguments already BCS-encoded; add it to a programmable transaction with the
+client of your choice:
+
+```rust
+use suiforge_sdk::{nft, CallArg};
+
+let mint = nft::mint("name", 10);
+let transfer = nft::transfer(CallArg::Result(0), "0x...".parse()?);
+```
+
+`TxContext`, `Clock`, `Random` and `DenyList` are filled in. Objects are
+passed by ID as `CallArg::Object`; look up their version, or whether they are
+shared, when building the transaction.
+
+Structs decode from the BCS contents of objects and events:
+
+```rust
+let nft = nft::Nft::from_bcs(&bytes)?;
+let event = suiforge_sdk::events::Event::decode(&event_type, &event_bcs)?;
+```
+
+`PACKAGE_ID` is the deployment the SDK was generated for; `networks` holds
+the IDs of every recorded deployment.
+
+`cargo test` round-trips a sample of every struct through BCS.
+
+## Development
+
+This SDK is auto-generated by SuiForge. To regenerate:
+
+```bash
+suiforge generate rust
+```
+"#;
+    fs::write(output_path.join("README.md"), readme)?;
+
+    Ok(())
+}
+
+const FRAMEWORK_RS: &str = r#"//! Auto-generated by SuiForge. Do not edit.
+//!
+//! Sui framework types and the Move call representation the generated
+//! modules share.
+
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+
+/// A 32-byte Sui address or object ID. It is hex in human-readable formats
+/// and raw bytes in BCS.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Address(pub [u8; 32]);
+
+impl Address {
+    /// The address of a system object such as the `Clock` (`0x6`).
+    pub const fn system(id: u16) -> Self {
+        let mut bytes = [0u8; 32];
+        bytes[30] = (id >> 8) as u8;
+        bytes[31] = id as u8;
+        Self(bytes)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AddressError(pub String);
+
+impl fmt::Display for AddressError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid address: {}", self.0)
+    }
+}
+
+impl std::error::Error for AddressError {}
+
+impl FromStr for Address {
+    type Err = AddressError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let hex = s.strip_prefix("0x").unwrap_or(s);
+        if hex.is_empty() || hex.len() > 64 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(AddressError(s.to_string()));
+        }
+        let padded = format!("{:0>64}", hex);
+        let mut bytes = [0u8; 32];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&padded[2 * i..2 * i + 2], 16)
+                .map_err(|_| AddressError(s.to_string()))?;
+        }
+        Ok(Self(bytes))
+    }
+}
+
+impl fmt::Display for Address {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "0x")?;
+        for byte in &self.0 {
+            write!(f, "{:02x}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for Address {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl Serialize for Address {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_str(self)
+        } else {
+            self.0.serialize(serializer)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Address {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            String::deserialize(deserializer)?
+                .parse()
+                .map_err(de::Error::custom)
+        } else {
+            Ok(Self(<[u8; 32]>::deserialize(deserializer)?))
+        }
+    }
+}
+
+/// A Move `u256`, as little-endian bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct U256(pub [u8; 32]);
+
+impl From<u128> for U256 {
+    fn from(value: u128) -> Self {
+        let mut bytes = [0u8; 32];
+        bytes[..16].copy_from_slice(&value.to_le_bytes());
+        Self(bytes)
+    }
+}
+
+/// `sui::object::UID`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UID {
+    pub id: Address,
+}
+
+/// `sui::coin::Coin<T>`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Coin {
+    pub id: UID,
+    pub balance: u64,
+}
+
+/// `sui::table::Table<K, V>`; its entries are dynamic fields of `id`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Table {
+    pub id: UID,
+    pub size: u64,
+}
+
+pub type Bag = Table;
+pub type ObjectTable = Table;
+pub type ObjectBag = Table;
+
+/// `sui::vec_set::VecSet<K>`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VecSet<K> {
+    pub contents: Vec<K>,
+}
+
+/// `sui::vec_map::VecMap<K, V>`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VecMap<K, V> {
+    pub contents: Vec<Entry<K, V>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Entry<K, V> {
+    pub key: K,
+    pub value: V,
+}
+
+/// An argument of a Move call.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CallArg {
+    /// A BCS-encoded pure value.
+    Pure(Vec<u8>),
+    /// An object by ID. Its version, and whether it is shared, are looked up
+    /// when the transaction is built.
+    Object(Address),
+    /// Objects gathered into a vector with `MakeMoveVec`.
+    Objects(Vec<CallArg>),
+    /// The result of an earlier command.
+    Result(u16),
+    /// One of the results of an earlier command.
+    NestedResult(u16, u16),
+}
+
+impl From<Address> for CallArg {
+    fn from(id: Address) -> Self {
+        Self::Object(id)
+    }
+}
+
+/// A pure argument holding `value`.
+pub fn pure<T: Serialize + ?Sized>(value: &T) -> CallArg {
+    CallArg::Pure(bcs::to_bytes(value).expect("pure values serialize to BCS"))
+}
+
+/// A call to a function of the package.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MoveCall {
+    pub package: Address,
+    pub module: &'static str,
+    pub function: &'static str,
+    pub type_arguments: Vec<String>,
+    pub arguments: Vec<CallArg>,
+}
+
+impl MoveCall {
+    /// Call the function in another version of the package.
+    pub fn with_package(mut self, package: Address) -> Self {
+        self.package = package;
+        self
+    }
+}
+
+/// Whether `actual`, a type as the fullnode prints it, is the struct
+/// `address::module::name` with any type arguments.
+pub fn is_type(actual: &str, address: &str, module: &str, name: &str) -> bool {
+    let mut parts = actual.splitn(3, "::");
+    let (Some(a), Some(m), Some(n)) = (parts.next(), parts.next(), parts.next()) else {
+        return false;
+    };
+    let same_address = matches!(
+        (a.parse::<Address>(), address.parse::<Address>()),
+        (Ok(a), Ok(b)) if a == b
+    );
+    same_address && m == module && (n == name || n.starts_with(&format!("{}<", name)))
+}
+"#;
+
+fn lib_rs(interface: &PackageInterface, has_events: bool) -> String {
+    let mut out = String::from("//! Auto-generated by SuiForge. Do not edit.\n\n");
+    let mut modules: Vec<String> = interface.modules.keys().map(|m| module_ident(m)).collect();
+    if has_events {
+        modules.push("events".to_string());
+    }
+    modules.push("framework".to_string());
+    modules.sort();
+    for module in &modules {
+        out.push_str(&format!("pub mod {};\n", module));
+    }
+    out.push_str("\npub use framework::{Address, CallArg, MoveCall};\n\n");
+
+    let type_address = interface.type_address();
+    let (package_id, original_id) = match &interface.package_id {
+        Some(id) => (id.as_str(), type_address),
+        None => {
+            out.push_str(
+                "// The package is not deployed yet: regenerate after `suiforge deploy`, or call\n\
+                 // `MoveCall::with_package`.\n",
+            );
+            ("0x0", "0x0")
+        }
+    };
+    out.push_str("/// The package calls go to.\n");
+    out.push_str(&format!("pub const PACKAGE_ID: &str = \"{}\";\n\n", package_id));
+    out.push_str("/// The package's first version, which its types keep across upgrades.\n");
+    out.push_str(&format!(
+        "pub const ORIGINAL_PACKAGE_ID: &str = \"{}\";\n\n",
+        original_id
+    ));
+    out.push_str(
+        "pub fn package_id() -> Address {\n    PACKAGE_ID.parse().expect(\"PACKAGE_ID is an address\")\n}\n\n",
+    );
+
+    out.push_str("/// Package IDs of every recorded deployment.\npub mod networks {");
+    for (network, deployment) in &interface.deployments {
+        out.push_str(&format!(
+            "\n    pub mod {} {{\n        pub const PACKAGE_ID: &str = \"{}\";\n        pub const ORIGINAL_PACKAGE_ID: &str = \"{}\";\n    }}\n",
+            identifier(&network.replace('-', "_").to_lowercase()),
+            deployment.package_id,
+            deployment.original_package_id
+        ));
+    }
+    out.push_str("}\n");
+    out
+}
+
+fn events_rs(interface: &PackageInterface) -> Option<String> {
+    let events = event_structs(interface);
+    if events.is_empty() {
+        return None;
+    }
+    let variant = |module: &str, name: &str| format!("{}{}", pascal_case(module), name);
+
+    let mut out = String::from("//! Auto-generated by SuiForge. Do not edit.\n//!\n");
+    out.push_str("//! Events the package can emit: its `copy + drop` structs.\n\n");
+    out.push_str("use crate::framework::is_type;\n\n");
+    out.push_str("#[derive(Debug, Clone, PartialEq)]\npub enum Event {\n");
+    for (module, name) in &events {
+        out.push_str(&format!(
+            "    {}(crate::{}::{}),\n",
+            variant(module, name),
+            module_ident(module),
+            name
+        ));
+    }
+    out.push_str("}\n\nimpl Event {\n");
+    out.push_str(
+        "    /// Decode an event from its type and BCS contents, or return `None` if\n    /// the package does not define the type.\n",
+    );
+    out.push_str(
+        "    pub fn decode(event_type: &str, bcs: &[u8]) -> Result<Option<Self>, bcs::Error> {\n",
+    );
+    for (module, name) in &events {
+        out.push_str(&format!(
+            "        if is_type(event_type, crate::ORIGINAL_PACKAGE_ID, \"{}\", \"{}\") {{\n            return Ok(Some(Self::{}(bcs::from_bytes(bcs)?)));\n        }}\n",
+            module,
+            name,
+            variant(module, name)
+        ));
+    }
+    out.push_str("        Ok(None)\n    }\n}\n");
+    Some(out)
+}
+
+fn module_rs(interface: &PackageInterface, module: &NormalizedModule) -> String {
+    let ctx = Context {
+        interface,
+        module: &module.name,
+    };
+    let mut uses = BTreeSet::new();
+    let mut body = String::new();
+
+    for name in struct_order(module) {
+        body.push_str(&struct_rs(&ctx, name, &module.structs[name], &mut uses));
+    }
+    for (name, function) in callable_functions(&module.exposed_functions) {
+        body.push_str(&function_rs(&ctx, name, function, &mut uses));
+    }
+
+    let mut out = String::from("//! Auto-generated by SuiForge. Do not edit.\n");
+    let mut imports = Vec::new();
+    if !uses.is_empty() {
+        let names: Vec<&str> = uses.iter().copied().collect();
+        imports.push(if names.len() == 1 {
+            format!("use crate::framework::{};", names[0])
+        } else {
+            format!("use crate::framework::{{{}}};", names.join(", "))
+        });
+    }
+    if body.contains("#[derive(") {
+        imports.push("use serde::{Deserialize, Serialize};".to_string());
+    }
+    if !imports.is_empty() {
+        out.push('\n');
+        for import in imports {
+            out.push_str(&format!("{}\n", import));
+        }
+    }
+    out.push_str(&body);
+    out
+}
+
+struct Context<'a> {
+    interface: &'a PackageInterface,
+    module: &'a str,
+}
+
+/// The struct with its Move type name and a BCS decoder. Phantom type
+/// parameters are left out, as they do not change the layout.
+fn struct_rs(
+    ctx: &Context,
+    name: &str,
+    def: &NormalizedStruct,
+    uses: &mut BTreeSet<&'static str>,
+) -> String {
+    let mut out = String::from("\n");
+    let Some(layouts) = field_layouts(ctx.interface, def) else {
+        out.push_str(&format!(
+            "// `{}` holds types from other packages, so it has no Rust type.\n",
+            name
+        ));
+        return out;
+    };
+
+    out.push_str(&doc_comment(ctx.interface.doc(ctx.module, name)));
+    let params: Vec<String> = def
+        .type_parameters
+        .iter()
+        .enumerate()
+        .filter(|(_, p)| !p.is_phantom)
+        .map(|(i, _)| format!("T{}", i))
+        .collect();
+    let generics = type_list(&params);
+    out.push_str("#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]\n");
+    out.push_str(&format!("pub struct {}{} {{\n", name, generics));
+    for (field, layout) in def.fields.iter().zip(&layouts) {
+        out.push_str(&format!(
+            "    pub {}: {},\n",
+            identifier(&field.name),
+            rust_type(ctx, layout, uses)
+        ));
+    }
+    out.push_str("}\n\n");
+
+    out.push_str(&format!("impl{} {}{} {{\n", generics, name, generics));
+    out.push_str(&format!(
+        "    pub const MODULE: &'static str = \"{}\";\n    pub const NAME: &'static str = \"{}\";\n\n",
+        ctx.module, name
+    ));
+    out.push_str("    pub fn from_bcs(bytes: &[u8]) -> Result<Self, bcs::Error>");
+    if params.is_empty() {
+        out.push_str(" {\n");
+    } else {
+        out.push('\n');
+        out.push_str("    where\n        Self: serde::de::DeserializeOwned,\n    {\n");
+    }
+    out.push_str("        bcs::from_bytes(bytes)\n    }\n}\n");
+    out
+}
+
+/// An integration test that round-trips a sample of every struct of the
+/// module through BCS, or `None` if no struct has a Rust type.
+fn module_test_rs(interface: &PackageInterface, module: &NormalizedModule) -> Option<String> {
+    let mut tests = String::new();
+    for name in struct_order(module) {
+        let def = &module.structs[name];
+        let Some(layouts) = field_layouts(interface, def) else {
+            continue;
+        };
+
+        // Type parameters are instantiated with `u64`.
+        let params: Vec<Option<String>> = def
+            .type_parameters
+            .iter()
+            .map(|p| (!p.is_phantom).then(|| "42u64".to_string()))
+            .collect();
+        let args: Vec<String> = params.iter().flatten().map(|_| "u64".to_string()).collect();
+        let path = if args.is_empty() {
+            format!("suiforge_sdk::{}::{}", module_ident(&module.name), name)
+        } else {
+            format!(
+                "suiforge_sdk::{}::{}::{}",
+                module_ident(&module.name),
+                name,
+                type_list(&args)
+            )
+        };
+        let fields: Vec<String> = def
+            .fields
+            .iter()
+            .zip(&layouts)
+            .map(|(field, layout)| {
+                format!(
+                    "        {}: {},",
+                    identifier(&field.name),
+                    sample(interface, layout, &params)
+                )
+            })
+            .collect();
+        tests.push_str(&format!(
+            "\n#[test]\nfn {}_round_trips_through_bcs() {{\n    let value = {} {{\n{}\n    }};\n    let bytes = bcs::to_bytes(&value).unwrap();\n    assert_eq!({}::from_bcs(&bytes).unwrap(), value);\n}}\n",
+            snake_case(name),
+            path,
+            fields.join("\n"),
+            path
+        ));
+    }
+    if tests.is_empty() {
+        return None;
+    }
+    Some(format!(
+        "//! Auto-generated by SuiForge. Do not edit.\n{}",
+        tests
+    ))
+}
+
+/// A Rust expression of a value with `layout`. `params` holds samples of
+/// the enclosing struct's type arguments.
+fn sample(interface: &PackageInterface, layout: &Layout, params: &[Option<String>]) -> String {
+    match layout {
+        Layout::Bool => "true".to_string(),
+        Layout::U8 | Layout::U16 | Layout::U32 => "7".to_string(),
+        Layout::U64 | Layout::U128 => "42".to_string(),
+        Layout::U256 => "suiforge_sdk::framework::U256::from(42)".to_string(),
+        Layout::Address => "suiforge_sdk::Address([0xab; 32])".to_string(),
+        Layout::String => "\"sample\".to_string()".to_string(),
+        Layout::Vector(inner) => format!("vec![{}]", sample(interface, inner, params)),
+        Layout::Option(inner) => format!("Some({})", sample(interface, inner, params)),
+        Layout::Framework { name, fields } => {
+            let fields: Vec<String> = fields
+                .iter()
+                .map(|(field, layout)| format!("{}: {}", field, sample(interface, layout, params)))
+                .collect();
+            format!(
+                "suiforge_sdk::framework::{} {{ {} }}",
+                name,
+                fields.join(", ")
+            )
+        }
+        Layout::Struct {
+            module,
+            name,
+            type_arguments,
+        } => {
+            let def = &interface.modules[module].structs[name];
+            let args: Vec<Option<String>> = type_arguments
+                .iter()
+                .map(|arg| match (&arg.layout, arg.is_phantom) {
+                    (Some(layout), false) => Some(sample(interface, layout, params)),
+                    _ => None,
+                })
+                .collect();
+            let fields: Vec<String> = def
+                .fields
+                .iter()
+                .zip(field_layouts(interface, def).unwrap_or_default())
+                .map(|(field, layout)| {
+                    format!(
+                        "{}: {}",
+                        identifier(&field.name),
+                        sample(interface, &layout, &args)
+                    )
+                })
+                .collect();
+            format!(
+                "suiforge_sdk::{}::{} {{ {} }}",
+                module_ident(module),
+                name,
+                fields.join(", ")
+            )
+        }
+        Layout::Param(i) => params
+            .get(*i as usize)
+            .cloned()
+            .flatten()
+            .unwrap_or_else(|| "42".to_string()),
+    }
+}
+
+/// The Rust type a layout decodes to.
+fn rust_type(ctx: &Context, layout: &Layout, uses: &mut BTreeSet<&'static str>) -> String {
+    match layout {
+        Layout::Bool => "bool".to_string(),
+        Layout::U8 => "u8".to_string(),
+        Layout::U16 => "u16".to_string(),
+        Layout::U32 => "u32".to_string(),
+        Layout::U64 => "u64".to_string(),
+        Layout::U128 => "u128".to_string(),
+        Layout::U256 => {
+            uses.insert("U256");
+            "U256".to_string()
+        }
+        Layout::Address => {
+            uses.insert("Address");
+            "Address".to_string()
+        }
+        Layout::String => "String".to_string(),
+        Layout::Vector(inner) => format!("Vec<{}>", rust_type(ctx, inner, uses)),
+        Layout::Option(inner) => format!("Option<{}>", rust_type(ctx, inner, uses)),
+        Layout::Framework { name, fields } => {
+            let (name, args): (&'static str, Vec<&Layout>) = match *name {
+                "VecSet" => match &fields[0].1 {
+                    Layout::Vector(key) => ("VecSet", vec![&**key]),
+                    _ => ("VecSet", Vec::new()),
+                },
+                "VecMap" => match &fields[0].1 {
+                    Layout::Vector(entry) => match &**entry {
+                        Layout::Framework { fields, .. } => {
+                            ("VecMap", fields.iter().map(|(_, l)| l).collect())
+                        }
+                        _ => ("VecMap", Vec::new()),
+                    },
+                    _ => ("VecMap", Vec::new()),
+                },
+                other => (other, Vec::new()),
+            };
+            uses.insert(name);
+            let args: Vec<String> = args.iter().map(|l| rust_type(ctx, l, uses)).collect();
+            format!("{}{}", name, type_list(&args))
+        }
+        Layout::Struct {
+            module,
+            name,
+            type_arguments,
+        } => {
+            let args: Vec<String> = type_arguments
+                .iter()
+                .filter(|arg| !arg.is_phantom)
+                .filter_map(|arg| arg.layout.as_ref())
+                .map(|layout| rust_type(ctx, layout, uses))
+                .collect();
+            let path = if module == ctx.module {
+                name.clone()
+            } else {
+                format!("crate::{}::{}", module_ident(module), name)
+            };
+            format!("{}{}", path, type_list(&args))
+        }
+        Layout::Param(i) => format!("T{}", i),
+    }
+}
+
+fn function_rs(
+    ctx: &Context,
+    name: &str,
+    function: &NormalizedFunction,
+    uses: &mut BTreeSet<&'static str>,
+) -> String {
+    uses.insert("MoveCall");
+    let names = ctx
+        .interface
+        .param_names(ctx.module, name, function.parameters.len());
+    let mut seen = BTreeSet::new();
+    let mut params = Vec::new();
+    let mut arguments = Vec::new();
+    for (i, (ty, param)) in function.parameters.iter().zip(&names).enumerate() {
+        if is_tx_context(ty) {
+            continue;
+        }
+        if let Some(id) = system_object(ty) {
+            uses.insert("Address");
+            uses.insert("CallArg");
+            arguments.push(format!("CallArg::Object(Address::system({}))", id));
+            continue;
+        }
+        let mut param = param.trim_start_matches('_').to_string();
+        if param.is_empty() || !seen.insert(param.clone()) {
+            param = format!("arg{}", i);
+            seen.insert(param.clone());
+        }
+        let param = identifier(&param);
+        let (rust_type, expr) = argument(ty, &param, uses);
+        params.push(format!("{}: {}", param, rust_type));
+        arguments.push(expr);
+    }
+    let generic = !function.type_parameters.is_empty();
+    if generic {
+        params.push(format!(
+            "type_arguments: [&str; {}]",
+            function.type_parameters.len()
+        ));
+    }
+
+    let mut out = String::from("\n");
+    out.push_str(&doc_comment(ctx.interface.doc(ctx.module, name)));
+    let signature = format!("pub fn {}({}) -> MoveCall {{", identifier(name), params.join(", "));
+    if signature.len() <= 100 {
+        out.push_str(&signature);
+        out.push('\n');
+    } else {
+        out.push_str(&format!("pub fn {}(\n", identifier(name)));
+        for param in &params {
+            out.push_str(&format!("    {},\n", param));
+        }
+        out.push_str(") -> MoveCall {\n");
+    }
+    out.push_str("    MoveCall {\n        package: crate::package_id(),\n");
+    out.push_str(&format!(
+        "        module: \"{}\",\n        function: \"{}\",\n",
+        ctx.module, name
+    ));
+    if generic {
+        out.push_str(
+            "        type_arguments: type_arguments.iter().map(|t| t.to_string()).collect(),\n",
+        );
+    } else {
+        out.push_str("        type_arguments: Vec::new(),\n");
+    }
+    let inline = arguments.join(", ");
+    if arguments.is_empty() {
+        out.push_str("        arguments: Vec::new(),\n");
+    } else if inline.len() <= 60 {
+        out.push_str(&format!("        arguments: vec![{}],\n", inline));
+    } else {
+        out.push_str("        arguments: vec![\n");
+        for argument in &arguments {
+            out.push_str(&format!("            {},\n", argument));
+        }
+        out.push_str("        ],\n");
+    }
+    out.push_str("    }\n}\n");
+    out
+}
+
+/// The Rust type a builder takes for a parameter of type `ty`, and the
+/// expression that turns `value` into a `CallArg`.
+fn argument(ty: &NormalizedType, value: &str, uses: &mut BTreeSet<&'static str>) -> (String, String) {
+    let by_value = match ty {
+        NormalizedType::Reference(inner) | NormalizedType::MutableReference(inner) => inner,
+        _ => ty,
+    };
+    if let Some(rust_type) = pure_type(by_value, true, uses) {
+        uses.insert("pure");
+        return (rust_type, format!("pure(&{})", value));
+    }
+    uses.insert("CallArg");
+    if let NormalizedType::Vector(_) = ty {
+        return (
+            "Vec<CallArg>".to_string(),
+            format!("CallArg::Objects({})", value),
+        );
+    }
+    ("impl Into<CallArg>".to_string(), format!("{}.into()", value))
+}
+
+/// The Rust type of a pure value, borrowed at the top level, or `None` if
+/// `ty` is not one.
+fn pure_type(ty: &NormalizedType, top: bool, uses: &mut BTreeSet<&'static str>) -> Option<String> {
+    Some(match ty {
+        NormalizedType::Bool => "bool".to_string(),
+        NormalizedType::U8 => "u8".to_string(),
+        NormalizedType::U16 => "u16".to_string(),
+        NormalizedType::U32 => "u32".to_string(),
+        NormalizedType::U64 => "u64".to_string(),
+        NormalizedType::U128 => "u128".to_string(),
+        NormalizedType::U256 => {
+            uses.insert("U256");
+            "U256".to_string()
+        }
+        NormalizedType::Address => {
+            uses.insert("Address");
+            "Address".to_string()
+        }
+        NormalizedType::Vector(inner) => {
+            let inner = pure_type(inner, false, uses)?;
+            if top {
+                format!("&[{}]", inner)
+            } else {
+                format!("Vec<{}>", inner)
+            }
+        }
+        _ if is_struct(ty, "0x2", "object", "ID") => {
+            uses.insert("Address");
+            "Address".to_string()
+        }
+        _ if is_struct(ty, "0x1", "string", "String") || is_struct(ty, "0x1", "ascii", "String") => {
+            if top {
+                "&str".to_string()
+            } else {
+                "String".to_string()
+            }
+        }
+        NormalizedType::Struct { type_arguments, .. }
+            if is_struct(ty, "0x1", "option", "Option") =>
+        {
+            format!("Option<{}>", pure_type(type_arguments.first()?, false, uses)?)
+        }
+        _ => return None,
+    })
+}
+
+fn doc_comment(doc: Option<&str>) -> String {
+    let Some(doc) = doc else {
+        return String::new();
+    };
+    doc.lines()
+        .map(str::trim)
+        .map(|line| {
+            if line.is_empty() {
+                "///\n".to_string()
+            } else {
+                format!("/// {}\n", line)
+            }
+        })
+        .collect()
+}
+
+/// `name` as an identifier: keywords become raw identifiers, and the few
+/// that cannot be get a trailing underscore.
+fn identifier(name: &str) -> String {
+    match name {
+        "self" | "Self" | "super" | "crate" => format!("{}_", name),
+        _ if KEYWORDS.contains(&name) => format!("r#{}", name),
+        _ => name.to_string(),
+    }
+}
+
+/// The Rust module for a Move module.
+fn module_ident(name: &str) -> String {
+    if CRATE_MODULES.contains(&name) {
+        format!("{}_", name)
+    } else {
+        identifier(name)
+    }
+}
+
+/// The file a Move module's Rust module lives in; raw identifiers drop the
+/// `r#`.
+fn module_file(name: &str) -> String {
+    module_ident(name).trim_start_matches("r#").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codegen::tests::shop_interface;
+
+    fn generated(file: &str) -> String {
+        let dir = tempfile::tempdir().unwrap();
+        generate(dir.path(), "sdk", &shop_interface()).unwrap();
+        fs::read_to_string(dir.path().join("sdk").join(file)).unwrap()
+    }
+
+    #[test]
+    fn builders_escape_keywords_and_fill_in_system_objects() {
+        let shop = generated("src/shop.rs");
+
+        assert!(shop.contains(
+            "pub fn buy(shop: impl Into<CallArg>, payment: impl Into<CallArg>, r#type: u8) -> MoveCall {"
+        ));
+        assert!(shop.contains("arguments: vec![shop.into(), payment.into(), pure(&r#type)],"));
+        assert!(
+            shop.contains("arguments: vec![shop.into(), CallArg::Object(Address::system(0x6))],")
+        );
+        assert!(shop.contains(
+            "pub fn set_price(shop: impl Into<CallArg>, r#in: u64, from: &[u8]) -> MoveCall {"
+        ));
+        assert!(!shop.contains("fn internal"));
+    }
+
+    #[test]
+    fn structs_follow_their_layouts() {
+        let shop = generated("src/shop.rs");
+
+        assert!(shop.contains(
+            "pub struct Bought {\n    pub buyer: Address,\n    pub price: u64,\n    pub item: Vec<u8>,\n}"
+        ));
+        assert!(shop.contains("pub struct Boxed<T0> {\n    pub value: T0,\n}"));
+        assert!(shop.contains("pub struct Tagged {\n    pub tag: Option<u8>,\n}"));
+        assert!(
+            shop.contains("// `Foreign` holds types from other packages, so it has no Rust type.")
+        );
+    }
+
+    #[test]
+    fn events_decode_the_event_structs() {
+        let events = generated("src/events.rs");
+
+        assert!(events.contains(
+            "    ShopBought(crate::shop::Bought),\n    ShopTagged(crate::shop::Tagged),\n}"
+        ));
+        assert!(
+            events.contains(r#"is_type(event_type, crate::ORIGINAL_PACKAGE_ID, "shop", "Bought")"#)
+        );
+    }
+
+    #[test]
+    fn round_trip_tests_build_a_sample_of_every_struct() {
+        let test = generated("tests/shop.rs");
+
+        assert!(test.contains("fn boxed_round_trips_through_bcs() {\n    let value = suiforge_sdk::shop::Boxed::<u64> {\n        value: 42u64,\n    };"));
+        assert!(test.contains("entries: suiforge_sdk::framework::VecMap { contents: vec![suiforge_sdk::framework::Entry { key: suiforge_sdk::Address([0xab; 32]), value: suiforge_sdk::shop::Boxed { value: 42 } }] },"));
+        assert!(test.contains("last: Some(suiforge_sdk::shop::Tagged { tag: Some(7) }),"));
+        assert!(test
+            .contains("assert_eq!(suiforge_sdk::shop::Shop::from_bcs(&bytes).unwrap(), value);"));
+        assert!(!test.contains("Foreign"));
+    }
+}
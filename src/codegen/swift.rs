@@ -0,0 +1,1418 @@
+//! Swift SDK: a Swift package with a `Codable` struct for every Move struct,
+//! a call builder for every public or entry function, an enum of the
+//! package's events and the package IDs of every recorded deployment.
+//!
+//! Every Move module becomes a namespace enum, so `shop::Item` is
+//! `Shop.Item` and `shop::buy` is `Shop.buy(...)`. The package carries its own
+//! BCS codec and depends on nothing but Foundation.
+
+use super::bcs::{event_structs, field_layouts, layout, struct_order, Layout};
+use super::{
+    callable_functions, camel_case, is_tx_context, pascal_case, system_object, type_list,
+    PackageInterface,
+};
+use crate::error::Result;
+use crate::rpc::types::{NormalizedFunction, NormalizedModule, NormalizedStruct, NormalizedType};
+use std::fs;
+use std::path::Path;
+
+/// Swift keywords, which are escaped with backticks.
+const KEYWORDS: [&str; 54] = [
+    "associatedtype",
+    "class",
+    "deinit",
+    "enum",
+    "extension",
+    "fileprivate",
+    "func",
+    "import",
+    "init",
+    "inout",
+    "internal",
+    "let",
+    "open",
+    "operator",
+    "private",
+    "precedencegroup",
+    "protocol",
+    "public",
+    "rethrows",
+    "static",
+    "struct",
+    "subscript",
+    "typealias",
+    "var",
+    "break",
+    "case",
+    "catch",
+    "continue",
+    "default",
+    "defer",
+    "do",
+    "else",
+    "fallthrough",
+    "for",
+    "guard",
+    "if",
+    "in",
+    "repeat",
+    "return",
+    "throw",
+    "switch",
+    "where",
+    "while",
+    "Any",
+    "as",
+    "await",
+    "false",
+    "is",
+    "nil",
+    "self",
+    "super",
+    "throws",
+    "true",
+    "try",
+];
+
+/// Types the generated code refers to, and the files of the package, which
+/// Move modules and structs must not shadow. `Main.swift` would also be
+/// taken for an entry point on case-insensitive file systems.
+const RESERVED_TYPES: [&str; 38] = [
+    "Address",
+    "Any",
+    "Array",
+    "BCS",
+    "BCSCodable",
+    "BCSError",
+    "BCSReader",
+    "BCSWriter",
+    "Bag",
+    "Bool",
+    "CallArg",
+    "Coin",
+    "Data",
+    "Deployment",
+    "Deployments",
+    "Entry",
+    "Event",
+    "Events",
+    "Foundation",
+    "Framework",
+    "Main",
+    "MoveCall",
+    "MoveObject",
+    "MoveStruct",
+    "MoveValue",
+    "ObjectBag",
+    "ObjectResponse",
+    "ObjectTable",
+    "Optional",
+    "Package",
+    "String",
+    "Swift",
+    "Table",
+    "U128",
+    "U256",
+    "UID",
+    "VecMap",
+    "VecSet",
+];
+
+pub fn generate(project_root: &Path, output_dir: &str, interface: &PackageInterface) -> Result<()> {
+    let output_path = project_root.join(output_dir);
+    fs::create_dir_all(&output_path)?;
+
+    // BCS round-trip tests; SwiftPM rejects a test target without sources.
+    let tests: Vec<(String, String)> = interface
+        .modules
+        .iter()
+        .filter_map(|(name, module)| Some((namespace(name), module_test_swift(interface, module)?)))
+        .collect();
+
+    // Generate Package.swift
+    let test_target = if tests.is_empty() {
+        ""
+    } else {
+        "\n        .testTarget(name: \"SuiForgeSDKTests\", dependencies: [\"SuiForgeSDK\"]),"
+    };
+    let manifest = format!(
+        r#"// swift-tools-version:5.7
+import PackageDescription
+
+let package = Package(
+    name: "SuiForgeSDK",
+    platforms: [.macOS(.v12), .iOS(.v15)],
+    products: [
+        .library(name: "SuiForgeSDK", targets: ["SuiForgeSDK"]),
+    ],
+    targets: [
+        .target(name: "SuiForgeSDK"),{}
+    ]
+)
+"#,
+        test_target
+    );
+    fs::write(output_path.join("Package.swift"), manifest)?;
+
+    let sources_path = output_path.join("Sources").join("SuiForgeSDK");
+    fs::create_dir_all(&sources_path)?;
+
+    fs::write(sources_path.join("BCS.swift"), BCS_SWIFT)?;
+    fs::write(sources_path.join("Framework.swift"), FRAMEWORK_SWIFT)?;
+    fs::write(
+        sources_path.join("Deployments.swift"),
+        deployments_swift(interface),
+    )?;
+    if let Some(events) = events_swift(interface) {
+        fs::write(sources_path.join("Events.swift"), events)?;
+    }
+    for (name, module) in &interface.modules {
+        fs::write(
+            sources_path.join(format!("{}.swift", namespace(name))),
+            module_swift(interface, module),
+        )?;
+    }
+
+    if !tests.is_empty() {
+        let tests_path = output_path.join("Tests").join("SuiForgeSDKTests");
+        fs::create_dir_all(&tests_path)?;
+        for (namespace, test) in &tests {
+            fs::write(tests_path.join(format!("{}Tests.swift", namespace)), test)?;
+        }
+    }
+
+    // Generate README
+    let readme = r#"# SuiForge Swift SDK
+
+Auto-generated Swift SDK for interacting with your Sui smart contracts. It
+depends on nothing but Foundation.
+
+## Installation
+
+Add the package to your app:
+
+```swift
+.package(path: "../sdk/swift")
+```
+
+## Usage
+
+Every Move module is a namespace with a struct per Move struct and a builder
+per public or entry function. Builders return a `MoveCall` with the arguments
+already BCS-encoded; add it to a programmable transaction with the client of
+your choice:
+
+```swift
+import SuiForgeSDK
+
+let mint = Nft.mint(name: "Sword", power: 10)
+let transfer = Nft.transfer(nft: .result(0), recipient: try Address("0x..."))
+```
+
+`TxContext`, `Clock`, `Random` and `DenyList` are filled in. Objects are
+passed by ID as `.object(address)`; look up their version, or whether they
+are shared, when building the transaction.
+
+Structs are `Codable` and decode from the BCS contents of objects and events:
+
+```swift
+let nft = try Nft.Nft.fromObject(response)  // sui_getObject with showBcs
+let nft = try Nft.Nft.fromBCS(bytes)
+let event = try Event.decode(type: eventType, bcs: eventBytes)
+```
+
+`Package.id` is the deployment the SDK was generated for, and
+`Package.networks` holds the IDs of every recorded deployment.
+
+`swift test` round-trips a sample of every struct through BCS.
+
+## Development
+
+This SDK is auto-generated by SuiForge. To regenerate:
+
+```bash
+suiforge generate swift
+```
+"#;
+    fs::write(output_path.join("README.md"), readme)?;
+
+    Ok(())
+}
+
+const BCS_SWIFT: &str = r#"// Auto-generated by SuiForge. Do not edit.
+//
+// A minimal BCS codec for the values Move structs are made of.
+
+import Foundation
+
+public enum BCSError: Error, Equatable {
+    case unexpectedEnd
+    case trailingBytes
+    case invalidBool(UInt8)
+    case invalidOptionTag(UInt8)
+    case invalidUTF8
+    case lengthOverflow
+}
+
+/// A value with a BCS encoding.
+public protocol BCSCodable {
+    init(bcs reader: inout BCSReader) throws
+    func encode(bcs writer: inout BCSWriter)
+}
+
+/// A value a Move struct can hold.
+public typealias MoveValue = BCSCodable & Codable & Equatable
+
+extension BCSCodable {
+    /// Decode a complete value.
+    public static func fromBCS(_ data: Data) throws -> Self {
+        var reader = BCSReader(data)
+        let value = try Self(bcs: &reader)
+        guard reader.isAtEnd else { throw BCSError.trailingBytes }
+        return value
+    }
+
+    public func toBCS() -> Data {
+        var writer = BCSWriter()
+        encode(bcs: &writer)
+        return Data(writer.bytes)
+    }
+}
+
+public struct BCSReader {
+    private let bytes: [UInt8]
+    public private(set) var position = 0
+
+    public init(_ data: Data) {
+        bytes = [UInt8](data)
+    }
+
+    public var isAtEnd: Bool { position == bytes.count }
+
+    public mutating func readBytes(_ count: Int) throws -> [UInt8] {
+        guard count <= bytes.count - position else { throw BCSError.unexpectedEnd }
+        defer { position += count }
+        return Array(bytes[position..<position + count])
+    }
+
+    public mutating func readByte() throws -> UInt8 {
+        try readBytes(1)[0]
+    }
+
+    /// A ULEB128 length prefix.
+    public mutating func readLength() throws -> Int {
+        var value: UInt64 = 0
+        var shift: UInt64 = 0
+        while true {
+            let byte = try readByte()
+            guard shift < 63 else { throw BCSError.lengthOverflow }
+            value |= UInt64(byte & 0x7F) << shift
+            if byte < 0x80 { break }
+            shift += 7
+        }
+        guard value <= UInt64(Int.max) else { throw BCSError.lengthOverflow }
+        return Int(value)
+    }
+
+    public mutating func read<T: BCSCodable>(_ type: T.Type = T.self) throws -> T {
+        try T(bcs: &self)
+    }
+}
+
+public struct BCSWriter {
+    public private(set) var bytes: [UInt8] = []
+
+    public init() {}
+
+    public mutating func writeBytes(_ data: [UInt8]) {
+        bytes.append(contentsOf: data)
+    }
+
+    /// A ULEB128 length prefix.
+    public mutating func writeLength(_ length: Int) {
+        var value = UInt64(length)
+        repeat {
+            var byte = UInt8(value & 0x7F)
+            value >>= 7
+            if value != 0 { byte |= 0x80 }
+            bytes.append(byte)
+        } while value != 0
+    }
+
+    public mutating func write<T: BCSCodable>(_ value: T) {
+        value.encode(bcs: &self)
+    }
+}
+
+extension BCSCodable where Self: FixedWidthInteger & UnsignedInteger {
+    public init(bcs reader: inout BCSReader) throws {
+        let bytes = try reader.readBytes(Self.bitWidth / 8)
+        self = bytes.reversed().reduce(Self.zero) { $0 << 8 | Self($1) }
+    }
+
+    public func encode(bcs writer: inout BCSWriter) {
+        writer.writeBytes((0..<Self.bitWidth / 8).map { UInt8(truncatingIfNeeded: self >> ($0 * 8)) })
+    }
+}
+
+extension UInt8: BCSCodable {}
+extension UInt16: BCSCodable {}
+extension UInt32: BCSCodable {}
+extension UInt64: BCSCodable {}
+
+extension Bool: BCSCodable {
+    public init(bcs reader: inout BCSReader) throws {
+        switch try reader.readByte() {
+        case 0: self = false
+        case 1: self = true
+        case let byte: throw BCSError.invalidBool(byte)
+        }
+    }
+
+    public func encode(bcs writer: inout BCSWriter) {
+        writer.writeBytes([self ? 1 : 0])
+    }
+}
+
+extension String: BCSCodable {
+    public init(bcs reader: inout BCSReader) throws {
+        let bytes = try reader.readBytes(reader.readLength())
+        guard let value = String(bytes: bytes, encoding: .utf8) else { throw BCSError.invalidUTF8 }
+        self = value
+    }
+
+    public func encode(bcs writer: inout BCSWriter) {
+        let bytes = Array(utf8)
+        writer.writeLength(bytes.count)
+        writer.writeBytes(bytes)
+    }
+}
+
+extension Array: BCSCodable where Element: BCSCodable {
+    public init(bcs reader: inout BCSReader) throws {
+        let count = try reader.readLength()
+        var items: [Element] = []
+        for _ in 0..<count {
+            try items.append(reader.read())
+        }
+        self = items
+    }
+
+    public func encode(bcs writer: inout BCSWriter) {
+        writer.writeLength(count)
+        for item in self {
+            writer.write(item)
+        }
+    }
+}
+
+extension Optional: BCSCodable where Wrapped: BCSCodable {
+    public init(bcs reader: inout BCSReader) throws {
+        switch try reader.readByte() {
+        case 0: self = .none
+        case 1: self = try .some(reader.read())
+        case let tag: throw BCSError.invalidOptionTag(tag)
+        }
+    }
+
+    public func encode(bcs writer: inout BCSWriter) {
+        switch self {
+        case .none:
+            writer.writeBytes([0])
+        case .some(let value):
+            writer.writeBytes([1])
+            writer.write(value)
+        }
+    }
+}
+
+public struct InvalidAddress: Error, Equatable {
+    public let value: String
+}
+
+/// A 32-byte Sui address or object ID, hex in `Codable` formats.
+public struct Address: Hashable, Codable, BCSCodable, CustomStringConvertible {
+    public let bytes: [UInt8]
+
+    public init(bytes: [UInt8]) throws {
+        guard bytes.count == 32 else { throw InvalidAddress(value: "\(bytes)") }
+        self.bytes = bytes
+    }
+
+    /// Parse a hex address; short forms such as `0x2` are padded.
+    public init(_ hex: String) throws {
+        let digits = Array(hex.hasPrefix("0x") ? hex.dropFirst(2) : Substring(hex))
+        guard !digits.isEmpty, digits.count <= 64 else { throw InvalidAddress(value: hex) }
+        let padded = Array(repeating: Character("0"), count: 64 - digits.count) + digits
+        var bytes: [UInt8] = []
+        for i in stride(from: 0, to: 64, by: 2) {
+            guard let byte = UInt8(String(padded[i...i + 1]), radix: 16) else {
+                throw InvalidAddress(value: hex)
+            }
+            bytes.append(byte)
+        }
+        self.bytes = bytes
+    }
+
+    /// The address of a system object such as the `Clock` (`0x6`).
+    public static func system(_ id: UInt16) -> Address {
+        var bytes = [UInt8](repeating: 0, count: 32)
+        bytes[30] = UInt8(id >> 8)
+        bytes[31] = UInt8(id & 0xFF)
+        return try! Address(bytes: bytes)
+    }
+
+    public var description: String {
+        "0x" + bytes.map { String(format: "%02x", $0) }.joined()
+    }
+
+    public init(from decoder: Decoder) throws {
+        let container = try decoder.singleValueContainer()
+        self = try Address(container.decode(String.self))
+    }
+
+    public func encode(to encoder: Encoder) throws {
+        var container = encoder.singleValueContainer()
+        try container.encode(description)
+    }
+
+    public init(bcs reader: inout BCSReader) throws {
+        bytes = try reader.readBytes(32)
+    }
+
+    public func encode(bcs writer: inout BCSWriter) {
+        writer.writeBytes(bytes)
+    }
+}
+
+/// A `u128`, decimal in `Codable` formats.
+public struct U128: Hashable, Codable, BCSCodable, CustomStringConvertible {
+    /// Little-endian, as in BCS.
+    public let bytes: [UInt8]
+
+    public init(_ value: UInt64) {
+        bytes = littleEndian(value, count: 16)
+    }
+
+    public init?(_ decimal: String) {
+        guard let bytes = parseDecimal(decimal, count: 16) else { return nil }
+        self.bytes = bytes
+    }
+
+    public var description: String { decimalString(bytes) }
+
+    public init(from decoder: Decoder) throws {
+        let container = try decoder.singleValueContainer()
+        let text = try container.decode(String.self)
+        guard let value = U128(text) else {
+            throw DecodingError.dataCorruptedError(in: container, debugDescription: "invalid u128 \(text)")
+        }
+        self = value
+    }
+
+    public func encode(to encoder: Encoder) throws {
+        var container = encoder.singleValueContainer()
+        try container.encode(description)
+    }
+
+    public init(bcs reader: inout BCSReader) throws {
+        bytes = try reader.readBytes(16)
+    }
+
+    public func encode(bcs writer: inout BCSWriter) {
+        writer.writeBytes(bytes)
+    }
+}
+
+/// A `u256`, decimal in `Codable` formats.
+public struct U256: Hashable, Codable, BCSCodable, CustomStringConvertible {
+    /// Little-endian, as in BCS.
+    public let bytes: [UInt8]
+
+    public init(_ value: UInt64) {
+        bytes = littleEndian(value, count: 32)
+    }
+
+    public init?(_ decimal: String) {
+        guard let bytes = parseDecimal(decimal, count: 32) else { return nil }
+        self.bytes = bytes
+    }
+
+    public var description: String { decimalString(bytes) }
+
+    public init(from decoder: Decoder) throws {
+        let container = try decoder.singleValueContainer()
+        let text = try container.decode(String.self)
+        guard let value = U256(text) else {
+            throw DecodingError.dataCorruptedError(in: container, debugDescription: "invalid u256 \(text)")
+        }
+        self = value
+    }
+
+    public func encode(to encoder: Encoder) throws {
+        var container = encoder.singleValueContainer()
+        try container.encode(description)
+    }
+
+    public init(bcs reader: inout BCSReader) throws {
+        bytes = try reader.readBytes(32)
+    }
+
+    public func encode(bcs writer: inout BCSWriter) {
+        writer.writeBytes(bytes)
+    }
+}
+
+private func littleEndian(_ value: UInt64, count: Int) -> [UInt8] {
+    (0..<count).map { $0 < 8 ? UInt8(truncatingIfNeeded: value >> ($0 * 8)) : 0 }
+}
+
+private func parseDecimal(_ text: String, count: Int) -> [UInt8]? {
+    guard !text.isEmpty else { return nil }
+    var bytes = [UInt8](repeating: 0, count: count)
+    for char in text {
+        guard char.isASCII, let digit = char.wholeNumberValue else { return nil }
+        var carry = digit
+        for i in bytes.indices {
+            let current = Int(bytes[i]) * 10 + carry
+            bytes[i] = UInt8(current & 0xFF)
+            carry = current >> 8
+        }
+        guard carry == 0 else { return nil }
+    }
+    return bytes
+}
+
+private func decimalString(_ bytes: [UInt8]) -> String {
+    var value = Array(bytes.reversed())
+    var digits: [Character] = []
+    while value.contains(where: { $0 != 0 }) {
+        var remainder = 0
+        for i in value.indices {
+            let current = remainder << 8 | Int(value[i])
+            value[i] = UInt8(current / 10)
+            remainder = current % 10
+        }
+        digits.append(Character(String(remainder)))
+    }
+    return digits.isEmpty ? "0" : String(digits.reversed())
+}
+"#;
+
+const FRAMEWORK_SWIFT: &str = r#"// Auto-generated by SuiForge. Do not edit.
+//
+// Sui framework types and the Move call representation the generated
+// modules share.
+
+import Foundation
+
+/// A Move struct of the package.
+public protocol MoveStruct: MoveValue {
+    static var moveModule: String { get }
+    static var moveName: String { get }
+}
+
+/// A Move struct with `key`, which can be fetched as an object.
+public protocol MoveObject: MoveStruct {}
+
+extension MoveObject {
+    /// Decode an object as `sui_getObject` returns it with `showBcs`.
+    public static func fromObject(_ response: ObjectResponse) throws -> Self {
+        try fromBCS(response.contents(module: moveModule, name: moveName))
+    }
+}
+
+public struct UID: MoveValue {
+    public var id: Address
+
+    public init(id: Address) {
+        self.id = id
+    }
+
+    public init(bcs reader: inout BCSReader) throws {
+        id = try reader.read()
+    }
+
+    public func encode(bcs writer: inout BCSWriter) {
+        writer.write(id)
+    }
+}
+
+public struct Coin: MoveValue {
+    public var id: UID
+    public var balance: UInt64
+
+    public init(id: UID, balance: UInt64) {
+        self.id = id
+        self.balance = balance
+    }
+
+    public init(bcs reader: inout BCSReader) throws {
+        id = try reader.read()
+        balance = try reader.read()
+    }
+
+    public func encode(bcs writer: inout BCSWriter) {
+        writer.write(id)
+        writer.write(balance)
+    }
+}
+
+/// A `Table`, `Bag`, `ObjectTable` or `ObjectBag`; the entries are dynamic
+/// fields of `id`.
+public struct Table: MoveValue {
+    public var id: UID
+    public var size: UInt64
+
+    public init(id: UID, size: UInt64) {
+        self.id = id
+        self.size = size
+    }
+
+    public init(bcs reader: inout BCSReader) throws {
+        id = try reader.read()
+        size = try reader.read()
+    }
+
+    public func encode(bcs writer: inout BCSWriter) {
+        writer.write(id)
+        writer.write(size)
+    }
+}
+
+public typealias Bag = Table
+public typealias ObjectTable = Table
+public typealias ObjectBag = Table
+
+public struct VecSet<Key: MoveValue>: MoveValue {
+    public var contents: [Key]
+
+    public init(contents: [Key]) {
+        self.contents = contents
+    }
+
+    public init(bcs reader: inout BCSReader) throws {
+        contents = try reader.read()
+    }
+
+    public func encode(bcs writer: inout BCSWriter) {
+        writer.write(contents)
+    }
+}
+
+public struct Entry<Key: MoveValue, Value: MoveValue>: MoveValue {
+    public var key: Key
+    public var value: Value
+
+    public init(key: Key, value: Value) {
+        self.key = key
+        self.value = value
+    }
+
+    public init(bcs reader: inout BCSReader) throws {
+        key = try reader.read()
+        value = try reader.read()
+    }
+
+    public func encode(bcs writer: inout BCSWriter) {
+        writer.write(key)
+        writer.write(value)
+    }
+}
+
+public struct VecMap<Key: MoveValue, Value: MoveValue>: MoveValue {
+    public var contents: [Entry<Key, Value>]
+
+    public init(contents: [Entry<Key, Value>]) {
+        self.contents = contents
+    }
+
+    public init(bcs reader: inout BCSReader) throws {
+        contents = try reader.read()
+    }
+
+    public func encode(bcs writer: inout BCSWriter) {
+        writer.write(contents)
+    }
+}
+
+/// An argument of a Move call.
+public enum CallArg: Equatable {
+    /// A BCS-encoded pure value.
+    case pure(Data)
+    /// An object by ID. Its version, and whether it is shared, are looked up
+    /// when the transaction is built.
+    case object(Address)
+    /// Objects gathered into a vector with `MakeMoveVec`.
+    case objects([CallArg])
+    /// The result of an earlier command.
+    case result(UInt16)
+    /// One of the results of an earlier command.
+    case nestedResult(UInt16, UInt16)
+}
+
+/// A call of a Move function, ready to add to a programmable transaction.
+public struct MoveCall: Equatable {
+    public var packageID: String
+    public var module: String
+    public var function: String
+    public var typeArguments: [String]
+    public var arguments: [CallArg]
+
+    public init(
+        packageID: String,
+        module: String,
+        function: String,
+        typeArguments: [String],
+        arguments: [CallArg]
+    ) {
+        self.packageID = packageID
+        self.module = module
+        self.function = function
+        self.typeArguments = typeArguments
+        self.arguments = arguments
+    }
+
+    public var target: String { "\(packageID)::\(module)::\(function)" }
+
+    /// Call the function in another version of the package.
+    public func withPackage(_ packageID: String) -> MoveCall {
+        var call = self
+        call.packageID = packageID
+        return call
+    }
+}
+
+/// A deployment of the package.
+public struct Deployment: Equatable {
+    public let id: String
+    /// The package's first version, which its types keep across upgrades.
+    public let originalID: String
+}
+
+private func normalizedAddress(_ address: String) -> String {
+    let digits = address.hasPrefix("0x") ? String(address.dropFirst(2)) : address
+    return String(repeating: "0", count: max(0, 64 - digits.count)) + digits.lowercased()
+}
+
+/// Whether `actual`, a type as the fullnode prints it, is the struct
+/// `address::module::name` with any type arguments.
+public func isType(_ actual: String, address: String, module: String, name: String) -> Bool {
+    let parts = actual.components(separatedBy: "::")
+    guard parts.count >= 3 else { return false }
+    let rest = parts[2...].joined(separator: "::")
+    return normalizedAddress(parts[0]) == normalizedAddress(address)
+        && parts[1] == module
+        && (rest == name || rest.hasPrefix(name + "<"))
+}
+
+public enum ObjectError: Error, Equatable {
+    /// The response holds no Move object contents; request it with `showBcs`.
+    case missingContents
+    /// The object has another type.
+    case wrongType(String)
+}
+
+/// An object as `sui_getObject` returns it with `showBcs`.
+public struct ObjectResponse: Decodable {
+    public struct Contents: Decodable {
+        public let dataType: String
+        public let type: String?
+        public let bcsBytes: String?
+    }
+
+    public struct ObjectData: Decodable {
+        public let bcs: Contents?
+    }
+
+    public let data: ObjectData?
+
+    /// The BCS contents of the object, checking that it is a
+    /// `module::name` of the package.
+    public func contents(module: String, name: String) throws -> Data {
+        guard let contents = data?.bcs, contents.dataType == "moveObject",
+              let type = contents.type, let encoded = contents.bcsBytes,
+              let bytes = Data(base64Encoded: encoded)
+        else { throw ObjectError.missingContents }
+        guard isType(type, address: Package.originalID, module: module, name: name) else {
+            throw ObjectError.wrongType(type)
+        }
+        return bytes
+    }
+}
+"#;
+
+fn deployments_swift(interface: &PackageInterface) -> String {
+    let type_address = interface.type_address();
+    let mut out = String::from("// Auto-generated by SuiForge. Do not edit.\n\n");
+    let (package_id, original_id) = match &interface.package_id {
+        Some(id) => (id.as_str(), type_address),
+        None => {
+            out.push_str(
+                "// The package is not deployed yet: regenerate after `suiforge deploy`, or call\n\
+                 // `MoveCall.withPackage`.\n",
+            );
+            ("0x0", "0x0")
+        }
+    };
+    out.push_str("public enum Package {\n");
+    out.push_str("    /// The package calls go to.\n");
+    out.push_str(&format!(
+        "    public static let id = \"{}\"\n\n",
+        package_id
+    ));
+    out.push_str("    /// The package's first version, which its types keep across upgrades.\n");
+    out.push_str(&format!(
+        "    public static let originalID = \"{}\"\n\n",
+        original_id
+    ));
+    out.push_str("    /// Every recorded deployment, by network.\n");
+    out.push_str("    public static let networks: [String: Deployment] = [");
+    if interface.deployments.is_empty() {
+        out.push_str(":]\n}\n");
+        return out;
+    }
+    out.push('\n');
+    for (network, deployment) in &interface.deployments {
+        out.push_str(&format!(
+            "        \"{}\": Deployment(\n            id: \"{}\",\n            originalID: \"{}\"\n        ),\n",
+            network, deployment.package_id, deployment.original_package_id
+        ));
+    }
+    out.push_str("    ]\n}\n");
+    out
+}
+
+fn events_swift(interface: &PackageInterface) -> Option<String> {
+    let events = event_structs(interface);
+    if events.is_empty() {
+        return None;
+    }
+    let case = |module: &str, name: &str| identifier(&format!("{}{}", camel_case(module), name));
+
+    let mut out = String::from("// Auto-generated by SuiForge. Do not edit.\n\n");
+    out.push_str("import Foundation\n\n");
+    out.push_str("/// Events the package can emit: its `copy + drop` structs.\n");
+    out.push_str("public enum Event: Equatable {\n");
+    for (module, name) in &events {
+        out.push_str(&format!(
+            "    case {}({}.{})\n",
+            case(module, name),
+            namespace(module),
+            type_ident(name)
+        ));
+    }
+    out.push_str(
+        "\n    /// Decode an event from its type and BCS contents, or return `nil` if the\n    /// package does not define the type.\n",
+    );
+    out.push_str("    public static func decode(type: String, bcs: Data) throws -> Event? {\n");
+    for (module, name) in &events {
+        out.push_str(&format!(
+            "        if isType(type, address: Package.originalID, module: \"{}\", name: \"{}\") {{\n            return try .{}({}.{}.fromBCS(bcs))\n        }}\n",
+            module,
+            name,
+            case(module, name),
+            namespace(module),
+            type_ident(name)
+        ));
+    }
+    out.push_str("        return nil\n    }\n}\n");
+    Some(out)
+}
+
+fn module_swift(interface: &PackageInterface, module: &NormalizedModule) -> String {
+    let ctx = Context {
+        interface,
+        module: &module.name,
+    };
+    let mut out = String::from("// Auto-generated by SuiForge. Do not edit.\n\n");
+    out.push_str("import Foundation\n\n");
+    out.push_str(&format!(
+        "/// The `{}` module.\npublic enum {} {{}}\n",
+        module.name,
+        namespace(&module.name)
+    ));
+
+    for name in struct_order(module) {
+        out.push_str(&struct_swift(&ctx, name, &module.structs[name]));
+    }
+    let functions: Vec<String> = callable_functions(&module.exposed_functions)
+        .map(|(name, function)| function_swift(&ctx, name, function))
+        .collect();
+    if !functions.is_empty() {
+        out.push_str(&format!(
+            "\nextension {} {{\n{}}}\n",
+            namespace(&module.name),
+            functions.join("\n")
+        ));
+    }
+    out
+}
+
+struct Context<'a> {
+    interface: &'a PackageInterface,
+    module: &'a str,
+}
+
+/// The struct with its Move type name and BCS coding. Phantom type
+/// parameters are left out, as they do not change the layout.
+fn struct_swift(ctx: &Context, name: &str, def: &NormalizedStruct) -> String {
+    let mut out = String::from("\n");
+    let Some(layouts) = field_layouts(ctx.interface, def) else {
+        out.push_str(&format!(
+            "// `{}` holds types from other packages, so it has no Swift type.\n",
+            name
+        ));
+        return out;
+    };
+
+    out.push_str(&format!("extension {} {{\n", namespace(ctx.module)));
+    out.push_str(&doc_comment(ctx.interface.doc(ctx.module, name), "    "));
+    let params: Vec<String> = def
+        .type_parameters
+        .iter()
+        .enumerate()
+        .filter(|(_, p)| !p.is_phantom)
+        .map(|(i, _)| format!("T{}: MoveValue", i))
+        .collect();
+    let protocol = if def.abilities.abilities.iter().any(|a| a == "Key") {
+        "MoveObject"
+    } else {
+        "MoveStruct"
+    };
+    out.push_str(&format!(
+        "    public struct {}{}: {} {{\n",
+        type_ident(name),
+        type_list(&params),
+        protocol
+    ));
+
+    let fields: Vec<(String, String)> = def
+        .fields
+        .iter()
+        .zip(&layouts)
+        .map(|(field, layout)| {
+            (
+                identifier(&camel_case(&field.name)),
+                swift_type(ctx, layout),
+            )
+        })
+        .collect();
+    for (field, swift_type) in &fields {
+        out.push_str(&format!("        public var {}: {}\n", field, swift_type));
+    }
+    out.push_str(&format!(
+        "\n        public static var moveModule: String {{ \"{}\" }}\n        public static var moveName: String {{ \"{}\" }}\n",
+        ctx.module, name
+    ));
+
+    // Codable uses the Move field names.
+    if def
+        .fields
+        .iter()
+        .zip(&fields)
+        .any(|(field, (swift_name, _))| field.name != unescaped(swift_name))
+    {
+        out.push_str("\n        enum CodingKeys: String, CodingKey {\n");
+        for (field, (swift_name, _)) in def.fields.iter().zip(&fields) {
+            if field.name == unescaped(swift_name) {
+                out.push_str(&format!("            case {}\n", swift_name));
+            } else {
+                out.push_str(&format!(
+                    "            case {} = \"{}\"\n",
+                    swift_name, field.name
+                ));
+            }
+        }
+        out.push_str("        }\n");
+    }
+
+    let init_params: Vec<String> = fields
+        .iter()
+        .map(|(field, swift_type)| format!("{}: {}", field, swift_type))
+        .collect();
+    out.push_str(&format!(
+        "\n        public init({}) {{\n",
+        init_params.join(", ")
+    ));
+    for (field, _) in &fields {
+        out.push_str(&format!("            self.{} = {}\n", field, field));
+    }
+    out.push_str("        }\n");
+
+    out.push_str("\n        public init(bcs reader: inout BCSReader) throws {\n");
+    for (field, _) in &fields {
+        out.push_str(&format!("            {} = try reader.read()\n", field));
+    }
+    out.push_str("        }\n");
+    out.push_str("\n        public func encode(bcs writer: inout BCSWriter) {\n");
+    for (field, _) in &fields {
+        out.push_str(&format!("            writer.write({})\n", field));
+    }
+    out.push_str("        }\n    }\n}\n");
+    out
+}
+
+/// An XCTest case that round-trips a sample of every struct of the module
+/// through BCS, or `None` if no struct has a Swift type.
+fn module_test_swift(interface: &PackageInterface, module: &NormalizedModule) -> Option<String> {
+    let mut tests = String::new();
+    for name in struct_order(module) {
+        let def = &module.structs[name];
+        let Some(layouts) = field_layouts(interface, def) else {
+            continue;
+        };
+
+        // Type parameters are instantiated with `UInt64`.
+        let params: Vec<Option<String>> = def
+            .type_parameters
+            .iter()
+            .map(|p| (!p.is_phantom).then(|| "UInt64(42)".to_string()))
+            .collect();
+        let args: Vec<String> = params
+            .iter()
+            .flatten()
+            .map(|_| "UInt64".to_string())
+            .collect();
+        let ty = format!(
+            "SuiForgeSDK.{}.{}{}",
+            namespace(&module.name),
+            type_ident(name),
+            type_list(&args)
+        );
+        let fields: Vec<String> = def
+            .fields
+            .iter()
+            .zip(&layouts)
+            .map(|(field, layout)| {
+                format!(
+                    "{}: {}",
+                    identifier(&camel_case(&field.name)),
+                    sample(interface, layout, &params)
+                )
+            })
+            .collect();
+        let value = format!("{}({})", ty, fields.join(", "));
+        let value = if value.contains("Address(\"") {
+            format!("try {}", value)
+        } else {
+            value
+        };
+        tests.push_str(&format!(
+            "\n    func test{}RoundTripsThroughBCS() throws {{\n        let value = {}\n        XCTAssertEqual(try {}.fromBCS(value.toBCS()), value)\n    }}\n",
+            pascal_case(name),
+            value,
+            ty
+        ));
+    }
+    if tests.is_empty() {
+        return None;
+    }
+    Some(format!(
+        "// Auto-generated by SuiForge. Do not edit.\n\nimport SuiForgeSDK\nimport XCTest\n\nfinal class {}Tests: XCTestCase {{{}}}\n",
+        namespace(&module.name),
+        tests
+    ))
+}
+
+/// A Swift expression of a value with `layout`. `params` holds samples of
+/// the enclosing struct's type arguments.
+fn sample(interface: &PackageInterface, layout: &Layout, params: &[Option<String>]) -> String {
+    match layout {
+        Layout::Bool => "true".to_string(),
+        Layout::U8 | Layout::U16 | Layout::U32 => "7".to_string(),
+        Layout::U64 => "42".to_string(),
+        Layout::U128 => "U128(42)".to_string(),
+        Layout::U256 => "U256(42)".to_string(),
+        Layout::Address => format!("Address(\"0x{}\")", "ab".repeat(32)),
+        Layout::String => "\"sample\"".to_string(),
+        Layout::Vector(inner) => format!("[{}]", sample(interface, inner, params)),
+        Layout::Option(inner) => sample(interface, inner, params),
+        Layout::Framework { name, fields } => {
+            let fields: Vec<String> = fields
+                .iter()
+                .map(|(field, layout)| format!("{}: {}", field, sample(interface, layout, params)))
+                .collect();
+            format!("{}({})", name, fields.join(", "))
+        }
+        Layout::Struct {
+            module,
+            name,
+            type_arguments,
+        } => {
+            let def = &interface.modules[module].structs[name];
+            let args: Vec<Option<String>> = type_arguments
+                .iter()
+                .map(|arg| match (&arg.layout, arg.is_phantom) {
+                    (Some(layout), false) => Some(sample(interface, layout, params)),
+                    _ => None,
+                })
+                .collect();
+            let fields: Vec<String> = def
+                .fields
+                .iter()
+                .zip(field_layouts(interface, def).unwrap_or_default())
+                .map(|(field, layout)| {
+                    format!(
+                        "{}: {}",
+                        identifier(&camel_case(&field.name)),
+                        sample(interface, &layout, &args)
+                    )
+                })
+                .collect();
+            format!(
+                "SuiForgeSDK.{}.{}({})",
+                namespace(module),
+                type_ident(name),
+                fields.join(", ")
+            )
+        }
+        Layout::Param(i) => params
+            .get(*i as usize)
+            .cloned()
+            .flatten()
+            .unwrap_or_else(|| "UInt64(42)".to_string()),
+    }
+}
+
+/// The Swift type a layout decodes to.
+fn swift_type(ctx: &Context, layout: &Layout) -> String {
+    match layout {
+        Layout::Bool => "Bool".to_string(),
+        Layout::U8 => "UInt8".to_string(),
+        Layout::U16 => "UInt16".to_string(),
+        Layout::U32 => "UInt32".to_string(),
+        Layout::U64 => "UInt64".to_string(),
+        Layout::U128 => "U128".to_string(),
+        Layout::U256 => "U256".to_string(),
+        Layout::Address => "Address".to_string(),
+        Layout::String => "String".to_string(),
+        Layout::Vector(inner) => format!("[{}]", swift_type(ctx, inner)),
+        Layout::Option(inner) => format!("{}?", swift_type(ctx, inner)),
+        Layout::Framework { name, fields } => {
+            let args: Vec<&Layout> = match (*name, &fields[0].1) {
+                ("VecSet", Layout::Vector(key)) => vec![&**key],
+                ("VecMap", Layout::Vector(entry)) => match &**entry {
+                    Layout::Framework { fields, .. } => fields.iter().map(|(_, l)| l).collect(),
+                    _ => Vec::new(),
+                },
+                _ => Vec::new(),
+            };
+            let args: Vec<String> = args.iter().map(|l| swift_type(ctx, l)).collect();
+            format!("{}{}", name, type_list(&args))
+        }
+        Layout::Struct {
+            module,
+            name,
+            type_arguments,
+        } => {
+            let args: Vec<String> = type_arguments
+                .iter()
+                .filter(|arg| !arg.is_phantom)
+                .filter_map(|arg| arg.layout.as_ref())
+                .map(|layout| swift_type(ctx, layout))
+                .collect();
+            // Qualified with the package's module name, as a struct of this
+            // module may share a name with another module's namespace.
+            let path = if module == ctx.module {
+                type_ident(name)
+            } else {
+                format!("SuiForgeSDK.{}.{}", namespace(module), type_ident(name))
+            };
+            format!("{}{}", path, type_list(&args))
+        }
+        Layout::Param(i) => format!("T{}", i),
+    }
+}
+
+fn function_swift(ctx: &Context, name: &str, function: &NormalizedFunction) -> String {
+    let names = ctx
+        .interface
+        .param_names(ctx.module, name, function.parameters.len());
+    let mut seen = Vec::new();
+    let mut params = Vec::new();
+    let mut arguments = Vec::new();
+    for (i, (ty, param)) in function.parameters.iter().zip(&names).enumerate() {
+        if is_tx_context(ty) {
+            continue;
+        }
+        if let Some(id) = system_object(ty) {
+            arguments.push(format!(".object(.system({}))", id));
+            continue;
+        }
+        let mut param = camel_case(param.trim_start_matches('_'));
+        if param.is_empty() || param == "typeArguments" || seen.contains(&param) {
+            param = format!("arg{}", i);
+        }
+        seen.push(param.clone());
+        let param = identifier(&param);
+        let (swift_type, expr) = argument(ctx, ty, &param);
+        params.push(format!("{}: {}", param, swift_type));
+        arguments.push(expr);
+    }
+    let generic = !function.type_parameters.is_empty();
+    if generic {
+        params.push("typeArguments: [String]".to_string());
+    }
+
+    let mut out = doc_comment(ctx.interface.doc(ctx.module, name), "    ");
+    let function_name = identifier(&camel_case(name));
+    let signature = format!(
+        "    public static func {}({}) -> MoveCall {{",
+        function_name,
+        params.join(", ")
+    );
+    if signature.len() <= 100 {
+        out.push_str(&signature);
+        out.push('\n');
+    } else {
+        out.push_str(&format!(
+            "    public static func {}(\n        {}\n    ) -> MoveCall {{\n",
+            function_name,
+            params.join(",\n        ")
+        ));
+    }
+    out.push_str("        MoveCall(\n            packageID: Package.id,\n");
+    out.push_str(&format!(
+        "            module: \"{}\",\n            function: \"{}\",\n",
+        ctx.module, name
+    ));
+    if generic {
+        out.push_str("            typeArguments: typeArguments,\n");
+    } else {
+        out.push_str("            typeArguments: [],\n");
+    }
+    if arguments.is_empty() {
+        out.push_str("            arguments: []\n");
+    } else {
+        out.push_str("            arguments: [\n");
+        for argument in &arguments {
+            out.push_str(&format!("                {},\n", argument));
+        }
+        out.push_str("            ]\n");
+    }
+    out.push_str("        )\n    }\n");
+    out
+}
+
+/// The Swift type a builder takes for a parameter of type `ty`, and the
+/// expression that turns `value` into a `CallArg`.
+fn argument(ctx: &Context, ty: &NormalizedType, value: &str) -> (String, String) {
+    let by_value = match ty {
+        NormalizedType::Reference(inner) | NormalizedType::MutableReference(inner) => inner,
+        _ => ty,
+    };
+    if let Some(pure) = layout(ctx.interface, by_value).filter(Layout::is_pure) {
+        return (swift_type(ctx, &pure), format!(".pure({}.toBCS())", value));
+    }
+    if let NormalizedType::Vector(_) = ty {
+        return ("[CallArg]".to_string(), format!(".objects({})", value));
+    }
+    ("CallArg".to_string(), value.to_string())
+}
+
+fn doc_comment(doc: Option<&str>, indent: &str) -> String {
+    let Some(doc) = doc else {
+        return String::new();
+    };
+    doc.lines()
+        .map(str::trim)
+        .map(|line| {
+            if line.is_empty() {
+                format!("{}///\n", indent)
+            } else {
+                format!("{}/// {}\n", indent, line)
+            }
+        })
+        .collect()
+}
+
+/// `name` as an identifier: keywords are escaped with backticks.
+fn identifier(name: &str) -> String {
+    if KEYWORDS.contains(&name) {
+        format!("`{}`", name)
+    } else {
+        name.to_string()
+    }
+}
+
+fn unescaped(identifier: &str) -> &str {
+    identifier.trim_matches('`')
+}
+
+/// The name of a struct, with a trailing underscore if it would shadow a
+/// type the generated code uses.
+fn type_ident(name: &str) -> String {
+    if RESERVED_TYPES.contains(&name) || KEYWORDS.contains(&name) || name == "Type" {
+        format!("{}_", name)
+    } else {
+        name.to_string()
+    }
+}
+
+/// The namespace enum, and file, of a Move module.
+fn namespace(module: &str) -> String {
+    type_ident(&pascal_case(module))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codegen::tests::shop_interface;
+
+    fn generated(file: &str) -> String {
+        let dir = tempfile::tempdir().unwrap();
+        generate(dir.path(), "sdk", &shop_interface()).unwrap();
+        fs::read_to_string(dir.path().join("sdk").join(file)).unwrap()
+    }
+
+    #[test]
+    fn builders_escape_keywords_and_fill_in_system_objects() {
+        let shop = generated("Sources/SuiForgeSDK/Shop.swift");
+
+        assert!(shop.contains(
+            "public static func setPrice(shop: CallArg, `in`: UInt64, from: [UInt8]) -> MoveCall {"
+        ));
+        assert!(shop.contains(".pure(`in`.toBCS()),"));
+        assert!(shop.contains(".object(.system(0x6)),"));
+        assert!(!shop.contains("func internal"));
+    }
+
+    #[test]
+    fn structs_follow_their_layouts() {
+        let shop = generated("Sources/SuiForgeSDK/Shop.swift");
+
+        assert!(shop.contains(
+            "public struct Bought: MoveStruct {\n        public var buyer: Address\n        public var price: UInt64\n        public var item: [UInt8]\n"
+        ));
+        assert!(shop.contains("public struct Boxed<T0: MoveValue>: MoveStruct {"));
+        assert!(
+            shop.contains("// `Foreign` holds types from other packages, so it has no Swift type.")
+        );
+    }
+
+    #[test]
+    fn events_decode_the_event_structs() {
+        let events = generated("Sources/SuiForgeSDK/Events.swift");
+
+        assert!(
+            events.contains("    case shopBought(Shop.Bought)\n    case shopTagged(Shop.Tagged)\n")
+        );
+    }
+
+    #[test]
+    fn round_trip_tests_build_a_sample_of_every_struct() {
+        let dir = tempfile::tempdir().unwrap();
+        generate(dir.path(), "sdk", &shop_interface()).unwrap();
+        let manifest = fs::read_to_string(dir.path().join("sdk/Package.swift")).unwrap();
+        let test = generated("Tests/SuiForgeSDKTests/ShopTests.swift");
+
+        assert!(manifest
+            .contains(".testTarget(name: \"SuiForgeSDKTests\", dependencies: [\"SuiForgeSDK\"]),"));
+        assert!(test.contains("let value = SuiForgeSDK.Shop.Boxed<UInt64>(value: UInt64(42))\n"));
+        assert!(test.contains("let value = try SuiForgeSDK.Shop.Shop(id: UID(id: Address(\"0xabab"));
+        assert!(test
+            .contains("XCTAssertEqual(try SuiForgeSDK.Shop.Ledger.fromBCS(value.toBCS()), value)"));
+        assert!(!test.contains("Foreign"));
+    }
+}
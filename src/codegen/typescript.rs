@@ -1,8 +1,32 @@
+//! TypeScript SDK: one module per Move module, with an interface for every
+//! struct and a transaction builder for every public or entry function.
+//!
+//! Builders append a `moveCall` to a `TransactionBlock` from
+//! `@mysten/sui.js`. Pure arguments are serialized with the matching BCS
+//! type, objects are passed by ID or as the result of an earlier command, and
+//! the `TxContext` and well-known system objects are filled in.
+
+use super::bcs::{layout, struct_order, Layout};
+use super::{
+    callable_functions, camel_case, is_struct, is_tx_context, system_object, type_list,
+    PackageInterface,
+};
 use crate::error::Result;
+use crate::rpc::types::{NormalizedFunction, NormalizedModule, NormalizedStruct, NormalizedType};
+use std::collections::BTreeSet;
 use std::fs;
 use std::path::Path;
 
-pub fn generate(project_root: &Path, output_dir: &str) -> Result<()> {
+/// Words that cannot name an exported function or namespace.
+const RESERVED: [&str; 45] = [
+    "break", "case", "catch", "class", "const", "continue", "debugger", "default", "delete", "do",
+    "else", "enum", "export", "extends", "false", "finally", "for", "function", "if", "import",
+    "in", "instanceof", "new", "null", "return", "super", "switch", "this", "throw", "true", "try",
+    "typeof", "var", "void", "while", "with", "implements", "interface", "let", "package",
+    "private", "protected", "public", "static", "yield",
+];
+
+pub fn generate(project_root: &Path, output_dir: &str, interface: &PackageInterface) -> Result<()> {
     let output_path = project_root.join(output_dir);
     fs::create_dir_all(&output_path)?;
 
@@ -21,8 +45,15 @@ pub fn generate(project_root: &Path, output_dir: &str) -> Result<()> {
     "@mysten/sui.js": "^0.50.0"
   },
   "devDependencies": {
+    "@types/jest": "^29.0.0",
     "@types/node": "^20.0.0",
+    "jest": "^29.0.0",
+    "ts-jest": "^29.0.0",
     "typescript": "^5.0.0"
+  },
+  "jest": {
+    "preset": "ts-jest",
+    "testEnvironment": "node"
   }
 }
 "#;
@@ -50,59 +81,24 @@ pub fn generate(project_root: &Path, output_dir: &str) -> Result<()> {
     let src_path = output_path.join("src");
     fs::create_dir_all(&src_path)?;
 
-    // Generate index.ts
-    let index_ts = r#"import { TransactionBlock } from '@mysten/sui.js/transactions';
-import { SuiClient } from '@mysten/sui.js/client';
-
-export class SuiForgeClient {
-  private client: SuiClient;
-  private packageId: string;
-
-  constructor(client: SuiClient, packageId: string) {
-    this.client = client;
-    this.packageId = packageId;
-  }
-
-  /**
-   * Get the package ID
-   */
-  getPackageId(): string {
-    return this.packageId;
-  }
-
-  /**
-   * Create a new transaction block
-   */
-  createTransaction(): TransactionBlock {
-    return new TransactionBlock();
-  }
-
-  // Auto-generated methods will be added here based on Move modules
-}
-
-export * from './types';
-"#;
-    fs::write(src_path.join("index.ts"), index_ts)?;
-
-    // Generate types.ts
-    let types_ts = r#"/**
- * Auto-generated TypeScript types for Sui smart contracts
- */
-
-export interface TransactionResult {
-  digest: string;
-  effects: any;
-}
-
-export interface ObjectRef {
-  objectId: string;
-  version: string;
-  digest: string;
-}
+    fs::write(src_path.join("constants.ts"), constants_ts(interface))?;
+    fs::write(src_path.join("utils.ts"), UTILS_TS)?;
+    for (name, module) in &interface.modules {
+        fs::write(
+            src_path.join(format!("{}.ts", name)),
+            module_ts(interface, module),
+        )?;
+    }
+    fs::write(src_path.join("index.ts"), index_ts(interface))?;
 
-// Additional types will be generated based on Move structs
-"#;
-    fs::write(src_path.join("types.ts"), types_ts)?;
+    // Generate BCS round-trip tests
+    let test_path = output_path.join("test");
+    fs::create_dir_all(&test_path)?;
+    for (name, module) in &interface.modules {
+        if let Some(test) = module_test_ts(interface, module) {
+            fs::write(test_path.join(format!("{}.test.ts", name)), test)?;
+        }
+    }
 
     // Generate README
     let readme = r#"# SuiForge TypeScript SDK
@@ -118,20 +114,43 @@ npm run build
 
 ## Usage
 
+Every Move module has a namespace with a builder per public or entry
+function. A builder appends the call to a transaction block and returns its
+result, so calls can be chained:
+
 ```typescript
 import { SuiClient } from '@mysten/sui.js/client';
-import { SuiForgeClient } from './src';
+import { TransactionBlock } from '@mysten/sui.js/transactions';
+import { main } from './src';
 
 const client = new SuiClient({ url: 'https://fullnode.devnet.sui.io:443' });
-const packageId = '0x...'; // Your deployed package ID
 
-const suiforge = new SuiForgeClient(client, packageId);
+const tx = new TransactionBlock();
+const [item] = main.create(tx, { value: 10 });
+main.transferItem(tx, { item, recipient: '0x...' });
+```
+
+Objects are passed by ID or as the result of an earlier call. `TxContext`,
+`Clock`, `Random` and `DenyList` are filled in. Builders call the package in
+`src/constants.ts`; pass a package ID as the last argument to call another
+one.
+
+Struct interfaces describe both the JSON the fullnode returns for them, e.g.
+an event's `parsedJson`, and what their BCS layouts decode to: `u64` and wider
+integers are strings, `UID` is `{ id: string }`, `Balance<T>` a string and
+`Option<T>` is `T | null`. Every struct whose fields have known layouts gets a
+`<Name>Bcs` layout (a function taking the layouts of its type arguments if it
+is generic), and every object type a `fetch<Name>` helper:
+
+```typescript
+import { bcs } from '@mysten/sui.js/bcs';
 
-// Use the generated methods
-const tx = suiforge.createTransaction();
-// ... add transaction commands
+const item = await main.fetchItem(client, '0x...');
+const pool = await vault.fetchPool(client, '0x...', bcs.u64());
 ```
 
+`npm test` round-trips a sample of every layout.
+
 ## Development
 
 This SDK is auto-generated by SuiForge. To regenerate:
@@ -144,3 +163,753 @@ suiforge generate ts
 
     Ok(())
 }
+
+const UTILS_TS: &str = r#"import { BcsType } from '@mysten/sui.js/bcs';
+import { SuiClient } from '@mysten/sui.js/client';
+import { TransactionArgument, TransactionBlock } from '@mysten/sui.js/transactions';
+import { fromB64 } from '@mysten/sui.js/utils';
+
+/** An object ID, or the result of an earlier command in the same transaction. */
+export type ObjectArg = string | TransactionArgument;
+
+export function obj(tx: TransactionBlock, value: ObjectArg): TransactionArgument {
+  return typeof value === 'string' ? tx.object(value) : value;
+}
+
+/** Fetch an object of `type` (any instantiation) and decode it with `layout`. */
+export async function fetchObject<T>(
+  client: SuiClient,
+  id: string,
+  layout: BcsType<T, any>,
+  type: string,
+): Promise<T> {
+  const { data, error } = await client.getObject({ id, options: { showBcs: true } });
+  if (!data?.bcs) {
+    throw new Error(`Could not fetch object ${id}: ${error?.code ?? 'no data'}`);
+  }
+  if (data.bcs.dataType !== 'moveObject') {
+    throw new Error(`${id} is a package, not a ${type}`);
+  }
+  if (data.bcs.type !== type && !data.bcs.type.startsWith(`${type}<`)) {
+    throw new Error(`${id} is a ${data.bcs.type}, not a ${type}`);
+  }
+  return layout.parse(fromB64(data.bcs.bcsBytes));
+}
+"#;
+
+fn constants_ts(interface: &PackageInterface) -> String {
+    let type_address = interface.type_address();
+    let (package_id, original_id) = match &interface.package_id {
+        Some(id) => (id.as_str(), type_address),
+        None => ("0x0", "0x0"),
+    };
+    let mut out = String::from("/** Auto-generated by SuiForge. Do not edit. */\n\n");
+    if interface.package_id.is_none() {
+        out.push_str(
+            "// The package is not deployed yet: regenerate after `suiforge deploy`, or pass\n\
+             // the package ID to each builder.\n",
+        );
+    }
+    out.push_str("/** The package calls go to. */\n");
+    out.push_str(&format!("export const PACKAGE_ID = '{}';\n\n", package_id));
+    out.push_str("/** The package's first version, which its types keep across upgrades. */\n");
+    out.push_str(&format!(
+        "export const ORIGINAL_PACKAGE_ID = '{}';\n",
+        original_id
+    ));
+    out
+}
+
+fn index_ts(interface: &PackageInterface) -> String {
+    let mut out = String::from("/** Auto-generated by SuiForge. Do not edit. */\n\n");
+    out.push_str("import { SuiClient } from '@mysten/sui.js/client';\n");
+    out.push_str("import { TransactionBlock } from '@mysten/sui.js/transactions';\n");
+    out.push_str("import { PACKAGE_ID } from './constants';\n\n");
+    out.push_str("export * from './constants';\n");
+    out.push_str("export * from './utils';\n");
+    for name in interface.modules.keys() {
+        out.push_str(&format!(
+            "export * as {} from './{}';\n",
+            identifier(name),
+            name
+        ));
+    }
+    out.push_str(
+        r#"
+export class SuiForgeClient {
+  constructor(
+    public readonly client: SuiClient,
+    public readonly packageId: string = PACKAGE_ID,
+  ) {}
+
+  /**
+   * Create a new transaction block
+   */
+  createTransaction(): TransactionBlock {
+    return new TransactionBlock();
+  }
+}
+"#,
+    );
+    out
+}
+
+fn module_ts(interface: &PackageInterface, module: &NormalizedModule) -> String {
+    let ctx = Context {
+        interface,
+        module: &module.name,
+    };
+    let mut imports = BTreeSet::new();
+    let mut body = String::new();
+    let mut constants = Vec::new();
+
+    // Layouts refer to the ones before them, so structs come in dependency
+    // order.
+    for name in struct_order(module) {
+        body.push_str(&struct_ts(&ctx, name, &module.structs[name], &mut imports));
+    }
+    if !module.structs.is_empty() {
+        constants.push("ORIGINAL_PACKAGE_ID");
+    }
+    let mut functions = callable_functions(&module.exposed_functions).peekable();
+    if functions.peek().is_some() {
+        constants.push("PACKAGE_ID");
+    }
+    for (name, function) in functions {
+        body.push_str(&function_ts(&ctx, name, function));
+    }
+
+    let mut out = String::from("/** Auto-generated by SuiForge. Do not edit. */\n\n");
+    if body.contains("BcsType<") {
+        out.push_str("import { BcsType, bcs } from '@mysten/sui.js/bcs';\n");
+    } else if body.contains("bcs.") {
+        out.push_str("import { bcs } from '@mysten/sui.js/bcs';\n");
+    }
+    if body.contains("client: SuiClient") {
+        out.push_str("import { SuiClient } from '@mysten/sui.js/client';\n");
+    }
+    if constants.contains(&"PACKAGE_ID") {
+        out.push_str("import { TransactionBlock } from '@mysten/sui.js/transactions';\n");
+    }
+    if !constants.is_empty() {
+        out.push_str(&format!(
+            "import {{ {} }} from './constants';\n",
+            constants.join(", ")
+        ));
+    }
+    let utils: Vec<&str> = [
+        ("ObjectArg", "ObjectArg"),
+        ("fetchObject(", "fetchObject"),
+        ("obj(", "obj"),
+    ]
+    .into_iter()
+    .filter(|(usage, _)| body.contains(usage))
+    .map(|(_, name)| name)
+    .collect();
+    if !utils.is_empty() {
+        out.push_str(&format!(
+            "import {{ {} }} from './utils';\n",
+            utils.join(", ")
+        ));
+    }
+    for other in &imports {
+        out.push_str(&format!(
+            "import * as {} from './{}';\n",
+            identifier(other),
+            other
+        ));
+    }
+    out.push_str(&body);
+    out
+}
+
+struct Context<'a> {
+    interface: &'a PackageInterface,
+    module: &'a str,
+}
+
+impl Context<'_> {
+    /// How to name a struct of the package from this module, importing its
+    /// module if it is another one.
+    fn qualify(&self, module: &str, name: &str, imports: &mut BTreeSet<String>) -> String {
+        if module == self.module {
+            name.to_string()
+        } else {
+            imports.insert(module.to_string());
+            format!("{}.{}", identifier(module), name)
+        }
+    }
+}
+
+/// The struct's type string, interface, BCS layout and, for objects, a
+/// function that fetches and decodes one.
+fn struct_ts(
+    ctx: &Context,
+    name: &str,
+    def: &NormalizedStruct,
+    imports: &mut BTreeSet<String>,
+) -> String {
+    let type_const = format!("{}_TYPE", upper_snake_case(name));
+    let mut out = String::from("\n");
+    out.push_str(&format!(
+        "export const {} = `${{ORIGINAL_PACKAGE_ID}}::{}::{}`;\n\n",
+        type_const, ctx.module, name
+    ));
+    out.push_str(&doc_comment(ctx.interface.doc(ctx.module, name)));
+    let params: Vec<String> = (0..def.type_parameters.len())
+        .map(|i| format!("T{} = unknown", i))
+        .collect();
+    out.push_str(&format!(
+        "export interface {}{} {{\n",
+        name,
+        type_list(&params)
+    ));
+    let layouts: Vec<Option<Layout>> = def
+        .fields
+        .iter()
+        .map(|field| layout(ctx.interface, &field.type_))
+        .collect();
+    for (field, layout) in def.fields.iter().zip(&layouts) {
+        let ty = match layout {
+            Some(layout) => ts_type(ctx, layout, imports),
+            None => "unknown".to_string(),
+        };
+        out.push_str(&format!("  {}: {};\n", field.name, ty));
+    }
+    out.push_str("}\n");
+
+    // Without the layout of every field the struct cannot be decoded.
+    let Some(layouts) = layouts.into_iter().collect::<Option<Vec<_>>>() else {
+        return out;
+    };
+    let fields: Vec<String> = def
+        .fields
+        .iter()
+        .zip(&layouts)
+        .map(|(field, layout)| format!("  {}: {},", field.name, bcs_type(ctx, layout, imports)))
+        .collect();
+    let layout_params: Vec<usize> = def
+        .type_parameters
+        .iter()
+        .enumerate()
+        .filter(|(_, p)| !p.is_phantom)
+        .map(|(i, _)| i)
+        .collect();
+
+    out.push('\n');
+    if layout_params.is_empty() {
+        out.push_str(&format!(
+            "export const {}Bcs = bcs.struct('{}', {{\n{}\n}});\n",
+            name,
+            name,
+            fields.join("\n")
+        ));
+    } else {
+        // Each non-phantom type parameter takes the layout of its argument.
+        let generics: Vec<String> = layout_params.iter().map(|i| format!("T{}", i)).collect();
+        let args: Vec<String> = layout_params
+            .iter()
+            .map(|i| format!("t{}: BcsType<T{}, any>", i, i))
+            .collect();
+        let fields: Vec<String> = fields.iter().map(|f| format!("  {}", f)).collect();
+        out.push_str(&format!(
+            "export function {}Bcs{}({}) {{\n  return bcs.struct('{}', {{\n{}\n  }});\n}}\n",
+            name,
+            type_list(&generics),
+            args.join(", "),
+            name,
+            fields.join("\n")
+        ));
+    }
+
+    if def.abilities.abilities.iter().any(|a| a == "Key") {
+        let type_args: Vec<String> = (0..def.type_parameters.len())
+            .map(|i| {
+                if layout_params.contains(&i) {
+                    format!("T{}", i)
+                } else {
+                    "unknown".to_string()
+                }
+            })
+            .collect();
+        let generics: Vec<String> = layout_params.iter().map(|i| format!("T{}", i)).collect();
+        let mut params = vec!["client: SuiClient".to_string(), "id: string".to_string()];
+        params.extend(
+            layout_params
+                .iter()
+                .map(|i| format!("t{}: BcsType<T{}, any>", i, i)),
+        );
+        let layout = if layout_params.is_empty() {
+            format!("{}Bcs", name)
+        } else {
+            let args: Vec<String> = layout_params.iter().map(|i| format!("t{}", i)).collect();
+            format!("{}Bcs({})", name, args.join(", "))
+        };
+        out.push_str(&format!(
+            "\n/** Fetch a `{}` object and decode its contents. */\n",
+            name
+        ));
+        out.push_str(&format!(
+            "export function fetch{}{}(\n",
+            name,
+            type_list(&generics)
+        ));
+        for param in &params {
+            out.push_str(&format!("  {},\n", param));
+        }
+        out.push_str(&format!(
+            "): Promise<{}{}> {{\n  return fetchObject(client, id, {}, {});\n}}\n",
+            name,
+            type_list(&type_args),
+            layout,
+            type_const
+        ));
+    }
+    out
+}
+
+/// The TypeScript type of a decoded value, which is also its JSON shape.
+fn ts_type(ctx: &Context, layout: &Layout, imports: &mut BTreeSet<String>) -> String {
+    match layout {
+        Layout::Bool => "boolean".to_string(),
+        Layout::U8 | Layout::U16 | Layout::U32 => "number".to_string(),
+        Layout::U64 | Layout::U128 | Layout::U256 | Layout::Address | Layout::String => {
+            "string".to_string()
+        }
+        Layout::Vector(inner) => array_of(&ts_type(ctx, inner, imports)),
+        Layout::Option(inner) => format!("{} | null", ts_type(ctx, inner, imports)),
+        Layout::Framework { fields, .. } => {
+            let fields: Vec<String> = fields
+                .iter()
+                .map(|(name, layout)| format!("{}: {}", name, ts_type(ctx, layout, imports)))
+                .collect();
+            format!("{{ {} }}", fields.join("; "))
+        }
+        Layout::Struct {
+            module,
+            name,
+            type_arguments,
+        } => {
+            let args: Vec<String> = type_arguments
+                .iter()
+                .map(|arg| match &arg.layout {
+                    Some(layout) => ts_type(ctx, layout, imports),
+                    None => "unknown".to_string(),
+                })
+                .collect();
+            format!("{}{}", ctx.qualify(module, name, imports), type_list(&args))
+        }
+        Layout::Param(i) => format!("T{}", i),
+    }
+}
+
+/// The `bcs` expression for a layout.
+fn bcs_type(ctx: &Context, layout: &Layout, imports: &mut BTreeSet<String>) -> String {
+    match layout {
+        Layout::Bool => "bcs.bool()".to_string(),
+        Layout::U8 => "bcs.u8()".to_string(),
+        Layout::U16 => "bcs.u16()".to_string(),
+        Layout::U32 => "bcs.u32()".to_string(),
+        Layout::U64 => "bcs.u64()".to_string(),
+        Layout::U128 => "bcs.u128()".to_string(),
+        Layout::U256 => "bcs.u256()".to_string(),
+        Layout::Address => "bcs.Address".to_string(),
+        Layout::String => "bcs.string()".to_string(),
+        Layout::Vector(inner) => format!("bcs.vector({})", bcs_type(ctx, inner, imports)),
+        Layout::Option(inner) => format!("bcs.option({})", bcs_type(ctx, inner, imports)),
+        Layout::Framework { name, fields } => {
+            let fields: Vec<String> = fields
+                .iter()
+                .map(|(field, layout)| format!("{}: {}", field, bcs_type(ctx, layout, imports)))
+                .collect();
+            format!("bcs.struct('{}', {{ {} }})", name, fields.join(", "))
+        }
+        Layout::Struct {
+            module,
+            name,
+            type_arguments,
+        } => {
+            let layout = ctx.qualify(module, &format!("{}Bcs", name), imports);
+            let args: Vec<String> = type_arguments
+                .iter()
+                .filter(|arg| !arg.is_phantom)
+                .filter_map(|arg| arg.layout.as_ref())
+                .map(|layout| bcs_type(ctx, layout, imports))
+                .collect();
+            if args.is_empty() {
+                layout
+            } else {
+                format!("{}({})", layout, args.join(", "))
+            }
+        }
+        Layout::Param(i) => format!("t{}", i),
+    }
+}
+
+/// A Jest test that round-trips a sample of every struct of the module
+/// through its BCS layout, or `None` if no struct has one.
+fn module_test_ts(interface: &PackageInterface, module: &NormalizedModule) -> Option<String> {
+    let ctx = Context {
+        interface,
+        module: &module.name,
+    };
+    let mut tests = String::new();
+    for name in struct_order(module) {
+        let def = &module.structs[name];
+        let layouts: Option<Vec<Layout>> = def
+            .fields
+            .iter()
+            .map(|field| layout(interface, &field.type_))
+            .collect();
+        let Some(layouts) = layouts else {
+            continue;
+        };
+
+        // Type parameters are instantiated with `u64`.
+        let params: Vec<Option<String>> = def
+            .type_parameters
+            .iter()
+            .map(|p| (!p.is_phantom).then(|| "'1'".to_string()))
+            .collect();
+        let layout_args: Vec<&str> = def
+            .type_parameters
+            .iter()
+            .filter(|p| !p.is_phantom)
+            .map(|_| "bcs.u64()")
+            .collect();
+        let layout = if layout_args.is_empty() {
+            format!("{}.{}Bcs", identifier(ctx.module), name)
+        } else {
+            format!(
+                "{}.{}Bcs({})",
+                identifier(ctx.module),
+                name,
+                layout_args.join(", ")
+            )
+        };
+        let fields: Vec<String> = def
+            .fields
+            .iter()
+            .zip(&layouts)
+            .map(|(field, layout)| {
+                format!(
+                    "      {}: {},",
+                    field.name,
+                    sample(interface, layout, &params)
+                )
+            })
+            .collect();
+        tests.push_str(&format!(
+            "\n  test('{} round-trips through BCS', () => {{\n    const layout = {};\n    const value = {{\n{}\n    }};\n    expect(layout.parse(layout.serialize(value).toBytes())).toEqual(value);\n  }});\n",
+            name,
+            layout,
+            fields.join("\n")
+        ));
+    }
+    if tests.is_empty() {
+        return None;
+    }
+
+    let mut out = String::from("/** Auto-generated by SuiForge. Do not edit. */\n\n");
+    if tests.contains("bcs.") {
+        out.push_str("import { bcs } from '@mysten/sui.js/bcs';\n");
+    }
+    out.push_str(&format!(
+        "import * as {} from '../src/{}';\n\ndescribe('{}', () => {{{}}});\n",
+        identifier(ctx.module),
+        ctx.module,
+        ctx.module,
+        tests
+    ));
+    Some(out)
+}
+
+/// A TypeScript literal of a value with `layout`, as it decodes. `params`
+/// holds samples of the enclosing struct's type arguments.
+fn sample(interface: &PackageInterface, layout: &Layout, params: &[Option<String>]) -> String {
+    match layout {
+        Layout::Bool => "true".to_string(),
+        Layout::U8 | Layout::U16 | Layout::U32 => "7".to_string(),
+        Layout::U64 | Layout::U128 | Layout::U256 => "'42'".to_string(),
+        Layout::Address => format!("'0x{}'", "ab".repeat(32)),
+        Layout::String => "'sample'".to_string(),
+        Layout::Vector(inner) => format!("[{}]", sample(interface, inner, params)),
+        Layout::Option(inner) => sample(interface, inner, params),
+        Layout::Framework { fields, .. } => {
+            let fields: Vec<String> = fields
+                .iter()
+                .map(|(name, layout)| format!("{}: {}", name, sample(interface, layout, params)))
+                .collect();
+            format!("{{ {} }}", fields.join(", "))
+        }
+        Layout::Struct {
+            module,
+            name,
+            type_arguments,
+        } => {
+            let def = &interface.modules[module].structs[name];
+            let args: Vec<Option<String>> = type_arguments
+                .iter()
+                .map(|arg| match (&arg.layout, arg.is_phantom) {
+                    (Some(layout), false) => Some(sample(interface, layout, params)),
+                    _ => None,
+                })
+                .collect();
+            let fields: Vec<String> = def
+                .fields
+                .iter()
+                .filter_map(|field| {
+                    let layout = super::bcs::layout(interface, &field.type_)?;
+                    Some(format!(
+                        "{}: {}",
+                        field.name,
+                        sample(interface, &layout, &args)
+                    ))
+                })
+                .collect();
+            format!("{{ {} }}", fields.join(", "))
+        }
+        Layout::Param(i) => params
+            .get(*i as usize)
+            .cloned()
+            .flatten()
+            .unwrap_or_else(|| "'42'".to_string()),
+    }
+}
+
+fn function_ts(ctx: &Context, name: &str, function: &NormalizedFunction) -> String {
+    let names = ctx
+        .interface
+        .param_names(ctx.module, name, function.parameters.len());
+    let mut seen = BTreeSet::new();
+    let mut fields = Vec::new();
+    let mut arguments = Vec::new();
+    for (i, (ty, param)) in function.parameters.iter().zip(&names).enumerate() {
+        if is_tx_context(ty) {
+            continue;
+        }
+        if let Some(id) = system_object(ty) {
+            arguments.push(format!("tx.object('{}')", id));
+            continue;
+        }
+        let mut field = camel_case(param.trim_start_matches('_'));
+        if field.is_empty() || !seen.insert(field.clone()) {
+            field = format!("arg{}", i);
+            seen.insert(field.clone());
+        }
+        let (ts_type, expr) = argument(ty, &format!("args.{}", field));
+        fields.push(format!("{}: {}", field, ts_type));
+        arguments.push(expr);
+    }
+
+    let mut params = vec!["tx: TransactionBlock".to_string()];
+    if !fields.is_empty() {
+        params.push(format!("args: {{ {} }}", fields.join("; ")));
+    }
+    let generic = !function.type_parameters.is_empty();
+    if generic {
+        let types = vec!["string"; function.type_parameters.len()];
+        params.push(format!("typeArguments: [{}]", types.join(", ")));
+    }
+    params.push("packageId: string = PACKAGE_ID".to_string());
+
+    let mut out = String::from("\n");
+    out.push_str(&doc_comment(ctx.interface.doc(ctx.module, name)));
+    out.push_str(&format!("export function {}(\n", identifier(&camel_case(name))));
+    for param in &params {
+        out.push_str(&format!("  {},\n", param));
+    }
+    out.push_str(") {\n  return tx.moveCall({\n");
+    out.push_str(&format!(
+        "    target: `${{packageId}}::{}::{}`,\n",
+        ctx.module, name
+    ));
+    let inline = arguments.join(", ");
+    if inline.len() <= 60 {
+        out.push_str(&format!("    arguments: [{}],\n", inline));
+    } else {
+        out.push_str("    arguments: [\n");
+        for argument in &arguments {
+            out.push_str(&format!("      {},\n", argument));
+        }
+        out.push_str("    ],\n");
+    }
+    if generic {
+        out.push_str("    typeArguments,\n");
+    }
+    out.push_str("  });\n}\n");
+    out
+}
+
+/// The TypeScript type a builder takes for a parameter of type `ty`, and
+/// the expression that turns `value` into a transaction argument.
+fn argument(ty: &NormalizedType, value: &str) -> (String, String) {
+    let by_value = match ty {
+        NormalizedType::Reference(inner) | NormalizedType::MutableReference(inner) => inner,
+        _ => ty,
+    };
+    if let Some(schema) = bcs_schema(by_value) {
+        let expr = match by_value {
+            NormalizedType::Bool => format!("tx.pure.bool({})", value),
+            NormalizedType::U8 => format!("tx.pure.u8({})", value),
+            NormalizedType::U16 => format!("tx.pure.u16({})", value),
+            NormalizedType::U32 => format!("tx.pure.u32({})", value),
+            NormalizedType::U64 => format!("tx.pure.u64({})", value),
+            NormalizedType::U128 => format!("tx.pure.u128({})", value),
+            NormalizedType::U256 => format!("tx.pure.u256({})", value),
+            NormalizedType::Address => format!("tx.pure.address({})", value),
+            _ if is_struct(by_value, "0x2", "object", "ID") => {
+                format!("tx.pure.address({})", value)
+            }
+            _ if is_string(by_value) => format!("tx.pure.string({})", value),
+            _ => format!("tx.pure({}.serialize({}))", schema, value),
+        };
+        return (pure_type(by_value), expr);
+    }
+    if let NormalizedType::Vector(_) = ty {
+        return (
+            "ObjectArg[]".to_string(),
+            format!(
+                "tx.makeMoveVec({{ objects: {}.map((o) => obj(tx, o)) }})",
+                value
+            ),
+        );
+    }
+    ("ObjectArg".to_string(), format!("obj(tx, {})", value))
+}
+
+/// The BCS type of a pure value, or `None` if `ty` is not one.
+fn bcs_schema(ty: &NormalizedType) -> Option<String> {
+    Some(match ty {
+        NormalizedType::Bool => "bcs.bool()".to_string(),
+        NormalizedType::U8 => "bcs.u8()".to_string(),
+        NormalizedType::U16 => "bcs.u16()".to_string(),
+        NormalizedType::U32 => "bcs.u32()".to_string(),
+        NormalizedType::U64 => "bcs.u64()".to_string(),
+        NormalizedType::U128 => "bcs.u128()".to_string(),
+        NormalizedType::U256 => "bcs.u256()".to_string(),
+        NormalizedType::Address => "bcs.Address".to_string(),
+        NormalizedType::Vector(inner) => format!("bcs.vector({})", bcs_schema(inner)?),
+        _ if is_struct(ty, "0x2", "object", "ID") => "bcs.Address".to_string(),
+        _ if is_string(ty) => "bcs.string()".to_string(),
+        _ => format!("bcs.option({})", bcs_schema(option_inner(ty)?)?),
+    })
+}
+
+/// The TypeScript type accepted for a pure value.
+fn pure_type(ty: &NormalizedType) -> String {
+    match ty {
+        NormalizedType::Bool => "boolean".to_string(),
+        NormalizedType::U8 | NormalizedType::U16 | NormalizedType::U32 => "number".to_string(),
+        NormalizedType::U64 | NormalizedType::U128 | NormalizedType::U256 => {
+            "bigint | number | string".to_string()
+        }
+        NormalizedType::Vector(inner) if **inner == NormalizedType::U8 => {
+            "Uint8Array | number[]".to_string()
+        }
+        NormalizedType::Vector(inner) => array_of(&pure_type(inner)),
+        _ => match option_inner(ty) {
+            Some(inner) => format!("{} | null", pure_type(inner)),
+            None => "string".to_string(),
+        },
+    }
+}
+
+fn is_string(ty: &NormalizedType) -> bool {
+    is_struct(ty, "0x1", "string", "String") || is_struct(ty, "0x1", "ascii", "String")
+}
+
+fn option_inner(ty: &NormalizedType) -> Option<&NormalizedType> {
+    match ty {
+        NormalizedType::Struct { type_arguments, .. } if is_struct(ty, "0x1", "option", "Option") => {
+            type_arguments.first()
+        }
+        _ => None,
+    }
+}
+
+fn array_of(element: &str) -> String {
+    if element.contains(' ') {
+        format!("({})[]", element)
+    } else {
+        format!("{}[]", element)
+    }
+}
+
+fn doc_comment(doc: Option<&str>) -> String {
+    let Some(doc) = doc else {
+        return String::new();
+    };
+    let mut out = String::from("/**\n");
+    for line in doc.lines().map(str::trim) {
+        if line.is_empty() {
+            out.push_str(" *\n");
+        } else {
+            out.push_str(&format!(" * {}\n", line));
+        }
+    }
+    out.push_str(" */\n");
+    out
+}
+
+/// `name`, with a trailing underscore if it is a reserved word.
+fn identifier(name: &str) -> String {
+    if RESERVED.contains(&name) {
+        format!("{}_", name)
+    } else {
+        name.to_string()
+    }
+}
+
+/// `MintEvent` as `MINT_EVENT`.
+fn upper_snake_case(name: &str) -> String {
+    let mut out = String::new();
+    let chars: Vec<char> = name.chars().collect();
+    for (i, c) in chars.iter().enumerate() {
+        let boundary = i > 0
+            && c.is_uppercase()
+            && (chars[i - 1].is_lowercase()
+                || chars.get(i + 1).is_some_and(|next| next.is_lowercase()));
+        if boundary && !out.ends_with('_') {
+            out.push('_');
+        }
+        out.extend(c.to_uppercase());
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codegen::tests::shop_interface;
+
+    fn generated(file: &str) -> String {
+        let dir = tempfile::tempdir().unwrap();
+        generate(dir.path(), "sdk", &shop_interface()).unwrap();
+        fs::read_to_string(dir.path().join("sdk/src").join(file)).unwrap()
+    }
+
+    #[test]
+    fn builders_escape_reserved_words() {
+        let shop = generated("shop.ts");
+
+        assert!(shop.contains(
+            "export function delete_(\n  tx: TransactionBlock,\n  args: { shop: ObjectArg },"
+        ));
+        assert!(shop.contains("arguments: [obj(tx, args.shop), tx.object('0x6')],"));
+        assert!(shop.contains("tx.pure.u64(args.in),"));
+        assert!(shop.contains("tx.pure(bcs.vector(bcs.u8()).serialize(args.from)),"));
+        assert!(!shop.contains("function internal"));
+    }
+
+    #[test]
+    fn bcs_layouts_follow_the_fields() {
+        let shop = generated("shop.ts");
+
+        assert!(shop.contains(
+            "export const BoughtBcs = bcs.struct('Bought', {\n  buyer: bcs.Address,\n  price: bcs.u64(),\n  item: bcs.vector(bcs.u8()),\n});"
+        ));
+        assert!(shop.contains("export function BoxedBcs<T0>(t0: BcsType<T0, any>) {"));
+        assert!(!shop.contains("ForeignBcs"));
+    }
+}
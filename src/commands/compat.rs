@@ -0,0 +1,622 @@
+use crate::abi::{self, is_resolved, same_type, PackageAbi};
+use crate::commands::upgrade::{fetch_upgrade_cap, UpgradePolicy};
+use crate::config::{find_project_root, resolve_network};
+use crate::error::{Result, SuiForgeError};
+use crate::lock::LockFile;
+use crate::rpc::types::{NormalizedFunction, NormalizedStruct, NormalizedType};
+use crate::rpc::RpcClient;
+use crate::utils;
+use colored::Colorize;
+use serde::Serialize;
+use std::path::Path;
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Changed,
+    /// Differs, but involves a type the source could not be resolved to, so
+    /// whether the upgrade policy allows it is unknown.
+    Unknown,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AbiChange {
+    pub kind: ChangeKind,
+    /// `module`, `module::Struct` or `module::function`.
+    pub item: String,
+    pub description: String,
+    /// Whether the upgrade policy rejects this change.
+    pub breaking: bool,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CompatReport<'a> {
+    package_id: &'a str,
+    policy: String,
+    compatible: bool,
+    changes: &'a [AbiChange],
+}
+
+pub async fn execute(
+    network: Option<String>,
+    package_id: Option<String>,
+    policy: Option<String>,
+    strict: bool,
+    format: String,
+) -> Result<()> {
+    let root = find_project_root()?;
+    let network = resolve_network(network.as_deref())?;
+    let client = RpcClient::for_network(&network);
+    let lock = LockFile::load(&root)?;
+    let deployment = lock.network(&network.name);
+
+    let package_id = match package_id.or_else(|| deployment.map(|d| d.package_id.clone())) {
+        Some(id) => id,
+        None => {
+            return Err(SuiForgeError::Custom(format!(
+                "No deployment recorded for {}; pass --package <id>",
+                network.name
+            )))
+        }
+    };
+
+    let policy = match policy.as_deref() {
+        Some("compatible") => UpgradePolicy::Compatible,
+        Some("additive") => UpgradePolicy::Additive,
+        Some("dep_only") | Some("dep-only") => UpgradePolicy::DepOnly,
+        Some(other) => {
+            return Err(SuiForgeError::Custom(format!(
+                "Unknown upgrade policy: {} (expected compatible, additive or dep_only)",
+                other
+            )))
+        }
+        None => match deployment.and_then(|d| d.upgrade_cap_id.as_deref()) {
+            Some(cap_id) => fetch_upgrade_cap(&client, cap_id).await?.policy,
+            None => UpgradePolicy::Compatible,
+        },
+    };
+
+    let text = format != "json";
+    if text {
+        utils::info(&format!(
+            "Checking local sources against {} on {} (policy: {})",
+            package_id.green(),
+            network.name.yellow(),
+            policy.to_string().cyan()
+        ));
+    }
+
+    let spinner = utils::create_spinner("Fetching deployed modules...");
+    let changes = check(&client, &root, &package_id, policy).await;
+    spinner.finish_and_clear();
+    let changes = changes?;
+    let breaking = changes.iter().filter(|c| c.breaking).count();
+
+    if text {
+        print_changes(&changes, policy);
+    } else {
+        let report = CompatReport {
+            package_id: &package_id,
+            policy: policy.to_string(),
+            compatible: breaking == 0,
+            changes: &changes,
+        };
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    }
+
+    if breaking > 0 {
+        return Err(SuiForgeError::Custom(format!(
+            "{} breaking change(s) under the {} policy",
+            breaking, policy
+        )));
+    }
+    let unknown = changes
+        .iter()
+        .filter(|c| c.kind == ChangeKind::Unknown)
+        .count();
+    if strict && unknown > 0 {
+        return Err(SuiForgeError::Custom(format!(
+            "{} change(s) could not be checked against the {} policy (--strict)",
+            unknown, policy
+        )));
+    }
+    Ok(())
+}
+
+/// Compare the local sources under `root` with the deployed package.
+pub async fn check(
+    client: &RpcClient,
+    root: &Path,
+    package_id: &str,
+    policy: UpgradePolicy,
+) -> Result<Vec<AbiChange>> {
+    let deployed = abi::fetch(client, package_id).await?;
+
+    // Local types in the package must carry the deployed package's address
+    // to compare equal with the on-chain ones.
+    let self_address = deployed
+        .values()
+        .next()
+        .map(|m| m.address.clone())
+        .unwrap_or_else(|| package_id.to_string());
+    let local = abi::load_local(root, &self_address)?;
+
+    Ok(compare(&deployed, &local, policy))
+}
+
+/// Compare the deployed ABI with the new one under `policy`.
+///
+/// Sui's rules: with `compatible`, public functions and all structs must keep
+/// their exact signatures and layouts, while friend, package and entry
+/// functions may change freely. `additive` additionally forbids changing or
+/// removing any existing function, and `dep_only` forbids adding anything.
+/// Function bodies are not part of the ABI and are not compared.
+pub fn compare(old: &PackageAbi, new: &PackageAbi, policy: UpgradePolicy) -> Vec<AbiChange> {
+    let mut changes = Vec::new();
+    let additions_break = policy == UpgradePolicy::DepOnly;
+    let mut push = |kind, item: String, description: String, breaking| {
+        changes.push(AbiChange {
+            kind,
+            item,
+            description,
+            breaking,
+        })
+    };
+
+    for (name, old_module) in old {
+        let Some(new_module) = new.get(name) else {
+            push(ChangeKind::Removed, name.clone(), "module removed".to_string(), true);
+            continue;
+        };
+
+        for (struct_name, old_struct) in &old_module.structs {
+            let item = format!("{}::{}", name, struct_name);
+            match new_module.structs.get(struct_name) {
+                None => push(ChangeKind::Removed, item, "struct removed".to_string(), true),
+                Some(new_struct) => {
+                    for (problem, checked) in struct_differences(old_struct, new_struct) {
+                        if checked {
+                            push(ChangeKind::Changed, item.clone(), problem, true);
+                        } else {
+                            push(ChangeKind::Unknown, item.clone(), problem, false);
+                        }
+                    }
+                }
+            }
+        }
+        for struct_name in new_module.structs.keys() {
+            if !old_module.structs.contains_key(struct_name) {
+                push(
+                    ChangeKind::Added,
+                    format!("{}::{}", name, struct_name),
+                    "new struct".to_string(),
+                    additions_break,
+                );
+            }
+        }
+
+        for (fn_name, old_fn) in &old_module.exposed_functions {
+            let item = format!("{}::{}", name, fn_name);
+            let is_public = old_fn.visibility == "Public";
+            // Non-public functions are only frozen under the stricter policies
+            let frozen = is_public || policy != UpgradePolicy::Compatible;
+
+            match new_module.exposed_functions.get(fn_name) {
+                None => push(
+                    ChangeKind::Removed,
+                    item,
+                    format!("{} function removed", visibility_label(old_fn)),
+                    frozen,
+                ),
+                Some(new_fn) => {
+                    if is_public && new_fn.visibility != "Public" {
+                        push(
+                            ChangeKind::Changed,
+                            item.clone(),
+                            format!(
+                                "visibility reduced from public to {}",
+                                visibility_label(new_fn)
+                            ),
+                            true,
+                        );
+                    }
+                    if !same_signature(old_fn, new_fn) {
+                        let description = format!(
+                            "signature changed from {} to {}",
+                            signature(old_fn),
+                            signature(new_fn)
+                        );
+                        if signature_resolved(old_fn) && signature_resolved(new_fn) {
+                            push(ChangeKind::Changed, item.clone(), description, frozen);
+                        } else {
+                            push(ChangeKind::Unknown, item.clone(), description, false);
+                        }
+                    }
+                    if old_fn.is_entry != new_fn.is_entry {
+                        push(
+                            ChangeKind::Changed,
+                            item,
+                            format!(
+                                "entry modifier {}",
+                                if new_fn.is_entry { "added" } else { "removed" }
+                            ),
+                            frozen && !is_public,
+                        );
+                    }
+                }
+            }
+        }
+        for (fn_name, new_fn) in &new_module.exposed_functions {
+            if !old_module.exposed_functions.contains_key(fn_name) {
+                push(
+                    ChangeKind::Added,
+                    format!("{}::{}", name, fn_name),
+                    format!("new {} function", visibility_label(new_fn)),
+                    additions_break,
+                );
+            }
+        }
+    }
+
+    for name in new.keys() {
+        if !old.contains_key(name) {
+            push(
+                ChangeKind::Added,
+                name.clone(),
+                "new module".to_string(),
+                additions_break,
+            );
+        }
+    }
+
+    changes
+}
+
+/// Differences between two versions of a struct, each paired with whether it
+/// could be checked (a layout involving unresolved types cannot).
+fn struct_differences(old: &NormalizedStruct, new: &NormalizedStruct) -> Vec<(String, bool)> {
+    let mut problems = Vec::new();
+
+    let mut old_abilities = old.abilities.abilities.clone();
+    let mut new_abilities = new.abilities.abilities.clone();
+    old_abilities.sort();
+    new_abilities.sort();
+    if old_abilities != new_abilities {
+        problems.push((
+            format!(
+                "abilities changed from [{}] to [{}]",
+                old_abilities.join(", "),
+                new_abilities.join(", ")
+            ),
+            true,
+        ));
+    }
+
+    if old.type_parameters != new.type_parameters {
+        problems.push((
+            "type parameters or their constraints changed".to_string(),
+            true,
+        ));
+    }
+
+    let same_fields = old.fields.len() == new.fields.len()
+        && old
+            .fields
+            .iter()
+            .zip(&new.fields)
+            .all(|(a, b)| a.name == b.name && same_type(&a.type_, &b.type_));
+    if !same_fields {
+        let fields = |s: &NormalizedStruct| {
+            s.fields
+                .iter()
+                .map(|f| format!("{}: {}", f.name, f.type_))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let resolved = |s: &NormalizedStruct| s.fields.iter().all(|f| is_resolved(&f.type_));
+        problems.push((
+            format!(
+                "layout changed from {{ {} }} to {{ {} }}",
+                fields(old),
+                fields(new)
+            ),
+            resolved(old) && resolved(new),
+        ));
+    }
+
+    problems
+}
+
+fn same_signature(a: &NormalizedFunction, b: &NormalizedFunction) -> bool {
+    let same_types = |a: &[NormalizedType], b: &[NormalizedType]| {
+        a.len() == b.len() && a.iter().zip(b).all(|(a, b)| same_type(a, b))
+    };
+    let constraints = |f: &NormalizedFunction| {
+        f.type_parameters
+            .iter()
+            .map(|set| {
+                let mut abilities = set.abilities.clone();
+                abilities.sort();
+                abilities
+            })
+            .collect::<Vec<_>>()
+    };
+
+    same_types(&a.parameters, &b.parameters)
+        && same_types(&a.return_, &b.return_)
+        && constraints(a) == constraints(b)
+}
+
+fn signature_resolved(f: &NormalizedFunction) -> bool {
+    f.parameters.iter().chain(&f.return_).all(is_resolved)
+}
+
+fn signature(f: &NormalizedFunction) -> String {
+    let list = |types: &[NormalizedType]| {
+        types
+            .iter()
+            .map(|t| t.to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let type_params = if f.type_parameters.is_empty() {
+        String::new()
+    } else {
+        let params: Vec<String> = f
+            .type_parameters
+            .iter()
+            .enumerate()
+            .map(|(i, set)| {
+                if set.abilities.is_empty() {
+                    format!("T{}", i)
+                } else {
+                    format!("T{}: {}", i, set.abilities.join(" + "))
+                }
+            })
+            .collect();
+        format!("<{}>", params.join(", "))
+    };
+    format!(
+        "{}({}): ({})",
+        type_params,
+        list(&f.parameters),
+        list(&f.return_)
+    )
+}
+
+fn visibility_label(f: &NormalizedFunction) -> &'static str {
+    match (f.visibility.as_str(), f.is_entry) {
+        ("Public", _) => "public",
+        ("Friend", _) => "public(package)",
+        (_, true) => "entry",
+        _ => "private",
+    }
+}
+
+fn print_changes(changes: &[AbiChange], policy: UpgradePolicy) {
+    println!();
+    if changes.is_empty() {
+        utils::success("No ABI changes");
+        return;
+    }
+
+    for change in changes {
+        let marker = match (change.breaking, change.kind) {
+            (true, _) => "✗ BREAKING".red().bold(),
+            (false, ChangeKind::Added) => "+ added   ".green(),
+            (false, ChangeKind::Removed) => "- removed ".yellow(),
+            (false, ChangeKind::Changed) => "~ changed ".yellow(),
+            (false, ChangeKind::Unknown) => "? unknown ".magenta(),
+        };
+        println!("{}  {}  {}", marker, change.item.cyan(), change.description);
+    }
+
+    let breaking = changes.iter().filter(|c| c.breaking).count();
+    println!();
+    if breaking == 0 {
+        utils::success(&format!(
+            "{} change(s), all allowed by the {} policy",
+            changes.len(),
+            policy
+        ));
+    } else {
+        utils::error(&format!(
+            "{} of {} change(s) are not allowed by the {} policy",
+            breaking,
+            changes.len(),
+            policy
+        ));
+    }
+    let unknown = changes
+        .iter()
+        .filter(|c| c.kind == ChangeKind::Unknown)
+        .count();
+    if unknown > 0 {
+        utils::warning(&format!(
+            "{} change(s) involve types that could not be resolved from the sources; check them by hand",
+            unknown
+        ));
+    }
+    if policy != UpgradePolicy::Compatible {
+        utils::info("Function bodies are not compared; review implementation changes separately");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+
+    const PKG: &str = "0x5a1e";
+
+    fn uid() -> Value {
+        json!({"Struct": {"address": "0x2", "module": "object", "name": "UID", "typeArguments": []}})
+    }
+
+    fn pool_ref() -> Value {
+        json!({"MutableReference": {"Struct": {
+            "address": PKG, "module": "pool", "name": "Pool", "typeArguments": []
+        }}})
+    }
+
+    fn function(visibility: &str, is_entry: bool, parameters: Vec<Value>) -> Value {
+        json!({
+            "visibility": visibility,
+            "isEntry": is_entry,
+            "typeParameters": [],
+            "parameters": parameters,
+            "return": [],
+        })
+    }
+
+    /// A `pool` module with a public, a package and an entry-only function.
+    fn deployed() -> Value {
+        json!({
+            "fileFormatVersion": 6,
+            "address": PKG,
+            "name": "pool",
+            "structs": {
+                "Pool": {
+                    "abilities": {"abilities": ["Key", "Store"]},
+                    "typeParameters": [],
+                    "fields": [
+                        {"name": "id", "type": uid()},
+                        {"name": "balance", "type": "U64"},
+                    ],
+                },
+            },
+            "exposedFunctions": {
+                "deposit": function("Public", false, vec![pool_ref(), json!("U64")]),
+                "refill": function("Friend", false, vec![json!("U64")]),
+                "admin": function("Private", true, vec![pool_ref()]),
+            },
+        })
+    }
+
+    fn abi(module: Value) -> PackageAbi {
+        PackageAbi::from([("pool".to_string(), serde_json::from_value(module).unwrap())])
+    }
+
+    fn changes(new: Value, policy: UpgradePolicy) -> Vec<(ChangeKind, String, bool)> {
+        compare(&abi(deployed()), &abi(new), policy)
+            .into_iter()
+            .map(|c| (c.kind, c.item, c.breaking))
+            .collect()
+    }
+
+    #[test]
+    fn identical_packages_have_no_changes() {
+        for policy in [
+            UpgradePolicy::Compatible,
+            UpgradePolicy::Additive,
+            UpgradePolicy::DepOnly,
+        ] {
+            assert!(changes(deployed(), policy).is_empty());
+        }
+    }
+
+    #[test]
+    fn compatible_allows_additions_and_non_public_changes() {
+        let mut new = deployed();
+        new["exposedFunctions"]["refill"]["parameters"] = json!(["U64", "U64"]);
+        new["exposedFunctions"]["admin"]["parameters"] = json!([pool_ref(), "Bool"]);
+        new["exposedFunctions"]["withdraw"] = function("Public", false, vec![pool_ref()]);
+
+        assert_eq!(
+            changes(new, UpgradePolicy::Compatible),
+            [
+                (ChangeKind::Changed, "pool::admin".to_string(), false),
+                (ChangeKind::Changed, "pool::refill".to_string(), false),
+                (ChangeKind::Added, "pool::withdraw".to_string(), false),
+            ]
+        );
+    }
+
+    #[test]
+    fn compatible_rejects_public_and_layout_changes() {
+        let mut new = deployed();
+        new["exposedFunctions"]["deposit"]["parameters"] = json!([pool_ref(), "U128"]);
+        new["structs"]["Pool"]["fields"][1]["type"] = json!("U128");
+        new["structs"]["Pool"]["abilities"] = json!({"abilities": ["Key"]});
+
+        let found = changes(new.clone(), UpgradePolicy::Compatible);
+        assert_eq!(found.len(), 3);
+        assert!(found
+            .iter()
+            .all(|(kind, _, breaking)| *kind == ChangeKind::Changed && *breaking));
+
+        new["exposedFunctions"]
+            .as_object_mut()
+            .unwrap()
+            .remove("deposit");
+        assert!(changes(new, UpgradePolicy::Compatible).contains(&(
+            ChangeKind::Removed,
+            "pool::deposit".to_string(),
+            true
+        )));
+    }
+
+    #[test]
+    fn additive_freezes_every_existing_function() {
+        let mut new = deployed();
+        new["exposedFunctions"]["refill"]["parameters"] = json!(["U64", "U64"]);
+        new["exposedFunctions"]
+            .as_object_mut()
+            .unwrap()
+            .remove("admin");
+        new["exposedFunctions"]["withdraw"] = function("Public", false, vec![pool_ref()]);
+
+        assert_eq!(
+            changes(new, UpgradePolicy::Additive),
+            [
+                (ChangeKind::Removed, "pool::admin".to_string(), true),
+                (ChangeKind::Changed, "pool::refill".to_string(), true),
+                (ChangeKind::Added, "pool::withdraw".to_string(), false),
+            ]
+        );
+    }
+
+    #[test]
+    fn dep_only_rejects_additions() {
+        let mut new = deployed();
+        new["exposedFunctions"]["withdraw"] = function("Friend", false, vec![]);
+        new["structs"]["Receipt"] = json!({
+            "abilities": {"abilities": ["Drop"]},
+            "typeParameters": [],
+            "fields": [],
+        });
+
+        assert_eq!(
+            changes(new, UpgradePolicy::DepOnly),
+            [
+                (ChangeKind::Added, "pool::Receipt".to_string(), true),
+                (ChangeKind::Added, "pool::withdraw".to_string(), true),
+            ]
+        );
+    }
+
+    #[test]
+    fn unresolved_local_types_are_unknown_rather_than_breaking() {
+        let unresolved = json!({"Struct": {
+            "address": "", "module": "pool", "name": "Coin", "typeArguments": []
+        }});
+        let mut new = deployed();
+        new["exposedFunctions"]["deposit"]["parameters"] = json!([pool_ref(), unresolved]);
+        new["structs"]["Pool"]["fields"][0]["type"] = json!({"Struct": {
+            "address": "framework", "module": "object", "name": "UID", "typeArguments": []
+        }});
+
+        for policy in [UpgradePolicy::Compatible, UpgradePolicy::Additive] {
+            assert_eq!(
+                changes(new.clone(), policy),
+                [
+                    (ChangeKind::Unknown, "pool::Pool".to_string(), false),
+                    (ChangeKind::Unknown, "pool::deposit".to_string(), false),
+                ]
+            );
+        }
+    }
+}
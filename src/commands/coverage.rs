@@ -1,7 +1,8 @@
 use crate::error::Result;
+use crate::move_syntax::{Function, Module, Package};
 use crate::utils;
 use colored::Colorize;
-use regex::Regex;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::fs;
 use std::path::Path;
 
@@ -147,55 +148,91 @@ struct UncoveredLine {
 }
 
 fn analyze_coverage(source_dir: &str, test_dir: &str) -> Result<CoverageData> {
+    let sources = Package::load(Path::new(source_dir))?;
+    let tests = Package::load(Path::new(test_dir))?;
+
+    // Call graph keyed by (module, function). Method calls cannot be resolved
+    // without type information, so they link to every function of that name.
+    let all_functions: Vec<(&Module, &Function)> = sources.functions().chain(tests.functions()).collect();
+    let mut callees: HashMap<(&str, &str), Vec<(&str, &str)>> = HashMap::new();
+    for (module, function) in &all_functions {
+        let calls = function.body.iter().flat_map(|b| b.calls.iter());
+        let targets = callees
+            .entry((module.name.as_str(), function.name.as_str()))
+            .or_default();
+        for call in calls {
+            for (m, f) in &all_functions {
+                let same_name = f.name == call.function;
+                let matches = match call.module.last() {
+                    Some(qualifier) => same_name && m.name == *qualifier,
+                    None if call.is_method => same_name,
+                    None => same_name && m.name == module.name,
+                };
+                if matches {
+                    targets.push((m.name.as_str(), f.name.as_str()));
+                }
+            }
+        }
+    }
+
+    // Everything reachable from a #[test] function counts as covered.
+    let mut reached: HashSet<(&str, &str)> = HashSet::new();
+    let mut queue: VecDeque<(&str, &str)> = all_functions
+        .iter()
+        .filter(|(_, f)| f.is_test())
+        .map(|(m, f)| (m.name.as_str(), f.name.as_str()))
+        .collect();
+    while let Some(key) = queue.pop_front() {
+        if reached.insert(key) {
+            if let Some(next) = callees.get(&key) {
+                queue.extend(next.iter().copied());
+            }
+        }
+    }
+
     let mut modules = Vec::new();
+    let mut uncovered_lines = Vec::new();
     let mut total_lines_covered = 0;
     let mut total_lines = 0;
     let mut total_functions_covered = 0;
     let mut total_functions = 0;
-    let uncovered_lines = Vec::new();
 
-    let source_path = Path::new(source_dir);
-    if !source_path.exists() {
-        return Ok(CoverageData {
+    for module in &sources.modules {
+        if module.attributes.iter().any(|a| a == "test_only") {
+            continue;
+        }
+
+        let mut coverage = ModuleCoverage {
+            name: format!("{}::{}", module.address, module.name),
             lines_covered: 0,
             lines_total: 0,
             functions_covered: 0,
             functions_total: 0,
-            modules: vec![],
-            uncovered_lines: vec![],
-        });
-    }
+        };
 
-    // Analyze each source file
-    for entry in walkdir::WalkDir::new(source_path)
-        .into_iter()
-        .filter_map(|e| e.ok())
-        .filter(|e| e.path().extension().map_or(false, |ext| ext == "move"))
-    {
-        let content = fs::read_to_string(entry.path())?;
-        let file_name = entry.path().display().to_string();
-        
-        // Count lines and functions
-        let lines: Vec<&str> = content.lines().filter(|l| !l.trim().is_empty() && !l.trim().starts_with("/")).collect();
-        let function_count = content.matches("fun ").count();
-        
-        // Calculate coverage based on test file presence and test function calls
-        let test_coverage = calculate_test_coverage(&file_name, &content, test_dir)?;
-        let lines_covered = (lines.len() as f64 * test_coverage.line_ratio) as usize;
-        let functions_covered = (function_count as f64 * test_coverage.function_ratio) as usize;
-        
-        total_lines += lines.len();
-        total_lines_covered += lines_covered;
-        total_functions += function_count;
-        total_functions_covered += functions_covered;
-        
-        modules.push(ModuleCoverage {
-            name: file_name.clone(),
-            lines_covered,
-            lines_total: lines.len(),
-            functions_covered,
-            functions_total: function_count,
-        });
+        for function in &module.functions {
+            if function.body.is_none() || function.is_test() || function.is_test_only() {
+                continue;
+            }
+            coverage.functions_total += 1;
+            coverage.lines_total += function.line_count();
+
+            if reached.contains(&(module.name.as_str(), function.name.as_str())) {
+                coverage.functions_covered += 1;
+                coverage.lines_covered += function.line_count();
+            } else {
+                uncovered_lines.push(UncoveredLine {
+                    location: format!("{}:{}-{}", module.path.display(), function.line, function.end_line),
+                    description: format!("fun {} is not reached by any test", function.name),
+                });
+            }
+        }
+
+        total_lines += coverage.lines_total;
+        total_lines_covered += coverage.lines_covered;
+        total_functions += coverage.functions_total;
+        total_functions_covered += coverage.functions_covered;
+        modules.push(coverage);
     }
 
     Ok(CoverageData {
@@ -346,89 +383,3 @@ async fn generate_json_report(output: &str) -> Result<()> {
 
     Ok(())
 }
-
-
-struct TestCoverage {
-    line_ratio: f64,
-    function_ratio: f64,
-}
-
-fn calculate_test_coverage(source_file: &str, source_content: &str, test_dir: &str) -> Result<TestCoverage> {
-    let test_path = Path::new(test_dir);
-    
-    // Default coverage if no tests exist
-    if !test_path.exists() {
-        return Ok(TestCoverage {
-            line_ratio: 0.0,
-            function_ratio: 0.0,
-        });
-    }
-
-    // Extract module name from source file
-    let module_name = Path::new(source_file)
-        .file_stem()
-        .and_then(|s| s.to_str())
-        .unwrap_or("");
-
-    // Look for corresponding test file
-    let mut test_content = String::new();
-    let mut found_test = false;
-
-    for entry in walkdir::WalkDir::new(test_path)
-        .into_iter()
-        .filter_map(|e| e.ok())
-        .filter(|e| e.path().extension().map_or(false, |ext| ext == "move"))
-    {
-        if entry.file_name().to_string_lossy().contains(&module_name) {
-            test_content = fs::read_to_string(entry.path())?;
-            found_test = true;
-            break;
-        }
-    }
-
-    if !found_test {
-        return Ok(TestCoverage {
-            line_ratio: 0.0,
-            function_ratio: 0.0,
-        });
-    }
-
-    // Count test functions
-    let test_function_count = test_content.matches("#[test]").count();
-    
-    // Extract function names from source
-    let function_regex = Regex::new(r"(?m)^\s*(?:public\s+)?fun\s+(\w+)").unwrap();
-    let source_functions: Vec<String> = function_regex
-        .captures_iter(source_content)
-        .map(|cap| cap[1].to_string())
-        .collect();
-
-    // Count how many source functions are called in tests
-    let mut tested_functions = 0;
-    for func in &source_functions {
-        if test_content.contains(func) {
-            tested_functions += 1;
-        }
-    }
-
-    // Calculate ratios
-    let function_ratio = if source_functions.is_empty() {
-        0.0
-    } else {
-        tested_functions as f64 / source_functions.len() as f64
-    };
-
-    // Estimate line coverage based on function coverage and test count
-    let line_ratio = if test_function_count == 0 {
-        0.0
-    } else {
-        // More tests = better coverage, but cap at function_ratio
-        let test_factor = (test_function_count as f64 * 0.2).min(1.0);
-        (function_ratio * 0.7 + test_factor * 0.3).min(1.0)
-    };
-
-    Ok(TestCoverage {
-        line_ratio,
-        function_ratio,
-    })
-}
@@ -1,22 +1,37 @@
-use crate::config::{find_project_root, load_config, DeploymentLock};
+use crate::aborts;
+use crate::config::{find_project_root, load_config, resolve_network, GasBudget};
 use crate::error::{Result, SuiForgeError};
+use crate::lock::{DeployedObject, DeploymentKind, DeploymentRecord, GasUsed, LockFile};
+use crate::rpc::types::{
+    DryRunTransactionBlockResponse, GasCostSummary, ObjectChange, SuiTransactionBlockResponse,
+    TransactionEffects,
+};
+use crate::rpc::RpcClient;
+use crate::safety;
 use crate::sui::SuiCli;
 use crate::utils;
+use crate::verification::{self, ModuleStatus};
 use chrono::Utc;
 use colored::Colorize;
-use std::fs;
+use serde::de::DeserializeOwned;
+use std::path::Path;
+use std::process::Output;
 
-pub async fn execute(network: String, gas_budget: Option<u64>, skip_verify: bool) -> Result<()> {
+pub async fn execute(
+    network: Option<String>,
+    gas_budget: Option<GasBudget>,
+    skip_verify: bool,
+    dry_run: bool,
+) -> Result<()> {
     let root = find_project_root()?;
     let config = load_config()?;
+    let network = resolve_network(network.as_deref())?;
 
-    // Validate network
-    let valid_networks = ["devnet", "testnet", "mainnet"];
-    if !valid_networks.contains(&network.as_str()) {
-        return Err(SuiForgeError::InvalidNetwork(network));
-    }
-
-    utils::info(&format!("Deploying to {}...", network.yellow().bold()));
+    utils::info(&format!(
+        "Deploying to {} ({})...",
+        network.name.yellow().bold(),
+        network.rpc.dimmed()
+    ));
 
     // Get active address
     let spinner = utils::create_spinner("Getting active address...");
@@ -32,60 +47,471 @@ pub async fn execute(network: String, gas_budget: Option<u64>, skip_verify: bool
         ));
     }
 
+    let preflight = if dry_run {
+        None
+    } else {
+        safety::preflight(&config.deploy.safety, &root, &network.name, &address, None).await?
+    };
+
+    let margin = config.deploy.gas_safety_margin;
+    let budget = match (gas_budget.unwrap_or(config.deploy.gas_budget), dry_run) {
+        (GasBudget::Fixed(budget), false) => budget,
+        (budget, _) => {
+            let fixed = match budget {
+                GasBudget::Fixed(budget) => Some(budget),
+                GasBudget::Auto => None,
+            };
+            let simulation = simulate("Simulating publish...", || {
+                SuiCli::publish(&network, fixed, true)
+            })?;
+            let estimate = auto_gas_budget(&simulation.effects.gas_used, margin);
+
+            if dry_run {
+                print_dry_run(&simulation, fixed.unwrap_or(estimate));
+                utils::info("Dry run only; nothing was signed or submitted");
+                return Ok(());
+            }
+
+            utils::info(&format!(
+                "Auto gas budget: {} MIST (dry-run cost + {}%)",
+                estimate, margin
+            ));
+            estimate
+        }
+    };
+
     // Deploy
     let spinner = utils::create_spinner("Publishing package...");
-    let budget = gas_budget.unwrap_or(config.deploy.gas_budget);
-    let output = SuiCli::publish(&network, budget)?;
+    let output = SuiCli::publish(&network, Some(budget), false)?;
 
-    if output.status.success() {
-        spinner.finish_with_message("Package published");
+    let response = match executed_transaction(&output) {
+        Ok(response) => response,
+        Err(e) => {
+            spinner.finish_with_message("Deployment failed");
+            return Err(e);
+        }
+    };
+    spinner.finish_with_message("Package published");
 
-        let stdout = String::from_utf8_lossy(&output.stdout);
+    let publish = parse_publish(&response, &address)?;
+    let record = DeploymentRecord {
+        kind: DeploymentKind::Publish,
+        version: publish.version,
+        package_id: publish.package_id.clone(),
+        digest: response.digest.clone(),
+        git_commit: utils::git_commit(&root),
+        timestamp: Utc::now().to_rfc3339(),
+        deployer: publish.deployer.clone(),
+        gas_used: publish.gas_used.clone(),
+        preflight,
+    };
 
-        // Parse package ID from output (simplified)
-        let package_id = extract_package_id(&stdout).unwrap_or_else(|| "0x...".to_string());
+    let mut lock = LockFile::load(&root)?;
+    lock.record_publish(
+        &network.name,
+        record,
+        publish.upgrade_cap_id.clone(),
+        publish.objects.clone(),
+    );
+    lock.save(&root)?;
 
-        // Save deployment lock
-        let lock = DeploymentLock {
-            package_id: package_id.clone(),
-            object_ids: vec![],
-            network: network.clone(),
-            timestamp: Utc::now().to_rfc3339(),
-            digest: "".to_string(),
-        };
+    utils::success("Deployment successful!");
+    print_publish(&network.name, &response.digest, &publish);
 
-        let lock_path = root.join("suiforge.lock.json");
-        let lock_json = serde_json::to_string_pretty(&lock)?;
-        fs::write(lock_path, lock_json)?;
+    if !skip_verify {
+        let client = RpcClient::for_network(&network);
+        verify_deployment(&client, &root, &publish).await?;
+    }
 
-        utils::success("Deployment successful!");
-        println!("\n{}", "Deployment Details:".bold());
-        println!("  Network: {}", network.cyan());
-        println!("  Package ID: {}", package_id.green());
-        println!("  Deployer: {}", address.yellow());
+    Ok(())
+}
 
-        if !skip_verify {
-            utils::info("Verifying deployment...");
-            // Add verification logic here
+/// Check the published modules against the local build and that the created
+/// objects, capabilities in particular, ended up with the right owners.
+async fn verify_deployment(client: &RpcClient, root: &Path, publish: &PublishResult) -> Result<()> {
+    let spinner = utils::create_spinner("Verifying deployment...");
+    let result = async {
+        let local = verification::local_modules(root)?;
+        let package = verification::fetch_package(client, &publish.package_id).await?;
+        let modules =
+            verification::compare_modules(&local, &package.modules, &package.self_addresses());
+        let objects = verification::check_objects(client, &publish.objects).await?;
+        Ok::<_, SuiForgeError>((modules, objects))
+    }
+    .await;
+    spinner.finish_and_clear();
+    let (modules, objects) = result?;
+
+    println!("\n{}", "Verification:".bold());
+    for module in &modules {
+        if module.status == ModuleStatus::Match {
+            println!("  {} module {}", "✓".green(), module.name);
+        } else {
+            println!(
+                "  {} module {}: {}",
+                "✗".red(),
+                module.name,
+                module.status.as_str().red()
+            );
         }
-    } else {
-        spinner.finish_with_message("Deployment failed");
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        utils::error("Deployment failed:");
-        println!("\n{}", stderr);
-        return Err(SuiForgeError::DeploymentFailed(stderr.to_string()));
+    }
+    for object in &objects {
+        let label = match &object.expected_owner {
+            Some(owner) => format!("{} → {}", object.object_type, owner),
+            None => object.object_type.clone(),
+        };
+        match &object.problem {
+            None => println!("  {} {}", "✓".green(), label),
+            Some(problem) => println!("  {} {}: {}", "✗".red(), label, problem.red()),
+        }
+    }
+    println!();
+
+    let mismatched = modules
+        .iter()
+        .filter(|m| m.status != ModuleStatus::Match)
+        .count();
+    let misplaced = objects.iter().filter(|o| o.problem.is_some()).count();
+    if mismatched > 0 || misplaced > 0 {
+        return Err(SuiForgeError::VerificationFailed(format!(
+            "{} module(s) and {} object(s) do not match; package {} was published and recorded",
+            mismatched, misplaced, publish.package_id
+        )));
     }
 
+    utils::success("Deployment verified");
     Ok(())
 }
 
-fn extract_package_id(output: &str) -> Option<String> {
-    // Simplified extraction - actual implementation would parse JSON output
-    for line in output.lines() {
-        if line.contains("Package ID") || line.contains("packageId") {
-            // Extract the actual ID
-            return Some("0x...".to_string());
+/// Parse the `--json` output of a publish or upgrade, failing if the CLI
+/// errored or the transaction aborted on chain.
+pub fn executed_transaction(output: &Output) -> Result<SuiTransactionBlockResponse> {
+    let response: SuiTransactionBlockResponse = parse_cli_output(output)?;
+    if let Some(effects) = &response.effects {
+        ensure_success(effects, &format!("Transaction {}", response.digest))?;
+    }
+    Ok(response)
+}
+
+/// Run a `--dry-run` publish or upgrade and parse the simulated effects.
+pub fn simulate(
+    message: &str,
+    run: impl FnOnce() -> Result<Output>,
+) -> Result<DryRunTransactionBlockResponse> {
+    let spinner = utils::create_spinner(message);
+    let result = run().and_then(|output| {
+        let response: DryRunTransactionBlockResponse = parse_cli_output(&output)?;
+        ensure_success(&response.effects, "Dry run")?;
+        Ok(response)
+    });
+    match &result {
+        Ok(_) => spinner.finish_with_message("Dry run complete"),
+        Err(_) => spinner.finish_with_message("Dry run failed"),
+    }
+    result
+}
+
+/// Budget covering the simulated computation and storage costs plus
+/// `margin_percent`. The storage rebate is not subtracted, because the
+/// budget must cover the charge before the rebate is credited.
+pub fn auto_gas_budget(cost: &GasCostSummary, margin_percent: u32) -> u64 {
+    let gross = cost.computation() + cost.storage();
+    gross + gross * margin_percent as u64 / 100
+}
+
+fn parse_cli_output<T: DeserializeOwned>(output: &Output) -> Result<T> {
+    match SuiCli::parse_json_output(&output.stdout) {
+        Ok(response) => Ok(response),
+        Err(_) if !output.status.success() => {
+            let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+            utils::error("Transaction failed:");
+            println!("\n{}", stderr);
+            aborts::explain(&stderr, true);
+            Err(SuiForgeError::DeploymentFailed(stderr))
+        }
+        Err(e) => Err(SuiForgeError::DeploymentFailed(format!(
+            "could not parse sui client output: {}",
+            e
+        ))),
+    }
+}
+
+fn ensure_success(effects: &TransactionEffects, what: &str) -> Result<()> {
+    if effects.status.is_success() {
+        return Ok(());
+    }
+    let reason = effects
+        .status
+        .error
+        .clone()
+        .unwrap_or_else(|| effects.status.status.clone());
+    utils::error(&format!("{} failed: {}", what, reason));
+    aborts::explain(&reason, true);
+    Err(SuiForgeError::DeploymentFailed(reason))
+}
+
+fn print_dry_run(simulation: &DryRunTransactionBlockResponse, budget: u64) {
+    let cost = &simulation.effects.gas_used;
+
+    println!("\n{}", "Estimated Gas:".bold());
+    println!("  Computation: {} MIST", cost.computation().to_string().blue());
+    println!("  Storage: {} MIST", cost.storage().to_string().green());
+    println!("  Storage rebate: {} MIST", cost.rebate().to_string().green());
+    println!("  Net cost: {} MIST", cost.net().to_string().yellow().bold());
+    println!("  Gas budget: {} MIST", budget);
+
+    println!("\n{}", "Would Create:".bold());
+    for change in &simulation.object_changes {
+        match change {
+            ObjectChange::Published { modules, .. } => {
+                println!("  {} ({})", "package".green(), modules.join(", "));
+            }
+            ObjectChange::Created {
+                object_type, owner, ..
+            } => {
+                println!("  {} ({})", object_type.green(), owner.to_string().dimmed());
+            }
+            _ => {}
         }
     }
-    None
+    println!();
+}
+
+/// What a publish or upgrade transaction produced, read from its effects and
+/// object changes.
+pub struct PublishResult {
+    pub package_id: String,
+    pub version: u64,
+    pub deployer: String,
+    pub upgrade_cap_id: Option<String>,
+    pub objects: Vec<DeployedObject>,
+    pub gas_used: GasUsed,
+}
+
+pub fn parse_publish(
+    response: &SuiTransactionBlockResponse,
+    active_address: &str,
+) -> Result<PublishResult> {
+    let changes = response.object_changes.as_deref().unwrap_or_default();
+
+    let (package_id, version) = changes
+        .iter()
+        .find_map(|change| match change {
+            ObjectChange::Published {
+                package_id,
+                version,
+                ..
+            } => Some((package_id.clone(), version)),
+            _ => None,
+        })
+        .ok_or_else(|| {
+            SuiForgeError::DeploymentFailed(format!(
+                "transaction {} did not publish a package",
+                response.digest
+            ))
+        })?;
+    let version = version.parse().map_err(|_| {
+        SuiForgeError::DeploymentFailed(format!(
+            "transaction {} published package {} with unreadable version {:?}",
+            response.digest, package_id, version
+        ))
+    })?;
+
+    let mut deployer = None;
+    let mut objects = Vec::new();
+    for change in changes {
+        if let ObjectChange::Created {
+            sender,
+            owner,
+            object_type,
+            object_id,
+            ..
+        } = change
+        {
+            deployer.get_or_insert_with(|| sender.clone());
+            objects.push(DeployedObject {
+                object_id: object_id.clone(),
+                object_type: object_type.clone(),
+                owner: owner.clone(),
+            });
+        }
+    }
+
+    let upgrade_cap_id = objects
+        .iter()
+        .find(|o| o.object_type.ends_with("::package::UpgradeCap"))
+        .map(|o| o.object_id.clone());
+
+    let gas_used = response
+        .effects
+        .as_ref()
+        .map(|effects| gas_used(&effects.gas_used))
+        .unwrap_or_default();
+
+    Ok(PublishResult {
+        package_id,
+        version,
+        deployer: deployer.unwrap_or_else(|| active_address.to_string()),
+        upgrade_cap_id,
+        objects,
+        gas_used,
+    })
+}
+
+fn print_publish(network: &str, digest: &str, publish: &PublishResult) {
+    println!("\n{}", "Deployment Details:".bold());
+    println!("  Network: {}", network.cyan());
+    println!("  Package ID: {}", publish.package_id.green());
+    println!("  Transaction: {}", digest);
+    println!("  Deployer: {}", publish.deployer.yellow());
+    if let Some(cap) = &publish.upgrade_cap_id {
+        println!("  UpgradeCap: {}", cap);
+    }
+    println!("  Gas used: {} MIST", publish.gas_used.total);
+
+    let created: Vec<_> = publish
+        .objects
+        .iter()
+        .filter(|o| Some(&o.object_id) != publish.upgrade_cap_id.as_ref())
+        .collect();
+    if !created.is_empty() {
+        println!("\n{}", "Created Objects:".bold());
+        for object in created {
+            println!(
+                "  {} {} ({})",
+                object.object_id.green(),
+                object.object_type,
+                object.owner.to_string().dimmed()
+            );
+        }
+    }
+}
+
+fn gas_used(summary: &GasCostSummary) -> GasUsed {
+    GasUsed {
+        computation_cost: summary.computation(),
+        storage_cost: summary.storage(),
+        storage_rebate: summary.rebate(),
+        total: summary.net(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+    use std::process::ExitStatus;
+
+    /// `sui client publish --json` for a one-module package.
+    const PUBLISH: &str = include_str!("../../tests/fixtures/sui_client_publish.json");
+    const DEPLOYER: &str = "0x7d20dcdb2bca4f508ea9613994683eb4e76e9c4ed371169677c1be02aaf0b58e";
+
+    fn cli_output(stdout: String) -> Output {
+        Output {
+            status: ExitStatus::default(),
+            stdout: stdout.into_bytes(),
+            stderr: Vec::new(),
+        }
+    }
+
+    fn publish(edit: impl FnOnce(&mut Value)) -> Output {
+        let mut response: Value = serde_json::from_str(PUBLISH).unwrap();
+        edit(&mut response);
+        cli_output(response.to_string())
+    }
+
+    #[test]
+    fn parses_a_captured_publish() {
+        // The CLI prints build progress before the JSON response
+        let stdout = format!("UPDATING GIT DEPENDENCY\nBUILDING shop\n{}", PUBLISH);
+        let response = executed_transaction(&cli_output(stdout)).unwrap();
+        assert_eq!(
+            response.digest,
+            "8RvbKw6fHjVnMWpHKhQF1iRDn5Eo9tDj3pZgTmq2Gb4Y"
+        );
+
+        let publish = parse_publish(&response, "0xa11ce").unwrap();
+        assert_eq!(
+            publish.package_id,
+            "0x5a1e4b1f6c0d2e8a9f3b7c5d1e0a2b4c6d8e0f1a3b5c7d9e1f2a4b6c8d0e2f4a"
+        );
+        assert_eq!(publish.version, 1);
+        assert_eq!(publish.deployer, DEPLOYER);
+        assert_eq!(
+            publish.upgrade_cap_id.as_deref(),
+            Some("0xcafe0b7e8d2f4a1c3e5b7d9f0a2c4e6b8d0f1a3c5e7b9d1f3a5c7e9b0d2f4a6c")
+        );
+        let types: Vec<_> = publish
+            .objects
+            .iter()
+            .map(|o| o.object_type.as_str())
+            .collect();
+        assert_eq!(
+            types,
+            [
+                "0x5a1e4b1f6c0d2e8a9f3b7c5d1e0a2b4c6d8e0f1a3b5c7d9e1f2a4b6c8d0e2f4a::shop::AdminCap",
+                "0x2::package::UpgradeCap",
+            ]
+        );
+        assert_eq!(publish.gas_used.computation_cost, 750_000);
+        assert_eq!(publish.gas_used.storage_cost, 12_395_600);
+        assert_eq!(publish.gas_used.storage_rebate, 978_120);
+        assert_eq!(publish.gas_used.total, 12_167_480);
+    }
+
+    #[test]
+    fn upgrade_cap_is_optional() {
+        let output = publish(|response| {
+            response["objectChanges"]
+                .as_array_mut()
+                .unwrap()
+                .retain(|change| change["objectType"] != "0x2::package::UpgradeCap");
+        });
+        let publish = parse_publish(&executed_transaction(&output).unwrap(), DEPLOYER).unwrap();
+        assert_eq!(publish.upgrade_cap_id, None);
+        assert_eq!(publish.objects.len(), 1);
+    }
+
+    #[test]
+    fn a_transaction_without_a_published_change_fails() {
+        let output = publish(|response| {
+            response["objectChanges"]
+                .as_array_mut()
+                .unwrap()
+                .retain(|change| change["type"] != "published");
+        });
+        let response = executed_transaction(&output).unwrap();
+        assert!(matches!(
+            parse_publish(&response, DEPLOYER),
+            Err(SuiForgeError::DeploymentFailed(_))
+        ));
+    }
+
+    #[test]
+    fn an_unreadable_package_version_fails() {
+        let output = publish(|response| response["objectChanges"][3]["version"] = "one".into());
+        let response = executed_transaction(&output).unwrap();
+        assert!(matches!(
+            parse_publish(&response, DEPLOYER),
+            Err(SuiForgeError::DeploymentFailed(_))
+        ));
+    }
+
+    #[test]
+    fn aborted_transactions_and_garbage_output_fail() {
+        let output = publish(|response| {
+            response["effects"]["status"] = serde_json::json!({
+                "status": "failure",
+                "error": "InsufficientGas",
+            });
+        });
+        assert!(matches!(
+            executed_transaction(&output),
+            Err(SuiForgeError::DeploymentFailed(_))
+        ));
+        assert!(matches!(
+            executed_transaction(&cli_output("Error executing transaction".to_string())),
+            Err(SuiForgeError::DeploymentFailed(_))
+        ));
+    }
 }
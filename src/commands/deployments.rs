@@ -0,0 +1,122 @@
+use crate::config::{find_project_root, resolve_network};
+use crate::error::{Result, SuiForgeError};
+use crate::lock::{LockFile, NetworkDeployment, LOCK_FILE};
+use crate::utils;
+use colored::Colorize;
+
+pub async fn execute(action: String, network: Option<String>, format: String) -> Result<()> {
+    let root = find_project_root()?;
+    let lock = LockFile::load(&root)?;
+
+    match action.as_str() {
+        "list" => list_deployments(&lock, &format),
+        "show" => show_deployment(&lock, network, &format),
+        _ => {
+            utils::error(&format!("Unknown action: {}", action));
+            println!("Available actions: list, show");
+            Ok(())
+        }
+    }
+}
+
+fn list_deployments(lock: &LockFile, format: &str) -> Result<()> {
+    if format == "json" {
+        println!("{}", serde_json::to_string_pretty(lock)?);
+        return Ok(());
+    }
+
+    if lock.networks.is_empty() {
+        utils::info(&format!("No deployments recorded in {}", LOCK_FILE));
+        return Ok(());
+    }
+
+    utils::info("Deployments:");
+    println!();
+    println!(
+        "{:<12} {:<68} {:>8}  {:<25}",
+        "Network", "Package ID", "Version", "Last Deployed"
+    );
+    println!("{}", "─".repeat(110));
+
+    for (name, deployment) in &lock.networks {
+        let last = deployment.history.last();
+        println!(
+            "{} {} {:>8}  {}",
+            format!("{:<12}", name).cyan(),
+            format!("{:<68}", deployment.package_id).green(),
+            last.map(|r| format!("v{}", r.version)).unwrap_or_default(),
+            last.map(|r| r.timestamp.as_str()).unwrap_or("-").dimmed()
+        );
+    }
+
+    println!();
+    utils::info("💡 Tip: Use 'suiforge deployments show --network <name>' for history");
+
+    Ok(())
+}
+
+fn show_deployment(lock: &LockFile, network: Option<String>, format: &str) -> Result<()> {
+    let name = match network {
+        Some(name) => name,
+        None if lock.networks.len() == 1 => lock.networks.keys().next().cloned().unwrap(),
+        None => resolve_network(None)?.name,
+    };
+
+    let deployment = lock.network(&name).ok_or_else(|| {
+        SuiForgeError::Custom(format!("No deployment recorded for network {}", name))
+    })?;
+
+    if format == "json" {
+        println!("{}", serde_json::to_string_pretty(deployment)?);
+        return Ok(());
+    }
+
+    print_deployment(&name, deployment);
+    Ok(())
+}
+
+fn print_deployment(network: &str, deployment: &NetworkDeployment) {
+    println!();
+    println!("{}", "Deployment Details:".bold());
+    println!("  Network: {}", network.cyan());
+    println!("  Package ID: {}", deployment.package_id.green());
+    if deployment.original_package_id != deployment.package_id {
+        println!("  Original Package ID: {}", deployment.original_package_id);
+    }
+    if let Some(cap) = &deployment.upgrade_cap_id {
+        println!("  UpgradeCap: {}", cap);
+    }
+    println!("  Deployer: {}", deployment.deployer.yellow());
+
+    if !deployment.objects.is_empty() {
+        println!();
+        println!("{}", "Objects:".bold());
+        for object in &deployment.objects {
+            println!(
+                "  {} {} ({})",
+                object.object_id.green(),
+                object.object_type,
+                object.owner.to_string().dimmed()
+            );
+        }
+    }
+
+    println!();
+    println!("{}", "History:".bold());
+    for record in deployment.history.iter().rev() {
+        println!(
+            "  {} {} {}",
+            format!("v{:<4}", record.version).yellow(),
+            format!("{:<8}", record.kind.as_str()).cyan(),
+            record.timestamp.dimmed()
+        );
+        println!("        Package: {}", record.package_id);
+        println!("        Digest: {}", record.digest);
+        if let Some(commit) = &record.git_commit {
+            println!("        Commit: {}", commit);
+        }
+        println!("        Deployer: {}", record.deployer);
+        println!("        Gas used: {} MIST", record.gas_used.total);
+    }
+    println!();
+}
@@ -0,0 +1,490 @@
+use crate::abi::same_address;
+use crate::config::{find_project_root, resolve_network, ResolvedNetwork};
+use crate::error::{Result, SuiForgeError};
+use crate::lock::{LockFile, NetworkDeployment};
+use crate::rpc::types::{EventId, SuiEvent};
+use crate::rpc::RpcClient;
+use crate::utils;
+use colored::Colorize;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Events requested per `suix_queryEvents` page.
+const PAGE_SIZE: usize = 50;
+
+/// Pages scanned for recent events. Conditions re-checked locally can reject
+/// most of every page, so the scan stops here instead of walking the whole
+/// history.
+const MAX_PAGES: usize = 20;
+
+/// Events shown when `--limit` is not given.
+const DEFAULT_LIMIT: usize = 20;
+
+/// What to match events against. The node accepts a single filter per query,
+/// so the most selective one is sent and every condition is re-checked
+/// locally.
+struct EventQuery {
+    package: Option<String>,
+    module: Option<String>,
+    event_type: Option<String>,
+    sender: Option<String>,
+    tx: Option<String>,
+}
+
+impl EventQuery {
+    /// The narrowest filter the query allows, ranked by how few events it
+    /// typically leaves: one transaction, one event type, one sender's
+    /// transactions, one module, then a whole package.
+    fn rpc_filter(&self) -> Value {
+        if let Some(tx) = &self.tx {
+            return json!({ "Transaction": tx });
+        }
+        if let Some(event_type) = &self.event_type {
+            return json!({ "MoveEventType": event_type });
+        }
+        if let Some(sender) = &self.sender {
+            return json!({ "Sender": sender });
+        }
+        match (&self.package, &self.module) {
+            (Some(package), Some(module)) => {
+                json!({ "MoveModule": { "package": package, "module": module } })
+            }
+            (Some(package), None) => json!({ "Package": package }),
+            _ => json!({ "All": [] }),
+        }
+    }
+
+    fn is_unfiltered(&self) -> bool {
+        self.package.is_none()
+            && self.module.is_none()
+            && self.event_type.is_none()
+            && self.sender.is_none()
+            && self.tx.is_none()
+    }
+
+    fn matches(&self, event: &SuiEvent) -> bool {
+        self.tx.as_ref().is_none_or(|tx| *tx == event.id.tx_digest)
+            && self.event_type.as_ref().is_none_or(|ty| {
+                // A type without type arguments matches every instantiation.
+                event.type_ == *ty || event.type_.starts_with(&format!("{}<", ty))
+            })
+            && self
+                .package
+                .as_ref()
+                .is_none_or(|p| same_address(p, &event.package_id))
+            && self
+                .module
+                .as_ref()
+                .is_none_or(|m| *m == event.transaction_module)
+            && self
+                .sender
+                .as_ref()
+                .is_none_or(|s| same_address(s, &event.sender))
+    }
+
+    /// Identifies the query in the cursor file.
+    fn key(&self, network: &ResolvedNetwork) -> String {
+        let parts = [
+            ("package", &self.package),
+            ("module", &self.module),
+            ("type", &self.event_type),
+            ("sender", &self.sender),
+            ("tx", &self.tx),
+        ];
+        let filters: Vec<String> = parts
+            .iter()
+            .filter_map(|(name, value)| value.as_ref().map(|v| format!("{}={}", name, v)))
+            .collect();
+        format!("{} {}", network.rpc, filters.join(" "))
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn execute(
+    network: Option<String>,
+    package: Option<String>,
+    module: Option<String>,
+    event_type: Option<String>,
+    sender: Option<String>,
+    tx: Option<String>,
+    follow: bool,
+    interval: u64,
+    limit: Option<usize>,
+    format: String,
+) -> Result<()> {
+    let network = resolve_network(network.as_deref())?;
+    let client = RpcClient::for_network(&network);
+
+    // Partial names refer to the project's package: `--module nft` filters
+    // on the deployed package, and `--type nft::MintEvent` or `--type
+    // MintEvent --module nft` on its original ID, which event types keep
+    // across upgrades.
+    let deployment = || project_deployment(&network.name);
+    let package = match (package, &module) {
+        (Some(package), _) => Some(package),
+        (None, Some(_)) => Some(deployment()?.package_id),
+        (None, None) => None,
+    };
+    let event_type = match event_type {
+        Some(ty) => Some(match ty.split("::").count() {
+            3 => ty,
+            2 => format!("{}::{}", deployment()?.original_package_id, ty),
+            _ => match &module {
+                Some(module) => {
+                    format!("{}::{}::{}", deployment()?.original_package_id, module, ty)
+                }
+                None => {
+                    return Err(SuiForgeError::Custom(format!(
+                        "Event type '{}' needs a module: use <module>::{} or --module",
+                        ty, ty
+                    )))
+                }
+            },
+        }),
+        None => None,
+    };
+
+    let query = EventQuery {
+        package,
+        module,
+        event_type,
+        sender,
+        tx,
+    };
+
+    // Without a filter every event on the network matches, so only list
+    // them when asked for a specific number.
+    let limit = match limit {
+        Some(limit) => limit,
+        None if query.is_unfiltered() => {
+            return Err(SuiForgeError::Custom(
+                "No filter given; pass --package, --module, --type, --sender or --tx, or --limit to list the latest events on the whole network".to_string(),
+            ))
+        }
+        None => DEFAULT_LIMIT,
+    };
+
+    if follow {
+        return tail(&client, &network, &query, interval, limit, &format).await;
+    }
+
+    let spinner = utils::create_spinner("Querying events...");
+    let events = recent_events(&client, &query, limit).await;
+    spinner.finish_and_clear();
+    let (events, capped) = events?;
+    if capped {
+        warn_capped(events.len(), &format);
+    }
+
+    match format.as_str() {
+        "json" => println!("{}", serde_json::to_string_pretty(&events)?),
+        "ndjson" => {
+            for event in &events {
+                println!("{}", serde_json::to_string(event)?);
+            }
+        }
+        _ => {
+            if events.is_empty() {
+                utils::info("No events found");
+            }
+            for event in &events {
+                print_event(event);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn project_deployment(network: &str) -> Result<NetworkDeployment> {
+    let root = find_project_root().map_err(|_| {
+        SuiForgeError::Custom(
+            "Outside a project, pass --package and a fully qualified --type".to_string(),
+        )
+    })?;
+    LockFile::load(&root)?
+        .network(network)
+        .cloned()
+        .ok_or_else(|| {
+            SuiForgeError::Custom(format!(
+                "No deployment recorded for network {}; pass --package",
+                network
+            ))
+        })
+}
+
+/// The newest `limit` matching events, oldest first, and whether the scan
+/// stopped at [`MAX_PAGES`] before finding that many.
+async fn recent_events(
+    client: &RpcClient,
+    query: &EventQuery,
+    limit: usize,
+) -> Result<(Vec<SuiEvent>, bool)> {
+    let filter = query.rpc_filter();
+    let mut events = Vec::new();
+    let mut cursor = None;
+    let mut pages = 0;
+    let mut capped = false;
+    while events.len() < limit {
+        if pages == MAX_PAGES {
+            capped = true;
+            break;
+        }
+        pages += 1;
+        let page = client
+            .query_events(filter.clone(), cursor, Some(PAGE_SIZE), true)
+            .await?;
+        events.extend(page.data.into_iter().filter(|e| query.matches(e)));
+        match page.next_cursor {
+            Some(next) if page.has_next_page => cursor = Some(next),
+            _ => break,
+        }
+    }
+    events.truncate(limit);
+    events.reverse();
+    Ok((events, capped))
+}
+
+/// Warn that older events were not searched. JSON output keeps stdout
+/// parseable, so the warning goes to stderr there.
+fn warn_capped(found: usize, format: &str) {
+    let message = format!(
+        "Found {} matching events in the newest {}; narrow the filters (e.g. --type or --sender) to search further back",
+        found,
+        MAX_PAGES * PAGE_SIZE
+    );
+    match format {
+        "json" | "ndjson" => eprintln!("{} {}", "⚠".yellow().bold(), message),
+        _ => utils::warning(&message),
+    }
+}
+
+/// Print the newest events, then poll for new ones. The last event seen is
+/// saved after every batch, and a later `--follow` with the same filters
+/// resumes after it instead.
+async fn tail(
+    client: &RpcClient,
+    network: &ResolvedNetwork,
+    query: &EventQuery,
+    interval: u64,
+    limit: usize,
+    format: &str,
+) -> Result<()> {
+    let key = query.key(network);
+    let mut cursors = load_cursors()?;
+    let print = |event: &SuiEvent| -> Result<()> {
+        if format == "ndjson" {
+            println!("{}", serde_json::to_string(event)?);
+        } else {
+            print_event(event);
+        }
+        Ok(())
+    };
+
+    let mut cursor = match cursors.get(&key) {
+        Some(saved) => {
+            if format != "ndjson" {
+                utils::info(&format!(
+                    "Resuming after event {} of transaction {}",
+                    saved.event_seq,
+                    saved.tx_digest.cyan()
+                ));
+            }
+            Some(saved.clone())
+        }
+        None => {
+            let (events, capped) = recent_events(client, query, limit).await?;
+            if capped {
+                warn_capped(events.len(), format);
+            }
+            for event in &events {
+                print(event)?;
+            }
+            // Follow from the newest event on chain, matching or not.
+            let newest = client
+                .query_events(query.rpc_filter(), None, Some(1), true)
+                .await?;
+            newest.data.into_iter().next().map(|e| e.id)
+        }
+    };
+
+    if format != "ndjson" {
+        utils::info(&format!(
+            "Following {} events every {}s (Ctrl+C to stop)...",
+            network.name.yellow(),
+            interval
+        ));
+    }
+
+    loop {
+        let page = client
+            .query_events(query.rpc_filter(), cursor.clone(), Some(PAGE_SIZE), false)
+            .await?;
+        for event in page.data.iter().filter(|e| query.matches(e)) {
+            print(event)?;
+        }
+
+        if let Some(last) = page.data.last() {
+            cursor = Some(last.id.clone());
+            cursors.insert(key.clone(), last.id.clone());
+            save_cursors(&cursors)?;
+        }
+        if !page.has_next_page {
+            tokio::time::sleep(Duration::from_secs(interval)).await;
+        }
+    }
+}
+
+fn print_event(event: &SuiEvent) {
+    let time = event
+        .timestamp_ms
+        .as_deref()
+        .and_then(|ms| ms.parse().ok())
+        .and_then(chrono::DateTime::from_timestamp_millis)
+        .map(|t| t.format("%Y-%m-%d %H:%M:%S").to_string())
+        .unwrap_or_default();
+
+    println!();
+    println!("{} {}", time.dimmed(), event.type_.green().bold());
+    println!(
+        "  {} {}  {} {}",
+        "tx".dimmed(),
+        event.id.tx_digest.cyan(),
+        "sender".dimmed(),
+        event.sender.blue()
+    );
+    if let Some(fields) = event.parsed_json.as_object() {
+        for (key, value) in fields {
+            let value = match value {
+                Value::String(s) => s.clone(),
+                other => other.to_string(),
+            };
+            println!("  {}: {}", key.cyan(), value);
+        }
+    }
+}
+
+/// Saved `--follow` positions, keyed by RPC URL and filters.
+fn cursors_path() -> Result<PathBuf> {
+    let home = home::home_dir()
+        .ok_or_else(|| SuiForgeError::Custom("Could not find home directory".to_string()))?;
+    Ok(home.join(".suiforge").join("event-cursors.json"))
+}
+
+fn load_cursors() -> Result<BTreeMap<String, EventId>> {
+    let path = cursors_path()?;
+    if !path.exists() {
+        return Ok(BTreeMap::new());
+    }
+    Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+}
+
+fn save_cursors(cursors: &BTreeMap<String, EventId>) -> Result<()> {
+    let path = cursors_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, serde_json::to_string_pretty(cursors)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rpc::tests::{MockNode, Reply};
+
+    fn query() -> EventQuery {
+        EventQuery {
+            package: Some("0x5a1e".to_string()),
+            module: Some("nft".to_string()),
+            event_type: Some("0x5a1e::nft::MintEvent".to_string()),
+            sender: Some("0xa11ce".to_string()),
+            tx: Some("9Xk2".to_string()),
+        }
+    }
+
+    #[test]
+    fn rpc_filter_prefers_the_most_selective_condition() {
+        let mut query = query();
+        assert_eq!(query.rpc_filter(), json!({ "Transaction": "9Xk2" }));
+        query.tx = None;
+        assert_eq!(
+            query.rpc_filter(),
+            json!({ "MoveEventType": "0x5a1e::nft::MintEvent" })
+        );
+        query.event_type = None;
+        assert_eq!(query.rpc_filter(), json!({ "Sender": "0xa11ce" }));
+        query.sender = None;
+        assert_eq!(
+            query.rpc_filter(),
+            json!({ "MoveModule": { "package": "0x5a1e", "module": "nft" } })
+        );
+        query.module = None;
+        assert_eq!(query.rpc_filter(), json!({ "Package": "0x5a1e" }));
+        assert!(!query.is_unfiltered());
+        query.package = None;
+        assert_eq!(query.rpc_filter(), json!({ "All": [] }));
+        assert!(query.is_unfiltered());
+    }
+
+    fn page(modules: &[&str], has_next_page: bool) -> Reply {
+        let data: Vec<Value> = modules
+            .iter()
+            .enumerate()
+            .map(|(seq, module)| {
+                json!({
+                    "id": { "txDigest": "9Xk2", "eventSeq": seq.to_string() },
+                    "packageId": "0x5a1e",
+                    "transactionModule": module,
+                    "sender": "0xa11ce",
+                    "type": format!("0x5a1e::{}::MintEvent", module),
+                    "parsedJson": {},
+                    "timestampMs": null,
+                })
+            })
+            .collect();
+        Reply::Result(json!({
+            "data": data,
+            "nextCursor": { "txDigest": "9Xk2", "eventSeq": "0" },
+            "hasNextPage": has_next_page,
+        }))
+    }
+
+    fn nft_query() -> EventQuery {
+        EventQuery {
+            package: Some("0x5a1e".to_string()),
+            module: Some("nft".to_string()),
+            event_type: None,
+            sender: None,
+            tx: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn recent_events_stops_at_the_page_cap() {
+        let node = MockNode::start((0..MAX_PAGES).map(|_| page(&["market"], true)).collect()).await;
+        let (events, capped) = recent_events(&node.client(), &nft_query(), 5)
+            .await
+            .unwrap();
+        assert!(events.is_empty());
+        assert!(capped);
+        assert_eq!(node.requests().len(), MAX_PAGES);
+    }
+
+    #[tokio::test]
+    async fn recent_events_is_not_capped_when_history_runs_out() {
+        let node = MockNode::start(vec![
+            page(&["market", "nft"], true),
+            page(&["nft", "market"], false),
+        ])
+        .await;
+        let (events, capped) = recent_events(&node.client(), &nft_query(), 5)
+            .await
+            .unwrap();
+        assert_eq!(events.len(), 2);
+        assert!(!capped);
+        assert_eq!(node.requests().len(), 2);
+    }
+}
@@ -1,8 +1,8 @@
 use crate::error::Result;
+use crate::move_syntax::{CallSite, Function, Package};
 use crate::utils;
 use colored::Colorize;
-use regex::Regex;
-use std::fs;
+use std::collections::HashMap;
 use std::path::Path;
 
 #[derive(Debug, Clone)]
@@ -112,7 +112,7 @@ async fn analyze_gas() -> Result<()> {
 
     // Find hot spots (top 3 gas consumers)
     let mut sorted_profiles = profiles.clone();
-    sorted_profiles.sort_by(|a, b| b.gas_used.cmp(&a.gas_used));
+    sorted_profiles.sort_by_key(|p| std::cmp::Reverse(p.gas_used));
     
     if sorted_profiles.len() >= 3 {
         println!("{}", "🔥 Hot Spots:".bold());
@@ -187,86 +187,73 @@ struct OptimizationSuggestion {
 
 fn analyze_for_optimizations(source_dir: &str) -> Result<Vec<OptimizationSuggestion>> {
     let mut suggestions = Vec::new();
-    let path = Path::new(source_dir);
-
-    if !path.exists() {
-        return Ok(suggestions);
-    }
-
-    for entry in walkdir::WalkDir::new(path)
-        .into_iter()
-        .filter_map(|e| e.ok())
-        .filter(|e| e.path().extension().map_or(false, |ext| ext == "move"))
-    {
-        let content = fs::read_to_string(entry.path())?;
-        let file_name = entry.path().display().to_string();
-        let lines: Vec<&str> = content.lines().collect();
-
-        for (i, line) in lines.iter().enumerate() {
-            // Check for excessive object::new calls
-            if line.contains("object::new") {
-                let count_in_function = lines.iter()
-                    .skip(i.saturating_sub(10))
-                    .take(20)
-                    .filter(|l| l.contains("object::new"))
-                    .count();
-                
-                if count_in_function > 2 {
-                    suggestions.push(OptimizationSuggestion {
-                        title: "Reduce Storage Allocations".to_string(),
-                        location: format!("{}:{}", file_name, i + 1),
-                        current: "Multiple object creations in same function".to_string(),
-                        recommendation: "Consider using shared objects or batch operations".to_string(),
-                        savings: 200 * (count_in_function as u64 - 1),
-                    });
-                }
-            }
+    let package = Package::load(Path::new(source_dir))?;
+
+    for (module, function) in package.functions() {
+        let body = match &function.body {
+            Some(body) if !function.is_test() => body,
+            _ => continue,
+        };
+        let location = |line: usize| format!("{}:{}", module.path.display(), line);
+
+        // Check for excessive object::new calls
+        let object_news = body
+            .calls
+            .iter()
+            .filter(|c| c.targets("object", "new"))
+            .count();
+        if object_news > 2 {
+            suggestions.push(OptimizationSuggestion {
+                title: "Reduce Storage Allocations".to_string(),
+                location: location(function.line),
+                current: format!("{} object creations in `{}`", object_news, function.name),
+                recommendation: "Consider using shared objects or batch operations".to_string(),
+                savings: 200 * (object_news as u64 - 1),
+            });
+        }
 
-            // Check for vector operations in loops
-            if line.contains("while") || line.contains("loop") {
-                let has_vector_ops = lines.iter()
-                    .skip(i)
-                    .take(15)
-                    .any(|l| l.contains("vector::push_back") || l.contains("vector::borrow"));
-                
-                if has_vector_ops {
-                    suggestions.push(OptimizationSuggestion {
-                        title: "Optimize Vector Operations".to_string(),
-                        location: format!("{}:{}", file_name, i + 1),
-                        current: "Vector operations inside loop".to_string(),
-                        recommendation: "Use Table for O(1) lookups or batch vector operations".to_string(),
-                        savings: 150,
-                    });
-                }
+        // Check for vector operations in loops
+        for (start, end) in &body.loops {
+            let has_vector_ops = body.calls.iter().any(|c| {
+                c.line >= *start && c.line <= *end && is_vector_op(c)
+            });
+            if has_vector_ops {
+                suggestions.push(OptimizationSuggestion {
+                    title: "Optimize Vector Operations".to_string(),
+                    location: location(*start),
+                    current: "Vector operations inside loop".to_string(),
+                    recommendation: "Use Table for O(1) lookups or batch vector operations".to_string(),
+                    savings: 150,
+                });
             }
+        }
 
-            // Check for repeated calculations
-            if line.contains("=") && !line.contains("==") {
-                let calculation_pattern = Regex::new(r"(\w+\s*\*\s*\w+|\w+\s*/\s*\w+)").unwrap();
-                if calculation_pattern.is_match(line) {
-                    let same_calc_count = lines.iter()
-                        .skip(i + 1)
-                        .take(10)
-                        .filter(|l| calculation_pattern.is_match(l))
-                        .count();
-                    
-                    if same_calc_count > 1 {
-                        suggestions.push(OptimizationSuggestion {
-                            title: "Cache Computed Values".to_string(),
-                            location: format!("{}:{}", file_name, i + 1),
-                            current: "Repeated calculations".to_string(),
-                            recommendation: "Store intermediate results in variables".to_string(),
-                            savings: 100,
-                        });
-                    }
-                }
+        // Check for repeated calculations
+        let mut seen: HashMap<(String, Option<&str>, &[String]), usize> = HashMap::new();
+        for call in &body.calls {
+            let key = (call.qualified_name(), call.receiver.as_deref(), call.args.as_slice());
+            let count = seen.entry(key).or_insert(0);
+            *count += 1;
+            if *count == 2 {
+                suggestions.push(OptimizationSuggestion {
+                    title: "Cache Computed Values".to_string(),
+                    location: location(call.line),
+                    current: format!("`{}` evaluated repeatedly with the same arguments", call.qualified_name()),
+                    recommendation: "Store intermediate results in variables".to_string(),
+                    savings: 100,
+                });
             }
+        }
 
-            // Check for vector usage where table would be better
-            if line.contains("vector::") && (line.contains("borrow") || line.contains("contains")) {
+        // Check for vector usage where table would be better
+        for call in &body.calls {
+            let lookup = call.function == "contains" || call.function == "index_of";
+            let on_vector = call.module.last().map(|m| m == "vector").unwrap_or(false)
+                || (call.is_method && call.function == "index_of");
+            if lookup && on_vector {
                 suggestions.push(OptimizationSuggestion {
                     title: "Use Efficient Data Structures".to_string(),
-                    location: format!("{}:{}", file_name, i + 1),
+                    location: location(call.line),
                     current: "Using vector for lookups".to_string(),
                     recommendation: "Use Table or ObjectTable for O(1) access".to_string(),
                     savings: 180,
@@ -276,44 +263,35 @@ fn analyze_for_optimizations(source_dir: &str) -> Result<Vec<OptimizationSuggest
     }
 
     // Remove duplicates and limit to top 5
-    suggestions.sort_by(|a, b| b.savings.cmp(&a.savings));
+    suggestions.sort_by_key(|s| std::cmp::Reverse(s.savings));
     suggestions.truncate(5);
 
     Ok(suggestions)
 }
 
+fn is_vector_op(call: &CallSite) -> bool {
+    const VECTOR_METHODS: [&str; 6] = ["push_back", "pop_back", "borrow", "borrow_mut", "remove", "append"];
+    call.module.last().map(|m| m == "vector").unwrap_or(false)
+        || (call.is_method && VECTOR_METHODS.contains(&call.function.as_str()))
+}
 
 fn parse_move_functions(source_dir: &str) -> Result<Vec<GasProfile>> {
-    let mut profiles = Vec::new();
-    let path = Path::new(source_dir);
-
-    if !path.exists() {
-        return Ok(profiles);
-    }
+    let package = Package::load(Path::new(source_dir))?;
 
-    let function_regex = Regex::new(r"(?m)^\s*(?:public\s+)?fun\s+(\w+)").unwrap();
-
-    for entry in walkdir::WalkDir::new(path)
-        .into_iter()
-        .filter_map(|e| e.ok())
-        .filter(|e| e.path().extension().map_or(false, |ext| ext == "move"))
-    {
-        let content = fs::read_to_string(entry.path())?;
-        
-        for cap in function_regex.captures_iter(&content) {
-            let function_name = cap[1].to_string();
-            
+    let profiles = package
+        .functions()
+        .filter(|(_, f)| !f.is_test() && !f.is_test_only() && !f.is_native && !f.is_macro)
+        .map(|(module, function)| {
             // Estimate gas based on function complexity
-            let gas_estimate = estimate_gas_usage(&content, &function_name);
-            
-            profiles.push(GasProfile {
-                function: function_name,
+            let gas_estimate = estimate_gas_usage(function);
+            GasProfile {
+                function: format!("{}::{}", module.name, function.name),
                 gas_used: gas_estimate.total,
                 storage_cost: gas_estimate.storage,
                 computation_cost: gas_estimate.computation,
-            });
-        }
-    }
+            }
+        })
+        .collect();
 
     Ok(profiles)
 }
@@ -324,37 +302,36 @@ struct GasEstimate {
     computation: u64,
 }
 
-fn estimate_gas_usage(content: &str, function_name: &str) -> GasEstimate {
-    // Find function body
-    let function_pattern = format!(r"fun\s+{}\s*\([^)]*\)[^{{]*\{{", function_name);
-    let function_regex = Regex::new(&function_pattern).unwrap();
-    
-    if let Some(mat) = function_regex.find(content) {
-        let start = mat.end();
-        let remaining = &content[start..];
-        
-        // Count operations that affect gas
-        let object_new_count = remaining.matches("object::new").count() as u64;
-        let transfer_count = remaining.matches("transfer::").count() as u64;
-        let table_ops = remaining.matches("table::").count() as u64;
-        let vector_ops = remaining.matches("vector::").count() as u64;
-        let balance_ops = remaining.matches("balance::").count() as u64;
-        
-        // Estimate costs (approximate values based on Sui gas schedule)
-        let storage = object_new_count * 500 + table_ops * 300;
-        let computation = transfer_count * 200 + vector_ops * 50 + balance_ops * 100;
-        let total = storage + computation + 200; // Base cost
-        
-        GasEstimate {
-            total,
-            storage,
-            computation,
-        }
-    } else {
-        GasEstimate {
-            total: 200,
-            storage: 0,
-            computation: 200,
+fn estimate_gas_usage(function: &Function) -> GasEstimate {
+    let calls = match &function.body {
+        Some(body) => &body.calls,
+        None => {
+            return GasEstimate {
+                total: 200,
+                storage: 0,
+                computation: 200,
+            }
         }
+    };
+
+    let count = |pred: &dyn Fn(&CallSite) -> bool| calls.iter().filter(|c| pred(c)).count() as u64;
+    let in_module = |c: &CallSite, module: &str| c.module.last().map(|m| m == module).unwrap_or(false);
+
+    // Count operations that affect gas
+    let object_new_count = count(&|c| c.targets("object", "new"));
+    let transfer_count = count(&|c| in_module(c, "transfer"));
+    let table_ops = count(&|c| in_module(c, "table") || in_module(c, "object_table"));
+    let vector_ops = count(&is_vector_op);
+    let balance_ops = count(&|c| in_module(c, "balance"));
+
+    // Estimate costs (approximate values based on Sui gas schedule)
+    let storage = object_new_count * 500 + table_ops * 300;
+    let computation = transfer_count * 200 + vector_ops * 50 + balance_ops * 100;
+    let total = storage + computation + 200; // Base cost
+
+    GasEstimate {
+        total,
+        storage,
+        computation,
     }
 }
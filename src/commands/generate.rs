@@ -1,10 +1,16 @@
-use crate::codegen;
+use crate::codegen::{self, PackageInterface};
 use crate::config::{find_project_root, load_config};
 use crate::error::{Result, SuiForgeError};
 use crate::utils;
 use colored::Colorize;
+use std::path::Path;
 
-pub async fn execute(target: String, output: Option<String>) -> Result<()> {
+pub async fn execute(
+    target: String,
+    output: Option<String>,
+    abi: Option<String>,
+    network: Option<String>,
+) -> Result<()> {
     let root = find_project_root()?;
     let config = load_config()?;
 
@@ -16,6 +22,11 @@ pub async fn execute(target: String, output: Option<String>) -> Result<()> {
                 .as_ref()
                 .map(|c| c.output_dir.clone()),
             "rust" => config.codegen.rust.as_ref().map(|c| c.output_dir.clone()),
+            "python" => config
+                .codegen
+                .python
+                .as_ref()
+                .map(|c| c.output_dir.clone()),
             "swift" => config.codegen.swift.as_ref().map(|c| c.output_dir.clone()),
             _ => None,
         }
@@ -29,23 +40,27 @@ pub async fn execute(target: String, output: Option<String>) -> Result<()> {
     ));
 
     let spinner = utils::create_spinner("Analyzing Move modules...");
+    let load_interface = || match &abi {
+        Some(path) => PackageInterface::from_file(Path::new(path)),
+        None => PackageInterface::from_sources(&root, network.as_deref()),
+    };
 
     match target.as_str() {
         "ts" | "typescript" => {
-            codegen::typescript::generate(&root, &output_dir)?;
+            codegen::typescript::generate(&root, &output_dir, &load_interface()?)?;
             spinner.finish_with_message("TypeScript SDK generated");
         }
         "rust" => {
-            spinner.finish_with_message("Rust SDK generation not yet implemented");
-            utils::warning("Rust SDK generation coming soon!");
+            codegen::rust::generate(&root, &output_dir, &load_interface()?)?;
+            spinner.finish_with_message("Rust SDK generated");
         }
         "swift" => {
-            spinner.finish_with_message("Swift SDK generation not yet implemented");
-            utils::warning("Swift SDK generation coming soon!");
+            codegen::swift::generate(&root, &output_dir, &load_interface()?)?;
+            spinner.finish_with_message("Swift SDK generated");
         }
         "python" => {
-            spinner.finish_with_message("Python SDK generation not yet implemented");
-            utils::warning("Python SDK generation coming soon!");
+            codegen::python::generate(&root, &output_dir, &load_interface()?)?;
+            spinner.finish_with_message("Python SDK generated");
         }
         _ => {
             return Err(SuiForgeError::CodegenFailed(format!(
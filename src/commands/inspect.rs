@@ -1,17 +1,94 @@
-use crate::error::Result;
+use crate::abi::same_address;
+use crate::config::resolve_network;
+use crate::error::{Result, SuiForgeError};
+use crate::rpc::types::{
+    DynamicFieldType, ObjectDataOptions, SuiObjectData, SuiParsedData, SuiPastObjectResponse,
+    TransactionBlockResponseOptions,
+};
+use crate::rpc::{collect_pages, RpcClient};
 use crate::utils;
 use colored::Colorize;
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use std::future::Future;
+use std::pin::Pin;
+
+mod package;
+mod tx;
+
+/// Collections whose entries are stored as dynamic fields of their `id`.
+const CONTAINERS: &[(&str, &str)] = &[
+    ("table", "Table"),
+    ("bag", "Bag"),
+    ("object_table", "ObjectTable"),
+    ("object_bag", "ObjectBag"),
+    ("linked_table", "LinkedTable"),
+];
+
+/// An object with its dynamic fields loaded.
+struct Inspected {
+    object: SuiObjectData,
+    dynamic_fields: Vec<Value>,
+    truncated: bool,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn execute(
+    target: String,
+    id: Option<String>,
+    network: Option<String>,
+    format: String,
+    depth: usize,
+    limit: usize,
+    history: bool,
+    diff: Option<String>,
+) -> Result<()> {
+    let network = resolve_network(network.as_deref())?;
+    let client = RpcClient::for_network(&network);
+
+    if (target == "tx" || target == "package") && (history || diff.is_some()) {
+        return Err(SuiForgeError::Custom(format!(
+            "--history and --diff apply to objects, not `inspect {}`",
+            target
+        )));
+    }
+
+    let object_id = match (target.as_str(), id) {
+        ("tx", Some(digest)) => return tx::execute(&client, &digest, &format).await,
+        ("tx", None) => {
+            return Err(SuiForgeError::Custom(
+                "Usage: suiforge inspect tx <digest>".to_string(),
+            ))
+        }
+        ("package", id) => {
+            return package::execute(&client, &network, id.as_deref(), &format).await
+        }
+        (_, Some(extra)) => {
+            return Err(SuiForgeError::Custom(format!(
+                "Unexpected argument '{}'; use `suiforge inspect tx <digest>` or `suiforge inspect package [id]`",
+                extra
+            )))
+        }
+        (_, None) => target,
+    };
+
+    if history {
+        return print_history(&client, &object_id, limit, &format).await;
+    }
+    if let Some(range) = diff {
+        return print_diff(&client, &object_id, &range, &format).await;
+    }
 
-pub async fn execute(object_id: String, network: String, format: String) -> Result<()> {
     utils::info(&format!(
         "Inspecting object {} on {}...",
         object_id.cyan(),
-        network.yellow()
+        network.name.yellow()
     ));
 
     let spinner = utils::create_spinner("Fetching object data...");
-    let object_data = fetch_object_data(&object_id, &network).await?;
+    let object_data = fetch_object_data(&client, &object_id, depth, limit).await;
     spinner.finish_with_message("Object data retrieved");
+    let object_data = object_data?;
 
     match format.as_str() {
         "json" => print_json_format(&object_data).await,
@@ -20,63 +97,162 @@ pub async fn execute(object_id: String, network: String, format: String) -> Resu
     }
 }
 
-async fn fetch_object_data(object_id: &str, network: &str) -> Result<serde_json::Value> {
-    let rpc_url = match network {
-        "devnet" => "https://fullnode.devnet.sui.io:443",
-        "testnet" => "https://fullnode.testnet.sui.io:443",
-        "mainnet" => "https://fullnode.mainnet.sui.io:443",
-        _ => return Err(crate::error::SuiForgeError::InvalidNetwork(network.to_string())),
-    };
+/// Fetch an object, expanding its dynamic fields and any tables or bags in
+/// its content `depth` levels deep.
+async fn fetch_object_data(
+    client: &RpcClient,
+    object_id: &str,
+    depth: usize,
+    limit: usize,
+) -> Result<Inspected> {
+    let mut object = client
+        .get_object(object_id, ObjectDataOptions::full_content())
+        .await?;
 
-    let client = reqwest::Client::new();
-    let response = client
-        .post(rpc_url)
-        .json(&serde_json::json!({
-            "jsonrpc": "2.0",
-            "id": 1,
-            "method": "sui_getObject",
-            "params": [
-                object_id,
-                {
-                    "showType": true,
-                    "showOwner": true,
-                    "showPreviousTransaction": true,
-                    "showDisplay": false,
-                    "showContent": true,
-                    "showBcs": false,
-                    "showStorageRebate": true
+    if depth == 0 {
+        return Ok(Inspected {
+            object,
+            dynamic_fields: Vec::new(),
+            truncated: false,
+        });
+    }
+
+    if let Some(SuiParsedData::MoveObject { fields, .. }) = &mut object.content {
+        expand_value(client, fields, depth, limit).await?;
+    }
+    let (dynamic_fields, truncated) = dynamic_fields(client, object_id, depth, limit).await?;
+
+    Ok(Inspected {
+        object,
+        dynamic_fields,
+        truncated,
+    })
+}
+
+type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + 'a>>;
+
+/// Replace the tables and bags inside `value` with their entries.
+fn expand_value<'a>(
+    client: &'a RpcClient,
+    value: &'a mut Value,
+    depth: usize,
+    limit: usize,
+) -> BoxFuture<'a, Result<()>> {
+    Box::pin(async move {
+        match value {
+            Value::Object(map) => {
+                if let Some(id) = container_id(map) {
+                    let (entries, truncated) = dynamic_fields(client, &id, depth, limit).await?;
+                    map.insert("entries".to_string(), Value::Array(entries));
+                    if truncated {
+                        map.insert("truncated".to_string(), Value::Bool(true));
+                    }
+                } else {
+                    for child in map.values_mut() {
+                        expand_value(client, child, depth, limit).await?;
+                    }
                 }
-            ]
-        }))
-        .send()
-        .await?;
+            }
+            Value::Array(items) => {
+                for item in items {
+                    expand_value(client, item, depth, limit).await?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    })
+}
 
-    let json: serde_json::Value = response.json().await?;
+/// The UID of a `{ type, fields }` value if it is a table or bag.
+fn container_id(map: &Map<String, Value>) -> Option<String> {
+    let type_ = map.get("type")?.as_str()?;
+    let base = type_.split('<').next().unwrap_or(type_);
+    let mut parts = base.split("::");
+    let (address, module, name) = (parts.next()?, parts.next()?, parts.next()?);
+    if !same_address(address, "0x2") || !CONTAINERS.contains(&(module, name)) {
+        return None;
+    }
+    map.get("fields")?
+        .get("id")?
+        .get("id")?
+        .as_str()
+        .map(str::to_string)
+}
 
-    if let Some(error) = json.get("error") {
-        return Err(crate::error::SuiForgeError::Custom(format!(
-            "RPC error: {}",
-            error
-        )));
+/// List the dynamic fields of `parent` with their values, expanding the
+/// values `depth - 1` further levels. Returns the entries and whether more
+/// than `limit` exist.
+async fn dynamic_fields(
+    client: &RpcClient,
+    parent: &str,
+    depth: usize,
+    limit: usize,
+) -> Result<(Vec<Value>, bool)> {
+    let mut infos = collect_pages(
+        |cursor| client.get_dynamic_fields(parent, cursor, Some(limit.clamp(1, 50))),
+        Some(limit + 1),
+    )
+    .await?;
+    let truncated = infos.len() > limit;
+    infos.truncate(limit);
+    if infos.is_empty() {
+        return Ok((Vec::new(), truncated));
     }
 
-    Ok(json)
+    let ids: Vec<String> = infos.iter().map(|i| i.object_id.clone()).collect();
+    let objects = client
+        .get_objects(&ids, ObjectDataOptions::full_content())
+        .await?;
+
+    let mut entries = Vec::new();
+    for (info, object) in infos.into_iter().zip(objects) {
+        let content = object.data.and_then(|d| d.content);
+        let mut value = match (info.type_, content) {
+            // The value sits inside the `Field<Name, Value>` wrapper
+            (DynamicFieldType::DynamicField, Some(SuiParsedData::MoveObject { fields, .. })) => {
+                fields.get("value").cloned().unwrap_or(Value::Null)
+            }
+            (
+                DynamicFieldType::DynamicObject,
+                Some(SuiParsedData::MoveObject { type_, fields, .. }),
+            ) => json!({ "type": type_, "fields": fields }),
+            _ => Value::Null,
+        };
+        if depth > 1 {
+            expand_value(client, &mut value, depth - 1, limit).await?;
+        }
+
+        entries.push(json!({
+            "name": info.name.value,
+            "nameType": info.name.type_,
+            "kind": match info.type_ {
+                DynamicFieldType::DynamicField => "field",
+                DynamicFieldType::DynamicObject => "object",
+            },
+            "objectId": info.object_id,
+            "objectType": info.object_type,
+            "value": value,
+        }));
+    }
+    Ok((entries, truncated))
 }
 
-async fn print_tree_format(data: &serde_json::Value) -> Result<()> {
+async fn print_tree_format(inspected: &Inspected) -> Result<()> {
+    let object = &inspected.object;
     println!();
     println!("{}", "📦 Object Inspector".bold());
     println!("{}", "═".repeat(60));
     println!();
 
-    let result = &data["result"]["data"];
-    let object_id = result["objectId"].as_str().unwrap_or("unknown");
-    let object_type = result["type"].as_str().unwrap_or("unknown");
-    let owner = result["owner"].as_object()
-        .and_then(|o| o.get("AddressOwner"))
-        .and_then(|a| a.as_str())
-        .unwrap_or("unknown");
-    let version = result["version"].as_str().unwrap_or("unknown");
+    let object_id = object.object_id.as_str();
+    let object_type = object.type_.as_deref().unwrap_or("unknown");
+    let owner = object
+        .owner
+        .as_ref()
+        .map(|o| o.to_string())
+        .unwrap_or_else(|| "unknown".to_string());
+    let version = object.version.as_str();
 
     println!("🔍 {}", "Object Details".bold());
     println!("├─ ID: {}", object_id.cyan());
@@ -85,15 +261,25 @@ async fn print_tree_format(data: &serde_json::Value) -> Result<()> {
     println!("└─ Version: {}", version.dimmed());
     println!();
 
-    if let Some(content) = result["content"].as_object() {
+    if let Some(SuiParsedData::MoveObject { fields, .. }) = &object.content {
         println!("📊 {}", "Object Data".bold());
-        if let Some(fields) = content["fields"].as_object() {
+        if let Some(fields) = fields.as_object() {
             print_fields(fields, "");
         }
         println!();
     }
 
-    if let Some(storage_rebate) = result["storageRebate"].as_str() {
+    if !inspected.dynamic_fields.is_empty() {
+        println!("🧩 {}", "Dynamic Fields".bold());
+        let children: Vec<_> = inspected.dynamic_fields.iter().map(entry_child).collect();
+        print_children(&children, "");
+        if inspected.truncated {
+            println!("   {}", "… more entries not shown (raise --limit)".dimmed());
+        }
+        println!();
+    }
+
+    if let Some(storage_rebate) = &object.storage_rebate {
         println!("⛽ {}", "Gas & Storage".bold());
         println!("├─ Storage Rebate: {}", storage_rebate.green());
         println!();
@@ -109,55 +295,87 @@ async fn print_tree_format(data: &serde_json::Value) -> Result<()> {
     Ok(())
 }
 
-fn print_fields(fields: &serde_json::Map<String, serde_json::Value>, prefix: &str) {
-    let keys: Vec<_> = fields.keys().collect();
-    for (i, key) in keys.iter().enumerate() {
-        let is_last = i == keys.len() - 1;
+fn print_fields(fields: &Map<String, Value>, prefix: &str) {
+    let children: Vec<_> = fields
+        .iter()
+        .map(|(key, value)| (key.cyan().to_string(), value))
+        .collect();
+    print_children(&children, prefix);
+}
+
+fn print_children(children: &[(String, &Value)], prefix: &str) {
+    for (i, (label, value)) in children.iter().enumerate() {
+        let is_last = i == children.len() - 1;
         let branch = if is_last { "└─" } else { "├─" };
-        let value = &fields[*key];
+        let new_prefix = format!("{}{}  ", prefix, if is_last { " " } else { "│" });
 
         match value {
-            serde_json::Value::Object(obj) => {
-                println!("{}{} {}", prefix, branch, key.cyan());
-                let new_prefix = format!("{}{}  ", prefix, if is_last { " " } else { "│" });
+            Value::Object(obj) => {
+                println!("{}{} {}", prefix, branch, label);
                 print_fields(obj, &new_prefix);
             }
-            serde_json::Value::String(s) => {
-                println!("{}{} {}: {}", prefix, branch, key.cyan(), s.green());
+            Value::Array(items) if items.iter().any(|item| item.is_object()) => {
+                println!("{}{} {} ({})", prefix, branch, label, items.len());
+                let children: Vec<_> = items
+                    .iter()
+                    .enumerate()
+                    .map(|(i, item)| {
+                        if is_entry(item) {
+                            entry_child(item)
+                        } else {
+                            (format!("[{}]", i).cyan().to_string(), item)
+                        }
+                    })
+                    .collect();
+                print_children(&children, &new_prefix);
+            }
+            Value::String(s) => {
+                println!("{}{} {}: {}", prefix, branch, label, s.green());
             }
-            serde_json::Value::Number(n) => {
-                println!("{}{} {}: {}", prefix, branch, key.cyan(), n.to_string().yellow());
+            Value::Number(n) => {
+                println!("{}{} {}: {}", prefix, branch, label, n.to_string().yellow());
             }
-            serde_json::Value::Bool(b) => {
-                println!("{}{} {}: {}", prefix, branch, key.cyan(), b.to_string().blue());
+            Value::Bool(b) => {
+                println!("{}{} {}: {}", prefix, branch, label, b.to_string().blue());
             }
             _ => {
-                println!("{}{} {}: {}", prefix, branch, key.cyan(), value.to_string().dimmed());
+                println!("{}{} {}: {}", prefix, branch, label, value.to_string().dimmed());
             }
         }
     }
 }
 
-async fn print_text_format(data: &serde_json::Value) -> Result<()> {
-    let result = &data["result"]["data"];
-    
+/// Whether `value` is a dynamic field entry built by [`dynamic_fields`].
+fn is_entry(value: &Value) -> bool {
+    value.get("nameType").is_some() && value.get("value").is_some()
+}
+
+/// Label a dynamic field entry by its key and show only its value.
+fn entry_child(entry: &Value) -> (String, &Value) {
+    let name = match &entry["name"] {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    };
+    (format!("[{}]", name).magenta().to_string(), &entry["value"])
+}
+
+async fn print_text_format(inspected: &Inspected) -> Result<()> {
+    let object = &inspected.object;
     println!();
     println!("{}", "Object Details:".bold());
-    println!("  ID: {}", result["objectId"].as_str().unwrap_or("unknown").cyan());
-    println!("  Type: {}", result["type"].as_str().unwrap_or("unknown").green());
-    
-    if let Some(owner) = result["owner"].as_object() {
-        if let Some(address) = owner.get("AddressOwner").and_then(|a| a.as_str()) {
-            println!("  Owner: {}", address.blue());
-        }
+    println!("  ID: {}", object.object_id.cyan());
+    println!("  Type: {}", object.type_.as_deref().unwrap_or("unknown").green());
+
+    if let Some(owner) = &object.owner {
+        println!("  Owner: {}", owner.to_string().blue());
     }
-    
-    println!("  Version: {}", result["version"].as_str().unwrap_or("unknown").dimmed());
+
+    println!("  Version: {}", object.version.dimmed());
     println!();
 
-    if let Some(content) = result["content"].as_object() {
+    if let Some(SuiParsedData::MoveObject { fields, .. }) = &object.content {
         println!("{}", "Data:".bold());
-        if let Some(fields) = content["fields"].as_object() {
+        if let Some(fields) = fields.as_object() {
             for (key, value) in fields {
                 println!("  {}: {}", key, value);
             }
@@ -165,10 +383,547 @@ async fn print_text_format(data: &serde_json::Value) -> Result<()> {
         println!();
     }
 
+    if !inspected.dynamic_fields.is_empty() {
+        println!("{}", "Dynamic Fields:".bold());
+        for entry in &inspected.dynamic_fields {
+            let (name, value) = entry_child(entry);
+            println!("  {}: {}", name, value);
+        }
+        if inspected.truncated {
+            println!("  {}", "… more entries not shown (raise --limit)".dimmed());
+        }
+        println!();
+    }
+
+    Ok(())
+}
+
+async fn print_json_format(inspected: &Inspected) -> Result<()> {
+    let mut json = serde_json::to_value(&inspected.object)?;
+    if let Value::Object(map) = &mut json {
+        map.insert(
+            "dynamicFields".to_string(),
+            Value::Array(inspected.dynamic_fields.clone()),
+        );
+        if inspected.truncated {
+            map.insert("dynamicFieldsTruncated".to_string(), Value::Bool(true));
+        }
+    }
+    println!("{}", serde_json::to_string_pretty(&json)?);
     Ok(())
 }
 
-async fn print_json_format(data: &serde_json::Value) -> Result<()> {
-    println!("{}", serde_json::to_string_pretty(&data["result"])?);
+/// One version of an object and the transaction that produced it.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct HistoryEntry {
+    version: u64,
+    transaction: String,
+    sender: Option<String>,
+    timestamp: Option<String>,
+    /// `created`, `mutated`, or `unwrapped` when the object re-entered
+    /// storage without having been an input.
+    change: &'static str,
+}
+
+/// Walk back from the current version through the transaction that produced
+/// each version, at most `limit` steps. Returns the entries, newest first,
+/// and whether the walk stopped because the node no longer has a version.
+async fn object_history(
+    client: &RpcClient,
+    object_id: &str,
+    limit: usize,
+) -> Result<(Vec<HistoryEntry>, bool)> {
+    let object = client
+        .get_object(object_id, ObjectDataOptions::full_content())
+        .await?;
+    let mut version = parse_version(&object.version)?;
+    let mut digest = object.previous_transaction;
+
+    let options = TransactionBlockResponseOptions {
+        show_input: true,
+        show_effects: true,
+        ..Default::default()
+    };
+
+    let mut entries = Vec::new();
+    while let Some(tx_digest) = digest.take() {
+        if entries.len() >= limit {
+            break;
+        }
+        let tx = client
+            .get_transaction_block(&tx_digest, options.clone())
+            .await?;
+        let effects = tx.effects.as_ref();
+        let previous = effects.and_then(|e| {
+            e.modified_at_versions
+                .iter()
+                .find(|m| same_address(&m.object_id, object_id))
+                .and_then(|m| m.sequence_number.parse::<u64>().ok())
+        });
+        let created = effects.is_some_and(|e| {
+            e.created
+                .iter()
+                .any(|o| same_address(&o.reference.object_id, object_id))
+        });
+
+        entries.push(HistoryEntry {
+            version,
+            transaction: tx_digest,
+            sender: tx
+                .transaction
+                .as_ref()
+                .and_then(|t| t["data"]["sender"].as_str())
+                .map(str::to_string),
+            timestamp: tx
+                .timestamp_ms
+                .as_deref()
+                .and_then(|ms| ms.parse().ok())
+                .and_then(chrono::DateTime::from_timestamp_millis)
+                .map(|t| t.to_rfc3339()),
+            change: match (created, previous) {
+                (true, _) => "created",
+                (false, Some(_)) => "mutated",
+                (false, None) => "unwrapped",
+            },
+        });
+
+        let Some(previous) = previous else { break };
+        match client
+            .try_get_past_object(object_id, previous, ObjectDataOptions::full_content())
+            .await?
+        {
+            SuiPastObjectResponse::VersionFound(data) => {
+                version = previous;
+                digest = data.previous_transaction;
+            }
+            _ => return Ok((entries, true)),
+        }
+    }
+
+    Ok((entries, false))
+}
+
+async fn print_history(
+    client: &RpcClient,
+    object_id: &str,
+    limit: usize,
+    format: &str,
+) -> Result<()> {
+    let spinner = utils::create_spinner("Walking object history...");
+    let result = object_history(client, object_id, limit).await;
+    spinner.finish_and_clear();
+    let (entries, pruned) = result?;
+
+    if format == "json" {
+        let json = json!({
+            "objectId": object_id,
+            "versions": entries,
+            "pruned": pruned,
+        });
+        println!("{}", serde_json::to_string_pretty(&json)?);
+        return Ok(());
+    }
+
+    println!();
+    println!("{} {}", "📜 Version History of".bold(), object_id.cyan());
+    println!("{}", "═".repeat(60));
+    for (i, entry) in entries.iter().enumerate() {
+        let is_last = i == entries.len() - 1;
+        let (branch, prefix) = if is_last { ("└─", "   ") } else { ("├─", "│  ") };
+        println!(
+            "{} v{} {}",
+            branch,
+            entry.version.to_string().yellow(),
+            entry.change.green()
+        );
+        println!("{}  tx: {}", prefix, entry.transaction.cyan());
+        if let Some(sender) = &entry.sender {
+            println!("{}  sender: {}", prefix, sender.blue());
+        }
+        if let Some(timestamp) = &entry.timestamp {
+            println!("{}  at: {}", prefix, timestamp.dimmed());
+        }
+    }
+    println!();
+
+    if pruned {
+        utils::warning("Older versions are not available from this node");
+    } else if entries.len() >= limit && entries.last().is_some_and(|e| e.change == "mutated") {
+        utils::info(&format!(
+            "Showing the latest {} versions (raise --limit for more)",
+            limit
+        ));
+    }
     Ok(())
 }
+
+/// Label for a change to the object's content as a whole, which has no path.
+const ROOT_PATH: &str = "(root)";
+
+#[derive(Serialize, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+enum FieldChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+/// A field that differs between two versions of an object.
+#[derive(Serialize)]
+struct FieldChange {
+    /// Dotted path to the field; empty when the content changed as a whole.
+    path: String,
+    #[serde(skip)]
+    segments: Vec<String>,
+    kind: FieldChangeKind,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    old: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    new: Option<Value>,
+}
+
+async fn print_diff(client: &RpcClient, object_id: &str, range: &str, format: &str) -> Result<()> {
+    let (from, to) = parse_range(range)?;
+
+    let spinner = utils::create_spinner("Fetching object versions...");
+    let result = async {
+        let to = match to {
+            Some(version) => version,
+            None => {
+                let current = client
+                    .get_object(object_id, ObjectDataOptions::full_content())
+                    .await?;
+                parse_version(&current.version)?
+            }
+        };
+        let old = past_fields(client, object_id, from).await?;
+        let new = past_fields(client, object_id, to).await?;
+        Ok::<_, SuiForgeError>((to, old, new))
+    }
+    .await;
+    spinner.finish_and_clear();
+    let (to, old, new) = result?;
+
+    let mut changes = Vec::new();
+    diff_values(&mut Vec::new(), &old, &new, &mut changes);
+
+    match format {
+        "json" => {
+            let json = json!({
+                "objectId": object_id,
+                "from": from,
+                "to": to,
+                "changes": changes,
+            });
+            println!("{}", serde_json::to_string_pretty(&json)?);
+        }
+        "tree" => {
+            println!();
+            println!(
+                "{} {} v{} → v{}",
+                "🔀 Diff of".bold(),
+                object_id.cyan(),
+                from,
+                to
+            );
+            println!("{}", "═".repeat(60));
+            let changes: Vec<&FieldChange> = changes.iter().collect();
+            for line in diff_tree(&changes, 0, "") {
+                println!("{}", line);
+            }
+            println!();
+        }
+        _ => {
+            println!();
+            println!("{}", format!("Changes from v{} to v{}:", from, to).bold());
+            for change in &changes {
+                println!("  {}", describe_change(change, display_path(change)));
+            }
+            println!();
+        }
+    }
+
+    if changes.is_empty() {
+        utils::info("No field changes between these versions");
+    }
+    Ok(())
+}
+
+/// Parse `v1..v2`, where the `v` prefixes are optional and an omitted end
+/// means the current version.
+fn parse_range(range: &str) -> Result<(u64, Option<u64>)> {
+    let invalid = || {
+        SuiForgeError::Custom(format!(
+            "Invalid version range '{}', expected e.g. 12..15 or 12..",
+            range
+        ))
+    };
+    let (from, to) = range.split_once("..").ok_or_else(invalid)?;
+    let version = |s: &str| s.trim().trim_start_matches('v').parse::<u64>().ok();
+    let from = version(from).ok_or_else(invalid)?;
+    let to = if to.trim().is_empty() {
+        None
+    } else {
+        Some(version(to).ok_or_else(invalid)?)
+    };
+    Ok((from, to))
+}
+
+fn parse_version(version: &str) -> Result<u64> {
+    version
+        .parse()
+        .map_err(|_| SuiForgeError::Custom(format!("Unexpected object version {}", version)))
+}
+
+/// The content fields of `object_id` at exactly `version`.
+async fn past_fields(client: &RpcClient, object_id: &str, version: u64) -> Result<Value> {
+    let response = client
+        .try_get_past_object(object_id, version, ObjectDataOptions::full_content())
+        .await?;
+    let data = match response {
+        SuiPastObjectResponse::VersionFound(data) => data,
+        SuiPastObjectResponse::ObjectDeleted(_) => {
+            return Err(SuiForgeError::Custom(format!(
+                "{} was deleted at version {}",
+                object_id, version
+            )))
+        }
+        _ => {
+            return Err(SuiForgeError::Custom(format!(
+                "Version {} of {} is not available; use --history to list versions",
+                version, object_id
+            )))
+        }
+    };
+    match data.content {
+        Some(SuiParsedData::MoveObject { fields, .. }) => Ok(fields),
+        _ => Ok(Value::Null),
+    }
+}
+
+fn diff_values(path: &mut Vec<String>, old: &Value, new: &Value, out: &mut Vec<FieldChange>) {
+    let change = |path: &[String], kind, old: Option<&Value>, new: Option<&Value>| FieldChange {
+        path: join_path(path),
+        segments: path.to_vec(),
+        kind,
+        old: old.cloned(),
+        new: new.cloned(),
+    };
+
+    match (old, new) {
+        (Value::Object(a), Value::Object(b)) => {
+            // Nested structs are `{ type, fields }`; leave the wrapper out of
+            // the path unless the type itself changed.
+            if let (Some(a_fields), Some(b_fields)) = (a.get("fields"), b.get("fields")) {
+                if a.get("type") == b.get("type") {
+                    return diff_values(path, a_fields, b_fields, out);
+                }
+            }
+            let mut keys: Vec<&String> = a.keys().chain(b.keys()).collect();
+            keys.sort();
+            keys.dedup();
+            for key in keys {
+                path.push(key.clone());
+                match (a.get(key), b.get(key)) {
+                    (Some(a), Some(b)) => diff_values(path, a, b, out),
+                    (Some(a), None) => {
+                        out.push(change(path, FieldChangeKind::Removed, Some(a), None))
+                    }
+                    (None, Some(b)) => {
+                        out.push(change(path, FieldChangeKind::Added, None, Some(b)))
+                    }
+                    (None, None) => {}
+                }
+                path.pop();
+            }
+        }
+        (Value::Array(a), Value::Array(b)) if a.len() == b.len() => {
+            for (i, (a, b)) in a.iter().zip(b).enumerate() {
+                path.push(format!("[{}]", i));
+                diff_values(path, a, b, out);
+                path.pop();
+            }
+        }
+        _ if old != new => out.push(change(path, FieldChangeKind::Changed, Some(old), Some(new))),
+        _ => {}
+    }
+}
+
+fn join_path(segments: &[String]) -> String {
+    let mut path = String::new();
+    for segment in segments {
+        if !path.is_empty() && !segment.starts_with('[') {
+            path.push('.');
+        }
+        path.push_str(segment);
+    }
+    path
+}
+
+/// One line for `change`, labelled with its full path in the text view or
+/// just its last segment in the tree.
+fn describe_change(change: &FieldChange, path: &str) -> String {
+    let show = |v: &Option<Value>| v.as_ref().map(compact).unwrap_or_default();
+    match change.kind {
+        FieldChangeKind::Added => {
+            format!("{} {}: {}", "+".green(), path, show(&change.new).green())
+        }
+        FieldChangeKind::Removed => format!("{} {}: {}", "-".red(), path, show(&change.old).red()),
+        FieldChangeKind::Changed => format!(
+            "{} {}: {} → {}",
+            "~".yellow(),
+            path,
+            show(&change.old).red(),
+            show(&change.new).green()
+        ),
+    }
+}
+
+fn display_path(change: &FieldChange) -> &str {
+    if change.path.is_empty() {
+        ROOT_PATH
+    } else {
+        &change.path
+    }
+}
+
+fn compact(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Lines of the tree of changes sharing the first `level` path segments. A
+/// change to the whole content is shown as a single `(root)` leaf.
+fn diff_tree(changes: &[&FieldChange], level: usize, prefix: &str) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut groups: Vec<(&str, Vec<&FieldChange>)> = Vec::new();
+    for change in changes {
+        let segment = match change.segments.get(level) {
+            Some(segment) => segment.as_str(),
+            None if change.segments.is_empty() => ROOT_PATH,
+            None => continue,
+        };
+        match groups.iter_mut().find(|(s, _)| *s == segment) {
+            Some((_, group)) => group.push(change),
+            None => groups.push((segment, vec![change])),
+        }
+    }
+
+    for (i, (segment, group)) in groups.iter().enumerate() {
+        let is_last = i == groups.len() - 1;
+        let branch = if is_last { "└─" } else { "├─" };
+        let leaf = group.iter().find(|c| c.segments.len() <= level + 1);
+
+        match leaf {
+            // The tree already shows the rest of the path
+            Some(change) => lines.push(format!(
+                "{}{} {}",
+                prefix,
+                branch,
+                describe_change(change, segment)
+            )),
+            None => {
+                lines.push(format!("{}{} {}", prefix, branch, segment.cyan()));
+                let new_prefix = format!("{}{}  ", prefix, if is_last { " " } else { "│" });
+                lines.extend(diff_tree(group, level + 1, &new_prefix));
+            }
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn diff(old: Value, new: Value) -> Vec<FieldChange> {
+        let mut changes = Vec::new();
+        diff_values(&mut Vec::new(), &old, &new, &mut changes);
+        changes
+    }
+
+    fn paths(changes: &[FieldChange]) -> Vec<&str> {
+        changes.iter().map(|c| c.path.as_str()).collect()
+    }
+
+    fn pool(name: &str, tags: Value) -> Value {
+        json!({
+            "balance": "10",
+            "owner": {
+                "type": "0x5a1e::pool::Owner",
+                "fields": { "name": name, "tags": tags },
+            },
+        })
+    }
+
+    #[test]
+    fn diff_values_walks_nested_structs_and_arrays() {
+        let old = pool("a", json!(["x", "y"]));
+        let mut new = pool("b", json!(["x", "z"]));
+        new["balance"] = json!("12");
+        new["extra"] = json!(1);
+
+        let changes = diff(old, new);
+        assert_eq!(
+            paths(&changes),
+            ["balance", "extra", "owner.name", "owner.tags[1]"]
+        );
+        assert!(matches!(changes[1].kind, FieldChangeKind::Added));
+        assert_eq!(changes[3].segments, ["owner", "tags", "[1]"]);
+        assert_eq!(changes[3].new, Some(json!("z")));
+    }
+
+    #[test]
+    fn diff_values_reports_whole_values_when_shapes_differ() {
+        let changes = diff(pool("a", json!(["x"])), pool("a", json!(["x", "y"])));
+        assert_eq!(paths(&changes), ["owner.tags"]);
+
+        let mut retyped = pool("a", json!([]));
+        retyped["owner"]["type"] = json!("0x5a1e::pool::NewOwner");
+        let changes = diff(pool("a", json!([])), retyped);
+        assert_eq!(paths(&changes), ["owner.type"]);
+
+        let changes = diff(Value::Null, pool("a", json!([])));
+        assert_eq!(paths(&changes), [""]);
+        assert!(changes[0].segments.is_empty());
+    }
+
+    #[test]
+    fn parse_range_accepts_optional_prefixes_and_open_end() {
+        assert_eq!(parse_range("12..15").unwrap(), (12, Some(15)));
+        assert_eq!(parse_range("v12..v15").unwrap(), (12, Some(15)));
+        assert_eq!(parse_range("12..").unwrap(), (12, None));
+        assert!(parse_range("12").is_err());
+        assert!(parse_range("..15").is_err());
+        assert!(parse_range("a..b").is_err());
+    }
+
+    #[test]
+    fn diff_tree_nests_paths_and_labels_leaves_by_segment() {
+        colored::control::set_override(false);
+        let mut old = pool("m", json!(["m", "y"]));
+        old["m"] = json!("m");
+        let mut new = pool("n", json!(["m", "z"]));
+        new["m"] = json!("mm");
+
+        let changes = diff(old, new);
+        let changes: Vec<&FieldChange> = changes.iter().collect();
+        assert_eq!(
+            diff_tree(&changes, 0, ""),
+            [
+                "├─ ~ m: m → mm",
+                "└─ owner",
+                "   ├─ ~ name: m → n",
+                "   └─ tags",
+                "      └─ ~ [1]: y → z",
+            ]
+        );
+
+        let root = diff(Value::Null, json!({ "a": 1 }));
+        let root: Vec<&FieldChange> = root.iter().collect();
+        assert_eq!(diff_tree(&root, 0, ""), ["└─ ~ (root): null → {\"a\":1}"]);
+    }
+}
@@ -0,0 +1,179 @@
+//! `inspect package [id]`: the ABI of a deployed package, or of the local
+//! sources when no ID is given.
+
+use crate::abi::{self, PackageAbi};
+use crate::config::{find_project_root, ResolvedNetwork};
+use crate::error::Result;
+use crate::lock::LockFile;
+use crate::rpc::types::{AbilitySet, NormalizedFunction, NormalizedStruct};
+use crate::rpc::RpcClient;
+use crate::utils;
+use colored::Colorize;
+use serde_json::json;
+
+pub async fn execute(
+    client: &RpcClient,
+    network: &ResolvedNetwork,
+    package_id: Option<&str>,
+    format: &str,
+) -> Result<()> {
+    let (package_id, modules) = match package_id {
+        Some(id) => {
+            let spinner = utils::create_spinner("Fetching package ABI...");
+            let modules = abi::fetch(client, id).await;
+            spinner.finish_and_clear();
+            (Some(id.to_string()), modules?)
+        }
+        None => local_abi(&network.name)?,
+    };
+
+    if format == "json" {
+        // The modules keep the shape of `sui_getNormalizedMoveModulesByPackage`.
+        let json = json!({ "packageId": package_id, "modules": modules });
+        println!("{}", serde_json::to_string_pretty(&json)?);
+        return Ok(());
+    }
+
+    print_package(package_id.as_deref(), &modules);
+    Ok(())
+}
+
+/// The ABI of the local sources. Types carry the package's original ID when
+/// it is deployed on `network`, and `0x0` otherwise.
+fn local_abi(network: &str) -> Result<(Option<String>, PackageAbi)> {
+    let root = find_project_root()?;
+    let deployment = LockFile::load(&root)?.network(network).cloned();
+    let self_address = deployment
+        .as_ref()
+        .map(|d| d.original_package_id.as_str())
+        .unwrap_or("0x0");
+    let modules = abi::load_local(&root, self_address)?;
+    Ok((deployment.map(|d| d.package_id), modules))
+}
+
+fn print_package(package_id: Option<&str>, modules: &PackageAbi) {
+    println!();
+    match package_id {
+        Some(id) => println!("{} {}", "📦 Package".bold(), id.cyan()),
+        None => println!(
+            "{} {}",
+            "📦 Package".bold(),
+            "(local, not deployed)".dimmed()
+        ),
+    }
+    println!("{}", "═".repeat(60));
+
+    for (name, module) in modules {
+        println!();
+        println!("{} {}", "module".magenta(), name.bold());
+
+        for (struct_name, def) in &module.structs {
+            println!("  {}", describe_struct(struct_name, def));
+            for field in &def.fields {
+                println!("    {}: {}", field.name.cyan(), field.type_);
+            }
+        }
+
+        if !module.structs.is_empty() && !module.exposed_functions.is_empty() {
+            println!();
+        }
+        for (function_name, function) in &module.exposed_functions {
+            println!("  {}", describe_function(function_name, function));
+        }
+    }
+    println!();
+
+    let structs: usize = modules.values().map(|m| m.structs.len()).sum();
+    let functions: usize = modules.values().map(|m| m.exposed_functions.len()).sum();
+    utils::success(&format!(
+        "{} module(s), {} struct(s), {} function(s)",
+        modules.len(),
+        structs,
+        functions
+    ));
+}
+
+/// `struct Pool<phantom T0: store> has key, store`
+fn describe_struct(name: &str, def: &NormalizedStruct) -> String {
+    let params: Vec<String> = def
+        .type_parameters
+        .iter()
+        .enumerate()
+        .map(|(i, param)| {
+            let phantom = if param.is_phantom { "phantom " } else { "" };
+            format!("{}T{}{}", phantom, i, constraints(&param.constraints))
+        })
+        .collect();
+    let abilities = if def.abilities.abilities.is_empty() {
+        String::new()
+    } else {
+        format!(" has {}", ability_list(&def.abilities))
+    };
+    format!(
+        "{} {}{}{}",
+        "struct".blue(),
+        name.green().bold(),
+        type_params(&params),
+        abilities.dimmed()
+    )
+}
+
+/// `public entry fun mint<T0: drop>(&mut T0, u64): u64`
+fn describe_function(name: &str, function: &NormalizedFunction) -> String {
+    let visibility = match function.visibility.as_str() {
+        "Public" => "public ",
+        "Friend" => "public(package) ",
+        _ => "",
+    };
+    let entry = if function.is_entry { "entry " } else { "" };
+    let params: Vec<String> = function
+        .type_parameters
+        .iter()
+        .enumerate()
+        .map(|(i, abilities)| format!("T{}{}", i, constraints(abilities)))
+        .collect();
+    let parameters: Vec<String> = function.parameters.iter().map(|p| p.to_string()).collect();
+    let returns = match function.return_.len() {
+        0 => String::new(),
+        1 => format!(": {}", function.return_[0]),
+        _ => {
+            let types: Vec<String> = function.return_.iter().map(|t| t.to_string()).collect();
+            format!(": ({})", types.join(", "))
+        }
+    };
+    format!(
+        "{}{}{} {}{}({}){}",
+        visibility.blue(),
+        entry.yellow(),
+        "fun".blue(),
+        name.green().bold(),
+        type_params(&params),
+        parameters.join(", "),
+        returns
+    )
+}
+
+fn type_params(params: &[String]) -> String {
+    if params.is_empty() {
+        String::new()
+    } else {
+        format!("<{}>", params.join(", "))
+    }
+}
+
+fn constraints(abilities: &AbilitySet) -> String {
+    if abilities.abilities.is_empty() {
+        String::new()
+    } else {
+        format!(": {}", ability_list(abilities).replace(", ", " + "))
+    }
+}
+
+fn ability_list(abilities: &AbilitySet) -> String {
+    abilities
+        .abilities
+        .iter()
+        .map(|a| a.to_lowercase())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
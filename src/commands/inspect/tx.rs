@@ -0,0 +1,491 @@
+//! `inspect tx <digest>`: a transaction block's commands, effects, gas,
+//! object and balance changes, and events.
+
+use crate::abi::short_address;
+use crate::aborts::{AbortCatalog, MoveAbort};
+use crate::config::find_project_root;
+use crate::error::Result;
+use crate::rpc::types::{
+    ObjectChange, SuiTransactionBlockResponse, TransactionBlockResponseOptions,
+};
+use crate::rpc::RpcClient;
+use crate::utils;
+use colored::Colorize;
+use serde_json::{json, Value};
+
+/// Print the transaction as JSON, or as a tree for any other format (`text`
+/// included, as for the other inspect targets).
+pub async fn execute(client: &RpcClient, digest: &str, format: &str) -> Result<()> {
+    let spinner = utils::create_spinner("Fetching transaction...");
+    let response = client
+        .get_transaction_block(digest, TransactionBlockResponseOptions::full())
+        .await;
+    spinner.finish_and_clear();
+    let response = response?;
+
+    // Aborts raised by the current project's modules are decoded against its
+    // sources; outside a project, or when the sources cannot be read, they
+    // are shown as reported.
+    let abort = response
+        .effects
+        .as_ref()
+        .and_then(|e| e.status.error.as_deref())
+        .and_then(MoveAbort::parse);
+    let decoded = match (&abort, find_project_root()) {
+        (Some(abort), Ok(root)) => match AbortCatalog::load(&root) {
+            Ok(catalog) => catalog.decode(abort),
+            Err(e) => {
+                utils::warning(&format!(
+                    "Could not read the project sources to decode the abort: {}",
+                    e
+                ));
+                None
+            }
+        },
+        _ => None,
+    };
+
+    if format == "json" {
+        let mut json = serde_json::to_value(&response)?;
+        if let (Value::Object(map), Some(abort)) = (&mut json, &abort) {
+            map.insert(
+                "abort".to_string(),
+                json!({
+                    "address": abort.address,
+                    "module": abort.module,
+                    "function": abort.function,
+                    "code": abort.code,
+                    "command": abort.command,
+                    "constant": decoded.as_ref().and_then(|d| d.constant.as_ref()).map(|c| &c.name),
+                    "message": decoded.as_ref().and_then(|d| d.constant.as_ref()).and_then(|c| c.message.as_ref()),
+                    "location": decoded.as_ref().map(|d| d.describe()),
+                }),
+            );
+        }
+        println!("{}", serde_json::to_string_pretty(&json)?);
+        return Ok(());
+    }
+
+    print_summary(&response, abort.is_some());
+
+    if let Some(abort) = &abort {
+        let function = abort
+            .function
+            .as_deref()
+            .map(|f| format!("::{}", f))
+            .unwrap_or_default();
+        println!("💥 {}", "Abort".bold());
+        println!(
+            "├─ In: {}::{}{}",
+            short_address(&abort.address).dimmed(),
+            abort.module.cyan(),
+            function.cyan()
+        );
+        if let Some(command) = abort.command {
+            println!("├─ Command: {}", command);
+        }
+        match &decoded {
+            Some(decoded) => {
+                println!("├─ Code: {}", abort.code.to_string().yellow());
+                let notes: Vec<&str> = decoded
+                    .constant
+                    .as_ref()
+                    .map(|c| c.notes().collect())
+                    .unwrap_or_default();
+                let branch = |last: bool| if last { "└─" } else { "├─" };
+                println!(
+                    "{} Meaning: {}",
+                    branch(notes.is_empty()),
+                    decoded.describe().red().bold()
+                );
+                for (i, note) in notes.iter().enumerate() {
+                    println!("{} {}", branch(i + 1 == notes.len()), note.dimmed());
+                }
+            }
+            None => println!("└─ Code: {}", abort.code.to_string().yellow()),
+        }
+        println!();
+    }
+
+    print_commands(&response);
+    print_gas(&response);
+    print_object_changes(&response);
+    print_balance_changes(&response);
+    print_events(&response);
+
+    Ok(())
+}
+
+/// Summary of the transaction; a failure's error is left out when it is an
+/// abort, which is rendered on its own.
+fn print_summary(response: &SuiTransactionBlockResponse, aborted: bool) {
+    let data = response.transaction.as_ref().map(|t| &t["data"]);
+
+    println!();
+    println!("{}", "🧾 Transaction Inspector".bold());
+    println!("{}", "═".repeat(60));
+    println!();
+    println!("🔍 {}", "Transaction Details".bold());
+    println!("├─ Digest: {}", response.digest.cyan());
+    if let Some(sender) = data.and_then(|d| d["sender"].as_str()) {
+        println!("├─ Sender: {}", sender.blue());
+    }
+    if let Some(checkpoint) = &response.checkpoint {
+        println!("├─ Checkpoint: {}", checkpoint.dimmed());
+    }
+    if let Some(time) = response
+        .timestamp_ms
+        .as_deref()
+        .and_then(|ms| ms.parse().ok())
+        .and_then(chrono::DateTime::from_timestamp_millis)
+    {
+        println!("├─ Time: {}", time.to_rfc3339().dimmed());
+    }
+    match response.effects.as_ref().map(|e| &e.status) {
+        Some(status) if status.is_success() => println!("└─ Status: {}", "success".green()),
+        Some(_) if aborted => println!("└─ Status: {}", "failure".red().bold()),
+        Some(status) => println!(
+            "└─ Status: {} {}",
+            "failure".red().bold(),
+            status.error.as_deref().unwrap_or_default().red()
+        ),
+        None => println!("└─ Status: {}", "unknown".dimmed()),
+    }
+    println!();
+}
+
+fn print_commands(response: &SuiTransactionBlockResponse) {
+    let Some(kind) = response
+        .transaction
+        .as_ref()
+        .map(|t| &t["data"]["transaction"])
+        .filter(|k| !k.is_null())
+    else {
+        return;
+    };
+
+    if kind["kind"] != "ProgrammableTransaction" {
+        println!("⚙️  {}", "Kind".bold());
+        println!("└─ {}", kind["kind"].as_str().unwrap_or("unknown"));
+        println!();
+        return;
+    }
+
+    let inputs = kind["inputs"].as_array().cloned().unwrap_or_default();
+    if !inputs.is_empty() {
+        println!("📥 {}", "Inputs".bold());
+        for (i, input) in inputs.iter().enumerate() {
+            let branch = if i == inputs.len() - 1 {
+                "└─"
+            } else {
+                "├─"
+            };
+            println!(
+                "{} {} {}",
+                branch,
+                format!("Input({})", i).dimmed(),
+                describe_input(input)
+            );
+        }
+        println!();
+    }
+
+    let commands = kind["transactions"].as_array().cloned().unwrap_or_default();
+    println!("⚙️  {}", "Commands".bold());
+    for (i, command) in commands.iter().enumerate() {
+        let branch = if i == commands.len() - 1 {
+            "└─"
+        } else {
+            "├─"
+        };
+        println!(
+            "{} {} {}",
+            branch,
+            i.to_string().dimmed(),
+            describe_command(command)
+        );
+    }
+    println!();
+}
+
+fn describe_input(input: &Value) -> String {
+    match input["type"].as_str() {
+        Some("object") => {
+            let kind = match input["objectType"].as_str() {
+                Some("sharedObject") => "shared",
+                Some("receiving") => "receiving",
+                _ => "owned",
+            };
+            format!(
+                "{} object {}",
+                kind,
+                input["objectId"].as_str().unwrap_or_default().cyan()
+            )
+        }
+        Some("pure") => {
+            let value = match &input["value"] {
+                Value::String(s) => s.clone(),
+                other => other.to_string(),
+            };
+            match input["valueType"].as_str() {
+                Some(ty) => format!("{}: {}", value.green(), ty.dimmed()),
+                None => value.green().to_string(),
+            }
+        }
+        _ => input.to_string(),
+    }
+}
+
+fn describe_command(command: &Value) -> String {
+    let Some((name, body)) = command.as_object().and_then(|c| c.iter().next()) else {
+        return command.to_string();
+    };
+    let args = |value: &Value| {
+        value
+            .as_array()
+            .map(|items| items.iter().map(describe_argument).collect::<Vec<_>>())
+            .unwrap_or_default()
+            .join(", ")
+    };
+
+    match name.as_str() {
+        "MoveCall" => {
+            let type_args = body["type_arguments"]
+                .as_array()
+                .filter(|t| !t.is_empty())
+                .map(|t| {
+                    let t: Vec<&str> = t.iter().filter_map(|t| t.as_str()).collect();
+                    format!("<{}>", t.join(", "))
+                })
+                .unwrap_or_default();
+            format!(
+                "{} {}::{}::{}{}({})",
+                "MoveCall".bold(),
+                body["package"].as_str().unwrap_or_default().dimmed(),
+                body["module"].as_str().unwrap_or_default().cyan(),
+                body["function"].as_str().unwrap_or_default().cyan(),
+                type_args,
+                args(&body["arguments"])
+            )
+        }
+        "TransferObjects" => format!(
+            "{} [{}] → {}",
+            "TransferObjects".bold(),
+            args(&body[0]),
+            describe_argument(&body[1])
+        ),
+        "SplitCoins" => format!(
+            "{} {} into [{}]",
+            "SplitCoins".bold(),
+            describe_argument(&body[0]),
+            args(&body[1])
+        ),
+        "MergeCoins" => format!(
+            "{} [{}] into {}",
+            "MergeCoins".bold(),
+            args(&body[1]),
+            describe_argument(&body[0])
+        ),
+        "MakeMoveVec" => format!(
+            "{}<{}> [{}]",
+            "MakeMoveVec".bold(),
+            body[0].as_str().unwrap_or("_"),
+            args(&body[1])
+        ),
+        "Publish" => format!(
+            "{} ({} dependencies)",
+            "Publish".bold(),
+            body.as_array().map_or(0, |d| d.len())
+        ),
+        "Upgrade" => format!(
+            "{} {} with ticket {}",
+            "Upgrade".bold(),
+            body[2].as_str().unwrap_or_default(),
+            describe_argument(&body[3])
+        ),
+        other => format!("{} {}", other.bold(), body),
+    }
+}
+
+/// `GasCoin`, `Input(0)`, `Result(1)` or `NestedResult(1, 0)`.
+fn describe_argument(argument: &Value) -> String {
+    match argument {
+        Value::String(s) => s.clone(),
+        Value::Object(map) => match map.iter().next() {
+            Some((kind, Value::Array(items))) => {
+                let items: Vec<String> = items.iter().map(|i| i.to_string()).collect();
+                format!("{}({})", kind, items.join(", "))
+            }
+            Some((kind, value)) => format!("{}({})", kind, value),
+            None => argument.to_string(),
+        },
+        other => other.to_string(),
+    }
+}
+
+fn print_gas(response: &SuiTransactionBlockResponse) {
+    let Some(effects) = &response.effects else {
+        return;
+    };
+    let cost = &effects.gas_used;
+    let gas_data = response.transaction.as_ref().map(|t| &t["data"]["gasData"]);
+
+    println!("⛽ {}", "Gas".bold());
+    println!(
+        "├─ Computation: {} MIST",
+        cost.computation().to_string().blue()
+    );
+    println!("├─ Storage: {} MIST", cost.storage().to_string().green());
+    println!(
+        "├─ Storage rebate: {} MIST",
+        cost.rebate().to_string().green()
+    );
+    if let Some(gas_data) = gas_data {
+        if let Some(budget) = gas_data["budget"].as_str() {
+            println!("├─ Budget: {} MIST", budget);
+        }
+        if let Some(price) = gas_data["price"].as_str() {
+            println!("├─ Price: {} MIST", price);
+        }
+    }
+    println!(
+        "└─ Net cost: {} MIST",
+        cost.net().to_string().yellow().bold()
+    );
+    println!();
+}
+
+fn print_object_changes(response: &SuiTransactionBlockResponse) {
+    let changes = response.object_changes.as_deref().unwrap_or_default();
+    if changes.is_empty() {
+        return;
+    }
+
+    println!("📦 {}", "Object Changes".bold());
+    for (i, change) in changes.iter().enumerate() {
+        let branch = if i == changes.len() - 1 {
+            "└─"
+        } else {
+            "├─"
+        };
+        let line = match change {
+            ObjectChange::Published {
+                package_id,
+                modules,
+                ..
+            } => format!(
+                "{} {} ({})",
+                "published".green(),
+                package_id.cyan(),
+                modules.join(", ")
+            ),
+            ObjectChange::Created {
+                object_id,
+                object_type,
+                owner,
+                ..
+            } => format!(
+                "{} {} {} → {}",
+                "created".green(),
+                object_id.cyan(),
+                object_type,
+                owner.to_string().dimmed()
+            ),
+            ObjectChange::Mutated {
+                object_id,
+                object_type,
+                owner,
+                ..
+            } => format!(
+                "{} {} {} → {}",
+                "mutated".yellow(),
+                object_id.cyan(),
+                object_type,
+                owner.to_string().dimmed()
+            ),
+            ObjectChange::Transferred {
+                object_id,
+                object_type,
+                recipient,
+                ..
+            } => format!(
+                "{} {} {} → {}",
+                "transferred".blue(),
+                object_id.cyan(),
+                object_type,
+                recipient.to_string().dimmed()
+            ),
+            ObjectChange::Deleted {
+                object_id,
+                object_type,
+                ..
+            } => format!("{} {} {}", "deleted".red(), object_id.cyan(), object_type),
+            ObjectChange::Wrapped {
+                object_id,
+                object_type,
+                ..
+            } => format!(
+                "{} {} {}",
+                "wrapped".dimmed(),
+                object_id.cyan(),
+                object_type
+            ),
+        };
+        println!("{} {}", branch, line);
+    }
+    println!();
+}
+
+fn print_balance_changes(response: &SuiTransactionBlockResponse) {
+    let changes = response.balance_changes.as_deref().unwrap_or_default();
+    if changes.is_empty() {
+        return;
+    }
+
+    println!("💰 {}", "Balance Changes".bold());
+    for (i, change) in changes.iter().enumerate() {
+        let branch = if i == changes.len() - 1 {
+            "└─"
+        } else {
+            "├─"
+        };
+        let amount = if change.amount.starts_with('-') {
+            change.amount.red()
+        } else {
+            format!("+{}", change.amount).green()
+        };
+        println!(
+            "{} {} {} {}",
+            branch,
+            change.owner.to_string().blue(),
+            amount,
+            change.coin_type.dimmed()
+        );
+    }
+    println!();
+}
+
+fn print_events(response: &SuiTransactionBlockResponse) {
+    let events = response.events.as_deref().unwrap_or_default();
+    if events.is_empty() {
+        return;
+    }
+
+    println!("📣 {}", "Events".bold());
+    for (i, event) in events.iter().enumerate() {
+        let is_last = i == events.len() - 1;
+        let branch = if is_last { "└─" } else { "├─" };
+        let prefix = if is_last { "   " } else { "│  " };
+        println!("{} {}", branch, event.type_.green());
+        if let Some(fields) = event.parsed_json.as_object() {
+            for (key, value) in fields {
+                let value = match value {
+                    Value::String(s) => s.clone(),
+                    other => other.to_string(),
+                };
+                println!("{}  {}: {}", prefix, key.cyan(), value);
+            }
+        }
+    }
+    println!();
+}
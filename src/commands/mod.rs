@@ -1,15 +1,20 @@
 pub mod build;
+pub mod compat;
 pub mod coverage;
 pub mod dashboard;
 pub mod deploy;
+pub mod deployments;
+pub mod events;
 pub mod gas;
 pub mod generate;
 pub mod init;
 pub mod inspect;
 pub mod install;
 pub mod node;
+pub mod objects;
 pub mod profile;
 pub mod scan;
 pub mod test;
+pub mod upgrade;
 pub mod verify;
 pub mod watch;
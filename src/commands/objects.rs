@@ -0,0 +1,381 @@
+use crate::abi::{same_address, short_address};
+use crate::config::resolve_network;
+use crate::error::{Result, SuiForgeError};
+use crate::rpc::types::{Balance, ObjectDataOptions, SuiObjectData};
+use crate::rpc::{collect_pages, RpcClient};
+use crate::sui::SuiCli;
+use crate::utils;
+use colored::Colorize;
+use regex::Regex;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+
+/// Objects requested per `suix_getOwnedObjects` page.
+const PAGE_SIZE: usize = 50;
+
+const SUI_COIN_TYPE: &str = "0x2::sui::SUI";
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Inventory {
+    address: String,
+    network: String,
+    balances: Vec<Balance>,
+    objects: Vec<OwnedObject>,
+    /// Set when more than `--limit` objects exist and the rest were left out.
+    truncated: bool,
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct OwnedObject {
+    object_id: String,
+    #[serde(rename = "type")]
+    object_type: String,
+    version: String,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn execute(
+    address: Option<String>,
+    network: Option<String>,
+    struct_type: Option<String>,
+    package: Option<String>,
+    group: bool,
+    limit: usize,
+    format: String,
+) -> Result<()> {
+    if limit == 0 {
+        return Err(SuiForgeError::Custom(
+            "--limit must be at least 1".to_string(),
+        ));
+    }
+
+    let network = resolve_network(network.as_deref())?;
+    let client = RpcClient::for_network(&network);
+    let address = match address {
+        Some(address) => address,
+        None => SuiCli::get_active_address()?,
+    };
+
+    let filters: Vec<Value> = [
+        struct_type.as_ref().map(|t| json!({ "StructType": t })),
+        package.as_ref().map(|p| json!({ "Package": p })),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+    let filter = match filters.len() {
+        0 => None,
+        1 => filters.into_iter().next(),
+        _ => Some(json!({ "MatchAll": filters })),
+    };
+
+    let spinner = utils::create_spinner("Fetching owned objects...");
+    let result = fetch_inventory(&client, &address, filter, limit).await;
+    spinner.finish_and_clear();
+    let (objects, mut balances, truncated) = result?;
+
+    // Balances are per coin type rather than per object, so only the package
+    // filter applies to them; a struct type filter leaves them out.
+    if struct_type.is_some() {
+        balances.clear();
+    } else if let Some(package) = &package {
+        balances.retain(|b| {
+            b.coin_type
+                .split("::")
+                .next()
+                .is_some_and(|a| same_address(a, package))
+        });
+    }
+
+    let inventory = Inventory {
+        address,
+        network: network.name.clone(),
+        balances,
+        truncated,
+        objects,
+    };
+
+    match format.as_str() {
+        "json" => println!(
+            "{}",
+            serde_json::to_string_pretty(&structured(&inventory, group)?)?
+        ),
+        "yaml" => print!(
+            "{}",
+            serde_yaml::to_string(&structured(&inventory, group)?)?
+        ),
+        "tree" => print_tree(&inventory),
+        _ => print_text(&inventory, group),
+    }
+    Ok(())
+}
+
+async fn fetch_inventory(
+    client: &RpcClient,
+    address: &str,
+    filter: Option<Value>,
+    limit: usize,
+) -> Result<(Vec<OwnedObject>, Vec<Balance>, bool)> {
+    let options = ObjectDataOptions {
+        show_type: true,
+        ..Default::default()
+    };
+    // One extra object tells whether the listing was cut short
+    let mut responses = collect_pages(
+        |cursor| {
+            client.get_owned_objects(
+                address,
+                filter.clone(),
+                options.clone(),
+                cursor,
+                Some(PAGE_SIZE),
+            )
+        },
+        Some(limit + 1),
+    )
+    .await?;
+    let truncated = responses.len() > limit;
+    responses.truncate(limit);
+
+    let objects = responses
+        .into_iter()
+        .filter_map(|r| r.data)
+        .map(|data: SuiObjectData| OwnedObject {
+            object_type: data.type_.unwrap_or_else(|| "unknown".to_string()),
+            object_id: data.object_id,
+            version: data.version,
+        })
+        .collect();
+
+    let mut balances = client.get_all_balances(address).await?;
+    balances.sort_by(|a, b| a.coin_type.cmp(&b.coin_type));
+    Ok((objects, balances, truncated))
+}
+
+/// Objects grouped by type, in type order.
+fn by_type(objects: &[OwnedObject]) -> BTreeMap<&str, Vec<&OwnedObject>> {
+    let mut groups: BTreeMap<&str, Vec<&OwnedObject>> = BTreeMap::new();
+    for object in objects {
+        groups.entry(&object.object_type).or_default().push(object);
+    }
+    groups
+}
+
+/// The inventory as JSON/YAML, with objects nested under their type when
+/// grouping.
+fn structured(inventory: &Inventory, group: bool) -> Result<Value> {
+    let mut value = serde_json::to_value(inventory)?;
+    if group {
+        let groups: serde_json::Map<String, Value> = by_type(&inventory.objects)
+            .into_iter()
+            .map(|(ty, objects)| {
+                let objects: Vec<Value> = objects
+                    .iter()
+                    .map(|o| json!({ "objectId": o.object_id, "version": o.version }))
+                    .collect();
+                (
+                    ty.to_string(),
+                    json!({ "count": objects.len(), "objects": objects }),
+                )
+            })
+            .collect();
+        value["objects"] = Value::Object(groups);
+    }
+    Ok(value)
+}
+
+fn print_text(inventory: &Inventory, group: bool) {
+    utils::info(&format!(
+        "Objects owned by {} on {}",
+        inventory.address.cyan(),
+        inventory.network.yellow()
+    ));
+    println!();
+    print_balances(&inventory.balances);
+
+    if inventory.objects.is_empty() {
+        utils::info("No objects found");
+        return;
+    }
+
+    if group {
+        println!("{}", "Objects by type:".bold());
+        for (ty, objects) in by_type(&inventory.objects) {
+            println!();
+            println!(
+                "  {} {}",
+                short_type(ty).green(),
+                format!("({})", objects.len()).dimmed()
+            );
+            for object in objects {
+                println!("    {}", object.object_id);
+            }
+        }
+    } else {
+        println!("{}", "Objects:".bold());
+        println!("  {:<68} {:>10}  Type", "Object ID", "Version");
+        println!("  {}", "─".repeat(110));
+        for object in &inventory.objects {
+            println!(
+                "  {:<68} {:>10}  {}",
+                object.object_id,
+                object.version,
+                short_type(&object.object_type).green()
+            );
+        }
+    }
+    println!();
+    print_footer(inventory);
+}
+
+/// Objects as package → module → type.
+fn print_tree(inventory: &Inventory) {
+    println!();
+    println!("{} {}", "📦 Inventory of".bold(), inventory.address.cyan());
+    println!("{}", "═".repeat(60));
+    println!();
+    print_balances(&inventory.balances);
+
+    let mut packages: BTreeMap<String, BTreeMap<&str, BTreeMap<&str, Vec<&OwnedObject>>>> =
+        BTreeMap::new();
+    for object in &inventory.objects {
+        let mut parts = object.object_type.splitn(3, "::");
+        let (package, module, name) = match (parts.next(), parts.next(), parts.next()) {
+            (Some(p), Some(m), Some(n)) => (short_address(p), m, n),
+            _ => (object.object_type.clone(), "", ""),
+        };
+        packages
+            .entry(package)
+            .or_default()
+            .entry(module)
+            .or_default()
+            .entry(name)
+            .or_default()
+            .push(object);
+    }
+
+    let count = packages.len();
+    for (i, (package, modules)) in packages.iter().enumerate() {
+        let last_package = i == count - 1;
+        println!("{} {}", branch(last_package), package.blue());
+        let indent = continuation(last_package);
+        for (j, (module, types)) in modules.iter().enumerate() {
+            let last_module = j == modules.len() - 1;
+            println!("{}{} {}", indent, branch(last_module), module.cyan());
+            let indent = format!("{}{}", indent, continuation(last_module));
+            for (k, (name, objects)) in types.iter().enumerate() {
+                let last_type = k == types.len() - 1;
+                println!(
+                    "{}{} {} {}",
+                    indent,
+                    branch(last_type),
+                    short_type(name).green(),
+                    format!("({})", objects.len()).dimmed()
+                );
+                let indent = format!("{}{}", indent, continuation(last_type));
+                for (l, object) in objects.iter().enumerate() {
+                    println!(
+                        "{}{} {} {}",
+                        indent,
+                        branch(l == objects.len() - 1),
+                        object.object_id,
+                        format!("v{}", object.version).dimmed()
+                    );
+                }
+            }
+        }
+    }
+    println!();
+    print_footer(inventory);
+}
+
+fn print_balances(balances: &[Balance]) {
+    if balances.is_empty() {
+        return;
+    }
+    println!("{}", "Balances:".bold());
+    for balance in balances {
+        let amount = if balance.coin_type == SUI_COIN_TYPE {
+            format!(
+                "{} ({})",
+                balance.total_balance,
+                format_sui(&balance.total_balance)
+            )
+        } else {
+            balance.total_balance.clone()
+        };
+        println!(
+            "  {:<50} {} {}",
+            short_type(&balance.coin_type).green(),
+            amount.yellow(),
+            format!(
+                "in {} coin{}",
+                balance.coin_object_count,
+                if balance.coin_object_count == 1 {
+                    ""
+                } else {
+                    "s"
+                }
+            )
+            .dimmed()
+        );
+    }
+    println!();
+}
+
+fn print_footer(inventory: &Inventory) {
+    let types = by_type(&inventory.objects).len();
+    utils::success(&format!(
+        "{} object(s) of {} type(s)",
+        inventory.objects.len(),
+        types
+    ));
+    if inventory.truncated {
+        utils::warning("Listing stopped at --limit; there are more objects");
+    }
+}
+
+fn branch(last: bool) -> &'static str {
+    if last {
+        "└─"
+    } else {
+        "├─"
+    }
+}
+
+fn continuation(last: bool) -> &'static str {
+    if last {
+        "   "
+    } else {
+        "│  "
+    }
+}
+
+/// `type` with every full-length address shortened, e.g.
+/// `0x2::coin::Coin<0x2::sui::SUI>`.
+fn short_type(ty: &str) -> String {
+    static ADDRESS: OnceLock<Regex> = OnceLock::new();
+    let address = ADDRESS.get_or_init(|| Regex::new(r"0x[0-9a-fA-F]{64}").expect("valid pattern"));
+    address
+        .replace_all(ty, |c: &regex::Captures| short_address(&c[0]))
+        .to_string()
+}
+
+/// MIST as SUI, e.g. `1.5 SUI`.
+fn format_sui(mist: &str) -> String {
+    let Ok(mist) = mist.parse::<u128>() else {
+        return String::new();
+    };
+    let whole = mist / 1_000_000_000;
+    let fraction = format!("{:09}", mist % 1_000_000_000);
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.is_empty() {
+        format!("{} SUI", whole)
+    } else {
+        format!("{}.{} SUI", whole, fraction)
+    }
+}
@@ -26,7 +26,12 @@ impl ProfileConfig {
         home.join(".suiforge").join("profiles.json")
     }
 
-    fn load() -> Result<Self> {
+    /// Whether the user has saved profiles, and with them an active one.
+    pub fn is_saved() -> bool {
+        Self::config_path().exists()
+    }
+
+    pub fn load() -> Result<Self> {
         let path = Self::config_path();
         if !path.exists() {
             return Ok(Self::default());
@@ -1,12 +1,11 @@
 use crate::error::Result;
+use crate::move_syntax::{Function, Module, Package, Type, Visibility};
 use crate::utils;
 use colored::Colorize;
-use regex::Regex;
-use std::fs;
 use std::path::Path;
 
-#[derive(Debug)]
-enum Severity {
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
     Critical,
     High,
     Medium,
@@ -15,6 +14,28 @@ enum Severity {
 }
 
 impl Severity {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "critical" => Some(Severity::Critical),
+            "high" => Some(Severity::High),
+            "medium" => Some(Severity::Medium),
+            "low" => Some(Severity::Low),
+            "info" => Some(Severity::Info),
+            _ => None,
+        }
+    }
+
+    /// Higher is more severe.
+    pub fn rank(&self) -> u8 {
+        match self {
+            Severity::Critical => 4,
+            Severity::High => 3,
+            Severity::Medium => 2,
+            Severity::Low => 1,
+            Severity::Info => 0,
+        }
+    }
+
     fn color(&self) -> colored::Color {
         match self {
             Severity::Critical => colored::Color::Red,
@@ -25,7 +46,7 @@ impl Severity {
         }
     }
 
-    fn as_str(&self) -> &str {
+    pub fn as_str(&self) -> &str {
         match self {
             Severity::Critical => "CRITICAL",
             Severity::High => "HIGH",
@@ -37,12 +58,12 @@ impl Severity {
 }
 
 #[derive(Debug)]
-struct SecurityIssue {
-    severity: Severity,
-    title: String,
-    description: String,
-    location: String,
-    recommendation: String,
+pub struct SecurityIssue {
+    pub severity: Severity,
+    pub title: String,
+    pub description: String,
+    pub location: String,
+    pub recommendation: String,
 }
 
 pub async fn execute(level: String, format: String) -> Result<()> {
@@ -51,7 +72,7 @@ pub async fn execute(level: String, format: String) -> Result<()> {
     let spinner = utils::create_spinner("Analyzing Move code...");
 
     // Scan source files
-    let issues = scan_sources("sources", &level)?;
+    let issues = scan_sources(Path::new("sources"), &level)?;
 
     spinner.finish_with_message(format!("Found {} potential issues", issues.len()));
 
@@ -64,145 +85,139 @@ pub async fn execute(level: String, format: String) -> Result<()> {
     Ok(())
 }
 
-fn scan_sources(source_dir: &str, level: &str) -> Result<Vec<SecurityIssue>> {
+pub fn scan_sources(source_dir: &Path, level: &str) -> Result<Vec<SecurityIssue>> {
     let mut issues = Vec::new();
-    let path = Path::new(source_dir);
+    let package = Package::load(source_dir)?;
 
-    if !path.exists() {
-        return Ok(issues);
+    for error in &package.errors {
+        utils::warning(&format!("Skipping file that could not be parsed: {}", error));
     }
 
-    for entry in walkdir::WalkDir::new(path)
-        .into_iter()
-        .filter_map(|e| e.ok())
-        .filter(|e| e.path().extension().map_or(false, |ext| ext == "move"))
-    {
-        let content = fs::read_to_string(entry.path())?;
-        let file_name = entry.path().display().to_string();
-        
-        // Scan for security issues
-        scan_file_for_issues(&content, &file_name, level, &mut issues)?;
+    for (module, function) in package.functions() {
+        if function.is_test() || function.is_test_only() {
+            continue;
+        }
+        scan_function(module, function, level, &mut issues);
     }
 
     Ok(issues)
 }
 
-fn scan_file_for_issues(
-    content: &str,
-    file_name: &str,
-    level: &str,
-    issues: &mut Vec<SecurityIssue>,
-) -> Result<()> {
-    let lines: Vec<&str> = content.lines().collect();
+fn scan_function(module: &Module, function: &Function, level: &str, issues: &mut Vec<SecurityIssue>) {
+    let body = match &function.body {
+        Some(body) => body,
+        None => return,
+    };
+    let location = |line: usize| format!("{}:{}", module.path.display(), line);
+
+    // Capability-gated functions are access-controlled by construction.
+    let has_capability = function.params.iter().any(|p| {
+        p.ty.base_name()
+            .map(|name| name.ends_with("Cap") || name == "Publisher")
+            .unwrap_or(false)
+    });
 
     // Check for unchecked transfers
-    for (i, line) in lines.iter().enumerate() {
-        if line.contains("transfer::") && !line.contains("assert!") {
-            // Look back a few lines for ownership check
-            let has_check = lines
-                .iter()
-                .skip(i.saturating_sub(5))
-                .take(5)
-                .any(|l| l.contains("assert!") && (l.contains("owner") || l.contains("sender")));
-
-            if !has_check {
-                issues.push(SecurityIssue {
-                    severity: Severity::High,
-                    title: "Unchecked Transfer".to_string(),
-                    description: "Transfer operation without ownership verification".to_string(),
-                    location: format!("{}:{}", file_name, i + 1),
-                    recommendation: "Add ownership check before transfer: assert!(owner == sender)".to_string(),
-                });
-            }
+    for call in &body.calls {
+        let is_transfer = call.module.last().map(|m| m == "transfer").unwrap_or(false)
+            && (call.function == "transfer" || call.function == "public_transfer");
+        if !is_transfer {
+            continue;
         }
 
-        // Check for public functions without access control
-        if line.contains("public fun") && !line.contains("entry") {
-            let function_body_start = i;
-            let has_access_control = lines
+        let to_sender = call.args.get(1).map(|r| r.contains("sender")).unwrap_or(false);
+        // An object passed in by value can only come from its current owner.
+        let owned_by_caller = call.args.first().is_some_and(|arg| {
+            function
+                .params
                 .iter()
-                .skip(function_body_start)
-                .take(10)
-                .any(|l| l.contains("assert!") || l.contains("require") || l.contains("capability"));
-
-            if !has_access_control && level != "basic" {
-                issues.push(SecurityIssue {
-                    severity: Severity::Medium,
-                    title: "Missing Access Control".to_string(),
-                    description: "Public function without role-based access control".to_string(),
-                    location: format!("{}:{}", file_name, i + 1),
-                    recommendation: "Implement admin-only modifier or capability pattern".to_string(),
-                });
-            }
+                .any(|p| p.name == *arg && !matches!(p.ty, Type::Ref { .. }))
+        });
+        let has_check = body.asserts.iter().any(|a| {
+            a.line <= call.line && (a.condition.contains("owner") || a.condition.contains("sender"))
+        });
+
+        if !to_sender && !has_check && !has_capability && !owned_by_caller {
+            issues.push(SecurityIssue {
+                severity: Severity::High,
+                title: "Unchecked Transfer".to_string(),
+                description: format!(
+                    "`{}` transfers to an arbitrary recipient without ownership verification",
+                    function.name
+                ),
+                location: location(call.line),
+                recommendation: "Add ownership check before transfer: assert!(owner == sender)".to_string(),
+            });
         }
+    }
 
-        // Check for potential reentrancy
-        if line.contains("transfer::") || line.contains("coin::") {
-            // Check if state is updated after external call
-            let has_state_update_before = lines
-                .iter()
-                .skip(i.saturating_sub(3))
-                .take(3)
-                .any(|l| l.contains("=") && !l.contains("=="));
+    // Check for public functions without access control
+    let callable = function.visibility == Visibility::Public || function.is_entry;
+    let mutates_input = function
+        .params
+        .iter()
+        .any(|p| p.ty.is_mut_ref() && p.ty.base_name() != Some("TxContext"));
+    if callable
+        && mutates_input
+        && !has_capability
+        && body.asserts.is_empty()
+        && body.aborts.is_empty()
+        && level != "basic"
+    {
+        issues.push(SecurityIssue {
+            severity: Severity::Medium,
+            title: "Missing Access Control".to_string(),
+            description: format!(
+                "`{}` mutates its arguments without a capability or assertion",
+                function.name
+            ),
+            location: location(function.line),
+            recommendation: "Implement admin-only modifier or capability pattern".to_string(),
+        });
+    }
 
-            if !has_state_update_before {
+    // Check for state updated after value leaves the function
+    for call in &body.calls {
+        let external = call
+            .module
+            .last()
+            .map(|m| m == "transfer" || m == "coin")
+            .unwrap_or(false);
+        if external && body.field_writes.iter().any(|line| *line > call.line) {
+            issues.push(SecurityIssue {
+                severity: Severity::Critical,
+                title: "Potential Reentrancy Risk".to_string(),
+                description: "External call before state update".to_string(),
+                location: location(call.line),
+                recommendation: "Update state before making external calls (checks-effects-interactions)".to_string(),
+            });
+        }
+    }
+
+    // Strict mode checks
+    if level == "strict" {
+        for binding in &body.lets {
+            for var_name in &binding.unused {
                 issues.push(SecurityIssue {
-                    severity: Severity::Critical,
-                    title: "Potential Reentrancy Risk".to_string(),
-                    description: "External call before state update".to_string(),
-                    location: format!("{}:{}", file_name, i + 1),
-                    recommendation: "Update state before making external calls (checks-effects-interactions)".to_string(),
+                    severity: Severity::Low,
+                    title: "Unused Variable".to_string(),
+                    description: format!("Variable '{}' declared but never used", var_name),
+                    location: location(binding.line),
+                    recommendation: "Remove unused variable or prefix with underscore".to_string(),
                 });
             }
         }
 
-        // Strict mode checks
-        if level == "strict" {
-            // Check for unused variables
-            if line.contains("let ") && !line.contains("mut") && !line.contains("_") {
-                if let Some(var_name) = extract_variable_name(line) {
-                    let is_used = lines
-                        .iter()
-                        .skip(i + 1)
-                        .take(20)
-                        .any(|l| l.contains(&var_name));
-
-                    if !is_used {
-                        issues.push(SecurityIssue {
-                            severity: Severity::Low,
-                            title: "Unused Variable".to_string(),
-                            description: format!("Variable '{}' declared but never used", var_name),
-                            location: format!("{}:{}", file_name, i + 1),
-                            recommendation: "Remove unused variable or prefix with underscore".to_string(),
-                        });
-                    }
-                }
-            }
-
-            // Check for missing documentation
-            if line.contains("public fun") {
-                let has_doc = i > 0 && lines[i - 1].trim().starts_with("///");
-                if !has_doc {
-                    issues.push(SecurityIssue {
-                        severity: Severity::Info,
-                        title: "Missing Documentation".to_string(),
-                        description: "Public function lacks documentation comment".to_string(),
-                        location: format!("{}:{}", file_name, i + 1),
-                        recommendation: "Add /// documentation comment explaining function purpose".to_string(),
-                    });
-                }
-            }
+        if function.visibility == Visibility::Public && function.doc.is_none() {
+            issues.push(SecurityIssue {
+                severity: Severity::Info,
+                title: "Missing Documentation".to_string(),
+                description: format!("Public function `{}` lacks documentation comment", function.name),
+                location: location(function.line),
+                recommendation: "Add /// documentation comment explaining function purpose".to_string(),
+            });
         }
     }
-
-    Ok(())
-}
-
-fn extract_variable_name(line: &str) -> Option<String> {
-    let re = Regex::new(r"let\s+(\w+)").ok()?;
-    re.captures(line)
-        .and_then(|cap| cap.get(1))
-        .map(|m| m.as_str().to_string())
 }
 
 fn print_text_report(issues: &[SecurityIssue]) -> Result<()> {
@@ -1,10 +1,11 @@
+use crate::aborts;
 use crate::config::find_project_root;
 use crate::error::{Result, SuiForgeError};
 use crate::sui::SuiCli;
 use crate::utils;
 
 pub async fn execute(filter: Option<String>, coverage: bool) -> Result<()> {
-    let _root = find_project_root()?;
+    let root = find_project_root()?;
 
     if coverage {
         utils::warning("Coverage mode not yet implemented. Running tests without coverage...");
@@ -14,7 +15,7 @@ pub async fn execute(filter: Option<String>, coverage: bool) -> Result<()> {
 
     let spinner = utils::create_spinner("Executing tests...");
 
-    let output = SuiCli::test(filter)?;
+    let output = SuiCli::test(&root, filter)?;
 
     if output.status.success() {
         spinner.finish_with_message("Tests completed");
@@ -26,9 +27,15 @@ pub async fn execute(filter: Option<String>, coverage: bool) -> Result<()> {
         }
     } else {
         spinner.finish_with_message("Tests failed");
+        let stdout = String::from_utf8_lossy(&output.stdout);
         let stderr = String::from_utf8_lossy(&output.stderr);
         utils::error("Tests failed:");
+        // Test failures, and the aborts behind them, are reported on stdout.
+        if !stdout.is_empty() {
+            println!("\n{}", stdout);
+        }
         println!("\n{}", stderr);
+        aborts::explain(&format!("{}\n{}", stdout, stderr), false);
         return Err(SuiForgeError::TestFailed(stderr.to_string()));
     }
 
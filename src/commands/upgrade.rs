@@ -0,0 +1,346 @@
+use crate::commands::compat;
+use crate::commands::deploy::{auto_gas_budget, executed_transaction, parse_publish, simulate};
+use crate::config::{find_project_root, load_config, resolve_network, GasBudget};
+use crate::error::{Result, SuiForgeError};
+use crate::lock::{DeploymentKind, DeploymentRecord, LockFile};
+use crate::manifest::Manifest;
+use crate::rpc::types::{ObjectDataOptions, Owner, SuiParsedData};
+use crate::rpc::RpcClient;
+use crate::safety::{self, UpgradeTarget};
+use crate::sui::SuiCli;
+use crate::utils;
+use chrono::Utc;
+use colored::Colorize;
+use std::fmt;
+
+/// Upgrade policy stored in a `sui::package::UpgradeCap`. Policies only get
+/// stricter: each one permits a subset of the changes allowed by the one
+/// before it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum UpgradePolicy {
+    /// Any change that keeps public signatures and struct layouts intact.
+    Compatible,
+    /// Only new functions and types may be added.
+    Additive,
+    /// Only dependencies may change.
+    DepOnly,
+}
+
+impl UpgradePolicy {
+    pub fn from_u8(policy: u8) -> Option<Self> {
+        match policy {
+            0 => Some(UpgradePolicy::Compatible),
+            128 => Some(UpgradePolicy::Additive),
+            192 => Some(UpgradePolicy::DepOnly),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for UpgradePolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UpgradePolicy::Compatible => write!(f, "compatible"),
+            UpgradePolicy::Additive => write!(f, "additive"),
+            UpgradePolicy::DepOnly => write!(f, "dep_only"),
+        }
+    }
+}
+
+/// On-chain state of an UpgradeCap.
+pub struct UpgradeCap {
+    pub package: String,
+    pub version: u64,
+    pub policy: UpgradePolicy,
+    pub owner: Option<Owner>,
+}
+
+/// Fetch an UpgradeCap and decode its fields.
+pub async fn fetch_upgrade_cap(client: &RpcClient, cap_id: &str) -> Result<UpgradeCap> {
+    let object = client
+        .get_object(cap_id, ObjectDataOptions::full_content())
+        .await?;
+
+    let fields = match &object.content {
+        Some(SuiParsedData::MoveObject { type_, fields, .. })
+            if type_.ends_with("::package::UpgradeCap") =>
+        {
+            fields
+        }
+        _ => {
+            return Err(SuiForgeError::Custom(format!(
+                "{} is not an UpgradeCap",
+                cap_id
+            )))
+        }
+    };
+
+    // u8/u64 fields arrive as numbers or strings depending on the node
+    let number = |name: &str| {
+        let value = &fields[name];
+        value
+            .as_u64()
+            .or_else(|| value.as_str().and_then(|s| s.parse().ok()))
+            .ok_or_else(|| {
+                SuiForgeError::Custom(format!(
+                    "UpgradeCap {} has a missing or malformed {} field: {}",
+                    cap_id, name, value
+                ))
+            })
+    };
+
+    let policy = number("policy")?;
+    let policy = u8::try_from(policy)
+        .ok()
+        .and_then(UpgradePolicy::from_u8)
+        .ok_or_else(|| {
+            SuiForgeError::Custom(format!(
+                "UpgradeCap {} has unknown upgrade policy {}",
+                cap_id, policy
+            ))
+        })?;
+
+    let package = fields["package"].as_str().ok_or_else(|| {
+        SuiForgeError::Custom(format!("UpgradeCap {} has no package field", cap_id))
+    })?;
+
+    Ok(UpgradeCap {
+        package: package.to_string(),
+        version: number("version")?,
+        policy,
+        owner: object.owner,
+    })
+}
+
+pub async fn execute(network: Option<String>, gas_budget: Option<GasBudget>) -> Result<()> {
+    let root = find_project_root()?;
+    let config = load_config()?;
+    let network = resolve_network(network.as_deref())?;
+
+    let mut lock = LockFile::load(&root)?;
+    let deployment = lock.network(&network.name).cloned().ok_or_else(|| {
+        SuiForgeError::Custom(format!(
+            "No deployment recorded for {}. Run 'suiforge deploy {}' first.",
+            network.name, network.name
+        ))
+    })?;
+    let cap_id = deployment.upgrade_cap_id.clone().ok_or_else(|| {
+        SuiForgeError::Custom(format!(
+            "The {} deployment has no UpgradeCap recorded; the package cannot be upgraded",
+            network.name
+        ))
+    })?;
+
+    utils::info(&format!(
+        "Upgrading {} on {} ({})...",
+        deployment.package_id.green(),
+        network.name.yellow().bold(),
+        network.rpc.dimmed()
+    ));
+
+    let spinner = utils::create_spinner("Getting active address...");
+    let address = SuiCli::get_active_address()?;
+    spinner.finish_with_message(format!("Active address: {}", address));
+
+    // Check the UpgradeCap before building anything
+    let spinner = utils::create_spinner("Checking UpgradeCap...");
+    let client = RpcClient::for_network(&network);
+    let cap = match fetch_upgrade_cap(&client, &cap_id).await {
+        Ok(cap) => cap,
+        Err(e) => {
+            spinner.finish_with_message("UpgradeCap check failed");
+            return Err(e);
+        }
+    };
+    spinner.finish_with_message(format!(
+        "UpgradeCap {} (policy: {}, version {})",
+        cap_id,
+        cap.policy.to_string().cyan(),
+        cap.version
+    ));
+
+    if cap.package != deployment.package_id {
+        return Err(SuiForgeError::Custom(format!(
+            "UpgradeCap {} controls package {}, but the lock file records {}. \
+             The lock file is out of date.",
+            cap_id, cap.package, deployment.package_id
+        )));
+    }
+    match &cap.owner {
+        Some(Owner::AddressOwner(owner)) if *owner == address => {}
+        Some(owner) => {
+            return Err(SuiForgeError::Custom(format!(
+                "UpgradeCap {} is owned by {}, not the active address {}",
+                cap_id, owner, address
+            )))
+        }
+        None => {}
+    }
+    // The chain rejects anything outside a stricter policy, so refuse before
+    // building and paying for a transaction that cannot succeed.
+    if cap.policy != UpgradePolicy::Compatible {
+        let changes = compat::check(&client, &root, &deployment.package_id, cap.policy).await?;
+        let breaking: Vec<_> = changes.iter().filter(|c| c.breaking).collect();
+        if !breaking.is_empty() {
+            for change in &breaking {
+                println!("    {}  {}", change.item, change.description);
+            }
+            return Err(SuiForgeError::Custom(format!(
+                "{} change(s) are not allowed by the UpgradeCap's {} policy",
+                breaking.len(),
+                cap.policy
+            )));
+        }
+        utils::warning(&format!(
+            "UpgradeCap policy is {}; function bodies are not compared, and changing an existing one will be rejected",
+            cap.policy
+        ));
+    }
+
+    let preflight = safety::preflight(
+        &config.deploy.safety,
+        &root,
+        &network.name,
+        &address,
+        Some(UpgradeTarget {
+            client: &client,
+            package_id: &deployment.package_id,
+            policy: cap.policy,
+        }),
+    )
+    .await?;
+
+    // The CLI finds the package being upgraded through `published-at`
+    let mut manifest = Manifest::load(&root)?;
+    if manifest.published_at()?.as_deref() != Some(deployment.package_id.as_str()) {
+        manifest.set_published_at(&deployment.package_id)?;
+        manifest.save()?;
+    }
+
+    utils::info("Building contracts...");
+    let build_output = SuiCli::build(true)?;
+    if !build_output.status.success() {
+        return Err(SuiForgeError::BuildFailed(
+            "Build failed before upgrade".to_string(),
+        ));
+    }
+
+    let budget = match gas_budget.unwrap_or(config.deploy.gas_budget) {
+        GasBudget::Fixed(budget) => budget,
+        GasBudget::Auto => {
+            let simulation = simulate("Simulating upgrade...", || {
+                SuiCli::upgrade(&network, &cap_id, None, true)
+            })?;
+            let margin = config.deploy.gas_safety_margin;
+            let estimate = auto_gas_budget(&simulation.effects.gas_used, margin);
+            utils::info(&format!(
+                "Auto gas budget: {} MIST (dry-run cost + {}%)",
+                estimate, margin
+            ));
+            estimate
+        }
+    };
+
+    let spinner = utils::create_spinner("Upgrading package...");
+    let output = SuiCli::upgrade(&network, &cap_id, Some(budget), false)?;
+    let response = match executed_transaction(&output) {
+        Ok(response) => response,
+        Err(e) => {
+            spinner.finish_with_message("Upgrade failed");
+            return Err(e);
+        }
+    };
+    spinner.finish_with_message("Package upgraded");
+
+    let upgrade = parse_publish(&response, &address)?;
+    lock.record_upgrade(
+        &network.name,
+        DeploymentRecord {
+            kind: DeploymentKind::Upgrade,
+            version: upgrade.version,
+            package_id: upgrade.package_id.clone(),
+            digest: response.digest.clone(),
+            git_commit: utils::git_commit(&root),
+            timestamp: Utc::now().to_rfc3339(),
+            deployer: address.clone(),
+            gas_used: upgrade.gas_used.clone(),
+            preflight,
+        },
+    )?;
+    lock.save(&root)?;
+
+    manifest.set_published_at(&upgrade.package_id)?;
+    manifest.save()?;
+
+    utils::success("Upgrade successful!");
+    println!("\n{}", "Upgrade Details:".bold());
+    println!("  Network: {}", network.name.cyan());
+    println!("  Previous Package ID: {}", deployment.package_id);
+    println!("  Package ID: {}", upgrade.package_id.green());
+    println!("  Version: {}", upgrade.version);
+    println!("  Transaction: {}", response.digest);
+    println!("  Gas used: {} MIST", upgrade.gas_used.total);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rpc::tests::{MockNode, Reply};
+    use serde_json::{json, Value};
+
+    fn cap_object(fields: Value) -> Reply {
+        Reply::Result(json!({
+            "data": {
+                "objectId": "0xcafe",
+                "version": "7",
+                "digest": "9Xk2",
+                "type": "0x2::package::UpgradeCap",
+                "owner": { "AddressOwner": "0xa11ce" },
+                "content": {
+                    "dataType": "moveObject",
+                    "type": "0x2::package::UpgradeCap",
+                    "hasPublicTransfer": true,
+                    "fields": fields,
+                },
+            }
+        }))
+    }
+
+    async fn fetch(fields: Value) -> Result<UpgradeCap> {
+        let node = MockNode::start(vec![cap_object(fields)]).await;
+        fetch_upgrade_cap(&node.client(), "0xcafe").await
+    }
+
+    #[tokio::test]
+    async fn fetch_upgrade_cap_reads_numbers_and_strings() {
+        let cap = fetch(
+            json!({"id": {"id": "0xcafe"}, "package": "0x5a1e", "version": "3", "policy": 128}),
+        )
+        .await
+        .unwrap();
+        assert_eq!(cap.package, "0x5a1e");
+        assert_eq!(cap.version, 3);
+        assert_eq!(cap.policy, UpgradePolicy::Additive);
+        assert_eq!(cap.owner, Some(Owner::AddressOwner("0xa11ce".to_string())));
+
+        let cap = fetch(json!({"package": "0x5a1e", "version": 3, "policy": "192"}))
+            .await
+            .unwrap();
+        assert_eq!(cap.policy, UpgradePolicy::DepOnly);
+    }
+
+    #[tokio::test]
+    async fn fetch_upgrade_cap_rejects_missing_or_unknown_fields() {
+        for fields in [
+            json!({"package": "0x5a1e", "version": "3"}),
+            json!({"package": "0x5a1e", "version": "3", "policy": "strict"}),
+            json!({"package": "0x5a1e", "version": "3", "policy": 64}),
+            json!({"package": "0x5a1e", "policy": 0}),
+            json!({"version": "3", "policy": 0}),
+        ] {
+            assert!(fetch(fields.clone()).await.is_err(), "{}", fields);
+        }
+    }
+}
@@ -1,150 +1,339 @@
-use crate::error::Result;
+use crate::abi::{same_address, short_address};
+use crate::attestation::{self, Attestation, LinkedDependency, ModuleHash};
+use crate::config::{find_project_root, resolve_network};
+use crate::error::{Result, SuiForgeError};
+use crate::lock::LockFile;
+use crate::move_lock::{resolve_dependencies, MoveLock};
+use crate::rpc::types::UpgradeInfo;
+use crate::rpc::RpcClient;
+use crate::sui::SuiCli;
 use crate::utils;
+use crate::verification::{self, DependencyCheck, LinkStatus, ModuleStatus};
+use chrono::Utc;
 use colored::Colorize;
-use sha2::{Digest, Sha256};
-use std::fs;
+use std::collections::BTreeMap;
 use std::path::Path;
 
-pub async fn execute(package_id: String, network: String) -> Result<()> {
+pub async fn execute(
+    package_id: Option<String>,
+    network: Option<String>,
+    report: Option<String>,
+    attestation: Option<String>,
+) -> Result<()> {
+    if let Some(path) = attestation {
+        return reverify(Path::new(&path), network).await;
+    }
+    let package_id =
+        package_id.ok_or_else(|| SuiForgeError::Custom("A package ID is required".to_string()))?;
+
+    let root = find_project_root()?;
+    let network = resolve_network(network.as_deref())?;
+    let client = RpcClient::for_network(&network);
+
     utils::info(&format!(
         "Verifying package {} on {}...",
         package_id.cyan(),
-        network.yellow()
+        network.name.yellow()
     ));
 
-    let spinner = utils::create_spinner("Computing source hash...");
+    let compiler = SuiCli::version()?;
+    let spinner = utils::create_spinner("Building package locally...");
+    let output = SuiCli::build(false)?;
+    if !output.status.success() {
+        spinner.finish_with_message("Build failed");
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        println!("\n{}", stderr);
+        return Err(SuiForgeError::BuildFailed(stderr.to_string()));
+    }
+    spinner.finish_with_message("Build completed");
+    let local = verification::local_modules(&root)?;
 
-    // Compute hash of source files
-    let source_hash = compute_source_hash("sources")?;
+    let spinner = utils::create_spinner("Fetching on-chain bytecode...");
+    let package = verification::fetch_package(&client, &package_id).await;
+    spinner.finish_and_clear();
+    let package = package?;
 
-    spinner.finish_with_message("Source hash computed");
+    // Upgraded packages keep the original ID as their self address; the lock
+    // file knows it even when the package defines no types.
+    let mut self_addresses = package.self_addresses();
+    let lock = LockFile::load(&root)?;
+    if let Some(deployment) = lock.network(&network.name) {
+        if deployment
+            .history
+            .iter()
+            .any(|r| r.package_id == package_id)
+        {
+            let original = &deployment.original_package_id;
+            if !self_addresses.iter().any(|a| same_address(a, original)) {
+                self_addresses.push(original.clone());
+            }
+        }
+    }
+
+    let modules = verification::compare_modules(&local, &package.modules, &self_addresses);
 
     println!();
     println!("{}", "Verification Report:".bold());
     println!("  Package ID: {}", package_id.cyan());
-    println!("  Network: {}", network.yellow());
-    println!("  Source Hash: {}", source_hash.green());
+    println!("  Network: {}", network.name.yellow());
+    println!("  Compiler: {}", compiler);
+    let locked_compiler = MoveLock::load(&root)?.and_then(|l| l.compiler_version());
+    if let Some(locked) = &locked_compiler {
+        println!("  Move.lock toolchain: {}", locked);
+        if !compiler.contains(locked.as_str()) {
+            utils::warning(&format!(
+                "Move.lock was written by compiler {}; a different compiler may produce different bytecode",
+                locked
+            ));
+        }
+    }
     println!();
 
-    // Fetch on-chain bytecode
-    let spinner = utils::create_spinner("Fetching on-chain bytecode...");
-    let bytecode_result = fetch_onchain_bytecode(&package_id, &network).await;
-    spinner.finish_with_message("Bytecode fetched");
-
-    match bytecode_result {
-        Ok(onchain_hash) => {
-            let spinner = utils::create_spinner("Comparing bytecode...");
-            let matches = source_hash == onchain_hash;
-            spinner.finish_with_message("Comparison complete");
-
-            println!();
-            if matches {
-                utils::success("✓ Contract verified successfully!");
-                println!();
-                println!("{}", "Verification Details:".bold());
-                println!("  ✓ Source code matches deployed bytecode");
-                println!("  ✓ No modifications detected");
-                println!("  ✓ Compiler version matches");
-            } else {
-                utils::error("✗ Verification failed!");
-                println!();
-                println!("{}", "Verification Details:".bold());
-                println!("  ✗ Source hash: {}", source_hash.yellow());
-                println!("  ✗ On-chain hash: {}", onchain_hash.red());
-                println!("  ✗ Hashes do not match");
-                println!();
-                utils::warning("The deployed bytecode does not match your source code.");
+    for module in &modules {
+        let status = match module.status {
+            ModuleStatus::Match => format!("✓ {}", module.status.as_str()).green(),
+            _ => format!("✗ {}", module.status.as_str()).red(),
+        };
+        println!("  {:<30} {}", module.name, status);
+    }
+    println!();
+
+    let dependencies = resolve_dependencies(&root, &network.name)?;
+    let links = verification::compare_linkage(&dependencies, &package.linkage_table);
+    if !links.is_empty() {
+        println!("{}", "Dependencies:".bold());
+        for link in &links {
+            let status = match link.status {
+                LinkStatus::Linked => format!("✓ {}", link.status.as_str()).green(),
+                status if status.is_drift() => format!("✗ {}", status.as_str()).red(),
+                status => format!("? {}", status.as_str()).yellow(),
+            };
+            let linked = match (&link.linked, link.linked_version) {
+                (Some(id), Some(version)) => format!("{} (v{})", short_address(id), version),
+                _ => "-".to_string(),
+            };
+            println!("  {:<30} {} {}", link.name, status, linked.dimmed());
+            if link.status == LinkStatus::Drift {
+                println!(
+                    "  {:<30} local build resolves {}",
+                    "",
+                    link.expected
+                        .as_deref()
+                        .map(short_address)
+                        .unwrap_or_default()
+                );
             }
-            println!();
-        }
-        Err(e) => {
-            utils::warning(&format!("Could not fetch on-chain bytecode: {}", e));
-            println!();
-            println!("{}", "Verification Status:".bold());
-            println!("  Source hash computed: {}", source_hash.green());
-            println!("  On-chain verification: {}", "Unavailable".yellow());
-            println!();
-            utils::info("Note: Full verification requires network access to fetch bytecode");
-            println!();
         }
+        println!();
     }
+
+    let count = |status| modules.iter().filter(|m| m.status == status).count();
+    let matched = count(ModuleStatus::Match);
+    let differs = count(ModuleStatus::Differs);
+    let missing = count(ModuleStatus::MissingOnChain) + count(ModuleStatus::MissingLocally);
+    let drifted = links.iter().filter(|l| l.status.is_drift()).count();
+
     println!(
         "View on explorer: {}",
         format!(
             "https://suiexplorer.com/object/{}?network={}",
-            package_id, network
+            package_id, network.name
         )
         .blue()
         .underline()
     );
+    println!();
 
+    // Dependency drift explains mismatching bytecode without the package's
+    // own sources having changed, so it is reported on its own.
+    if drifted > 0 {
+        utils::warning(&format!(
+            "The deployed package links {} dependenc{} at a different version than the local build",
+            drifted,
+            if drifted == 1 { "y" } else { "ies" }
+        ));
+    }
+    if differs > 0 || missing > 0 {
+        return Err(SuiForgeError::VerificationFailed(format!(
+            "source mismatch: {} of {} module(s) match; {} differ, {} missing",
+            matched,
+            modules.len(),
+            differs,
+            missing
+        )));
+    }
+    if drifted > 0 {
+        return Err(SuiForgeError::VerificationFailed(format!(
+            "dependency drift: modules match but {} dependenc{} differ from Move.lock",
+            drifted,
+            if drifted == 1 { "y" } else { "ies" }
+        )));
+    }
+
+    utils::success(&format!(
+        "All {} module(s) match the deployed bytecode",
+        matched
+    ));
+
+    if let Some(path) = report {
+        let sources = attestation::source_files(&root)?;
+        let attestation = Attestation {
+            schema_version: attestation::SCHEMA_VERSION,
+            generated_at: Utc::now().to_rfc3339(),
+            package_id: package_id.clone(),
+            network: network.name.clone(),
+            rpc: network.rpc.clone(),
+            modules: package
+                .modules
+                .iter()
+                .map(|(name, bytecode)| ModuleHash {
+                    name: name.clone(),
+                    sha256: verification::bytecode_hash(bytecode, &self_addresses),
+                })
+                .collect(),
+            self_addresses,
+            dependencies: linked_dependencies(&package.linkage_table, &links),
+            source_hash: attestation::source_hash(&sources),
+            sources,
+            compiler_version: compiler,
+            move_lock_compiler_version: locked_compiler,
+            suiforge_version: env!("CARGO_PKG_VERSION").to_string(),
+            git_commit: utils::git_commit(&root),
+        };
+        let (json, html) = attestation.write(Path::new(&path))?;
+        utils::success(&format!(
+            "Attestation written to {} and {}",
+            json.display(),
+            html.display()
+        ));
+    }
     Ok(())
 }
 
-fn compute_source_hash(source_dir: &str) -> Result<String> {
-    let mut hasher = Sha256::new();
-    let path = Path::new(source_dir);
+/// Check the chain against an attestation: every attested module must still
+/// hash the same and the package must link the same dependencies. The source
+/// hash is also checked when run inside the attested project.
+async fn reverify(path: &Path, network: Option<String>) -> Result<()> {
+    let attestation = Attestation::load(path)?;
+    let client = match network {
+        Some(name) => RpcClient::for_network(&resolve_network(Some(&name))?),
+        None => RpcClient::new(attestation.rpc.clone()),
+    };
 
-    if path.exists() {
-        for entry in walkdir::WalkDir::new(path)
-            .into_iter()
-            .filter_map(|e| e.ok())
-            .filter(|e| e.path().extension().map_or(false, |ext| ext == "move"))
-        {
-            let content = fs::read(entry.path())?;
-            hasher.update(&content);
+    utils::info(&format!(
+        "Re-verifying {} on {} from {}...",
+        attestation.package_id.cyan(),
+        attestation.network.yellow(),
+        path.display()
+    ));
+
+    let spinner = utils::create_spinner("Fetching on-chain bytecode...");
+    let package = verification::fetch_package(&client, &attestation.package_id).await;
+    spinner.finish_and_clear();
+    let package = package?;
+
+    let mut problems = 0;
+    println!();
+    println!("{}", "Modules:".bold());
+    for module in &attestation.modules {
+        let status = match package.modules.get(&module.name) {
+            None => Err("missing on chain"),
+            Some(bytecode) => {
+                if verification::bytecode_hash(bytecode, &attestation.self_addresses)
+                    == module.sha256
+                {
+                    Ok(())
+                } else {
+                    Err("differs")
+                }
+            }
+        };
+        print_outcome(&module.name, status, &mut problems);
+    }
+    for name in package.modules.keys() {
+        if !attestation.modules.iter().any(|m| &m.name == name) {
+            print_outcome(name, Err("not in attestation"), &mut problems);
         }
     }
 
-    let result = hasher.finalize();
-    Ok(hex::encode(result))
-}
+    if !attestation.dependencies.is_empty() || !package.linkage_table.is_empty() {
+        println!();
+        println!("{}", "Dependencies:".bold());
+    }
+    for dependency in &attestation.dependencies {
+        let status = match package.linkage_table.get(&dependency.original_id) {
+            None => Err("no longer linked"),
+            Some(info) if info.upgraded_id == dependency.linked_id => Ok(()),
+            Some(_) => Err("version drift"),
+        };
+        print_outcome(&dependency.name, status, &mut problems);
+    }
+    for id in package.linkage_table.keys() {
+        if !attestation
+            .dependencies
+            .iter()
+            .any(|d| &d.original_id == id)
+        {
+            print_outcome(&short_address(id), Err("not in attestation"), &mut problems);
+        }
+    }
 
+    println!();
+    match find_project_root() {
+        Ok(root) => {
+            let source_hash = attestation::source_hash(&attestation::source_files(&root)?);
+            if source_hash == attestation.source_hash {
+                utils::success(&format!("Local sources match source hash {}", source_hash));
+            } else {
+                utils::warning(&format!(
+                    "Local sources hash to {}, the attestation records {}",
+                    source_hash, attestation.source_hash
+                ));
+            }
+        }
+        Err(_) => utils::info("Not in a project; skipping the source hash check"),
+    }
 
-async fn fetch_onchain_bytecode(package_id: &str, network: &str) -> Result<String> {
-    // Construct RPC URL based on network
-    let rpc_url = match network {
-        "devnet" => "https://fullnode.devnet.sui.io:443",
-        "testnet" => "https://fullnode.testnet.sui.io:443",
-        "mainnet" => "https://fullnode.mainnet.sui.io:443",
-        _ => return Err(crate::error::SuiForgeError::InvalidNetwork(network.to_string())),
-    };
+    if problems > 0 {
+        return Err(SuiForgeError::VerificationFailed(format!(
+            "{} item(s) no longer match the attestation",
+            problems
+        )));
+    }
+    utils::success("On-chain package matches the attestation");
+    Ok(())
+}
 
-    // Make RPC call to fetch object
-    let client = reqwest::Client::new();
-    let response = client
-        .post(rpc_url)
-        .json(&serde_json::json!({
-            "jsonrpc": "2.0",
-            "id": 1,
-            "method": "sui_getObject",
-            "params": [
-                package_id,
-                {
-                    "showType": true,
-                    "showOwner": true,
-                    "showPreviousTransaction": true,
-                    "showDisplay": false,
-                    "showContent": true,
-                    "showBcs": true,
-                    "showStorageRebate": true
-                }
-            ]
-        }))
-        .send()
-        .await?;
-
-    let json: serde_json::Value = response.json().await?;
-
-    // Extract bytecode and compute hash
-    if let Some(bcs) = json["result"]["data"]["bcs"].as_str() {
-        let mut hasher = Sha256::new();
-        hasher.update(bcs.as_bytes());
-        let result = hasher.finalize();
-        Ok(hex::encode(result))
-    } else {
-        Err(crate::error::SuiForgeError::Custom(
-            "Could not extract bytecode from response".to_string(),
-        ))
+fn print_outcome(name: &str, status: std::result::Result<(), &str>, problems: &mut usize) {
+    match status {
+        Ok(()) => println!("  {:<30} {}", name, "✓ match".green()),
+        Err(problem) => {
+            *problems += 1;
+            println!("  {:<30} {}", name, format!("✗ {}", problem).red());
+        }
     }
 }
+
+/// The on-chain linkage, named after the local dependencies where known.
+fn linked_dependencies(
+    linkage: &BTreeMap<String, UpgradeInfo>,
+    links: &[DependencyCheck],
+) -> Vec<LinkedDependency> {
+    linkage
+        .iter()
+        .map(|(id, info)| LinkedDependency {
+            name: links
+                .iter()
+                .find(|l| {
+                    l.original_id
+                        .as_deref()
+                        .is_some_and(|o| same_address(o, id))
+                })
+                .map(|l| l.name.clone())
+                .unwrap_or_else(|| short_address(id)),
+            original_id: id.clone(),
+            linked_id: info.upgraded_id.clone(),
+            linked_version: info.upgraded_version,
+        })
+        .collect()
+}
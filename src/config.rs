@@ -1,7 +1,11 @@
+use crate::commands::profile::ProfileConfig;
 use crate::error::{Result, SuiForgeError};
-use serde::{Deserialize, Serialize};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::BTreeMap;
+use std::fmt;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct SuiForgeConfig {
@@ -12,11 +16,26 @@ pub struct SuiForgeConfig {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
 pub struct NetworkConfig {
     pub default: String,
     pub devnet: NetworkEndpoint,
     pub testnet: NetworkEndpoint,
     pub mainnet: Option<NetworkEndpoint>,
+    /// Additional networks (localnet, private RPCs) keyed by name.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub networks: BTreeMap<String, NetworkEndpoint>,
+}
+
+impl NetworkConfig {
+    pub fn endpoint(&self, name: &str) -> Option<&NetworkEndpoint> {
+        match name {
+            "devnet" => Some(&self.devnet),
+            "testnet" => Some(&self.testnet),
+            "mainnet" => self.mainnet.as_ref(),
+            _ => self.networks.get(name),
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -37,13 +56,114 @@ pub struct DeployConfig {
     #[serde(rename = "gasObjectSelection")]
     pub gas_object_selection: String,
     #[serde(rename = "gasBudget")]
-    pub gas_budget: u64,
+    pub gas_budget: GasBudget,
+    /// Percentage added on top of the dry-run cost when the budget is `auto`.
+    #[serde(rename = "gasSafetyMargin", default = "default_gas_safety_margin")]
+    pub gas_safety_margin: u32,
+    /// Pre-flight checks for deployments to protected networks.
+    #[serde(default)]
+    pub safety: SafetyConfig,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SafetyConfig {
+    /// Networks that deploys and upgrades must pass the checks for.
+    pub protected_networks: Vec<String>,
+    pub require_clean_git: bool,
+    pub require_tests: bool,
+    /// Lowest `scan` severity that blocks a deployment (critical, high,
+    /// medium, low, info); unset disables the scan.
+    pub scan_threshold: Option<String>,
+    /// Check upgrade compatibility against the deployed package (upgrades only).
+    pub require_compat: bool,
+    /// Ask for the network name and deployer address to be typed back.
+    pub require_confirmation: bool,
+}
+
+impl Default for SafetyConfig {
+    fn default() -> Self {
+        Self {
+            protected_networks: vec!["mainnet".to_string()],
+            require_clean_git: true,
+            require_tests: true,
+            scan_threshold: Some("high".to_string()),
+            require_compat: true,
+            require_confirmation: true,
+        }
+    }
+}
+
+impl SafetyConfig {
+    pub fn protects(&self, network: &str) -> bool {
+        self.protected_networks.iter().any(|n| n == network)
+    }
+}
+
+fn default_gas_safety_margin() -> u32 {
+    20
+}
+
+/// Gas budget in MIST, or `"auto"` to derive it from a dry run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GasBudget {
+    Auto,
+    Fixed(u64),
+}
+
+impl FromStr for GasBudget {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        if s.eq_ignore_ascii_case("auto") {
+            return Ok(GasBudget::Auto);
+        }
+        s.replace('_', "")
+            .parse()
+            .map(GasBudget::Fixed)
+            .map_err(|_| format!("expected a number of MIST or \"auto\", got \"{}\"", s))
+    }
+}
+
+impl fmt::Display for GasBudget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GasBudget::Auto => write!(f, "auto"),
+            GasBudget::Fixed(budget) => write!(f, "{}", budget),
+        }
+    }
+}
+
+impl Serialize for GasBudget {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        match self {
+            GasBudget::Auto => serializer.serialize_str("auto"),
+            GasBudget::Fixed(budget) => serializer.serialize_u64(*budget),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for GasBudget {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw {
+            Number(u64),
+            Text(String),
+        }
+
+        match Raw::deserialize(deserializer)? {
+            Raw::Number(budget) => Ok(GasBudget::Fixed(budget)),
+            Raw::Text(text) => text.parse().map_err(de::Error::custom),
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct CodegenConfig {
     pub typescript: Option<CodegenTarget>,
     pub rust: Option<CodegenTarget>,
+    pub python: Option<CodegenTarget>,
     pub swift: Option<CodegenTarget>,
 }
 
@@ -53,17 +173,6 @@ pub struct CodegenTarget {
     pub output_dir: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct DeploymentLock {
-    #[serde(rename = "packageId")]
-    pub package_id: String,
-    #[serde(rename = "objectIds")]
-    pub object_ids: Vec<String>,
-    pub network: String,
-    pub timestamp: String,
-    pub digest: String,
-}
-
 impl SuiForgeConfig {
     pub fn default() -> Self {
         Self {
@@ -78,6 +187,7 @@ impl SuiForgeConfig {
                 mainnet: Some(NetworkEndpoint {
                     rpc: "https://fullnode.mainnet.sui.io:443".to_string(),
                 }),
+                networks: BTreeMap::new(),
             },
             build: BuildConfig {
                 output_dir: "build".to_string(),
@@ -85,7 +195,9 @@ impl SuiForgeConfig {
             },
             deploy: DeployConfig {
                 gas_object_selection: "auto".to_string(),
-                gas_budget: 50000000,
+                gas_budget: GasBudget::Fixed(50000000),
+                gas_safety_margin: default_gas_safety_margin(),
+                safety: SafetyConfig::default(),
             },
             codegen: CodegenConfig {
                 typescript: Some(CodegenTarget {
@@ -94,6 +206,9 @@ impl SuiForgeConfig {
                 rust: Some(CodegenTarget {
                     output_dir: "./sdk/rust".to_string(),
                 }),
+                python: Some(CodegenTarget {
+                    output_dir: "./sdk/python".to_string(),
+                }),
                 swift: Some(CodegenTarget {
                     output_dir: "./sdk/swift".to_string(),
                 }),
@@ -103,8 +218,8 @@ impl SuiForgeConfig {
 
     pub fn load(path: &Path) -> Result<Self> {
         let content = fs::read_to_string(path)?;
-        let config: SuiForgeConfig = serde_json::from_str(&content)?;
-        Ok(config)
+        serde_json::from_str(&content)
+            .map_err(|e| SuiForgeError::ConfigError(format!("{}: {}", path.display(), e)))
     }
 
     pub fn save(&self, path: &Path) -> Result<()> {
@@ -134,3 +249,120 @@ pub fn load_config() -> Result<SuiForgeConfig> {
     let config_path = root.join("suiforge.config.json");
     SuiForgeConfig::load(&config_path)
 }
+
+/// A network name resolved to the RPC endpoint SuiForge should talk to.
+#[derive(Debug, Clone)]
+pub struct ResolvedNetwork {
+    pub name: String,
+    pub rpc: String,
+}
+
+/// Resolve a network or profile name. Networks declared in the project's
+/// `suiforge.config.json` take precedence over the user's profiles. With no
+/// name, the active profile is used once the user has saved profiles, and
+/// the project's `network.default` before that.
+pub fn resolve_network(name: Option<&str>) -> Result<ResolvedNetwork> {
+    let profiles = ProfileConfig::load()?;
+
+    // Outside a project only the profiles apply, but a project config that
+    // fails to load must not silently fall through to a profile of the same
+    // name with a different RPC.
+    let config = match load_config() {
+        Ok(config) => Some(config),
+        Err(SuiForgeError::NotInProject) => None,
+        Err(e) => return Err(e),
+    };
+
+    let name = match name {
+        Some(name) => name.to_string(),
+        None => default_network(
+            ProfileConfig::is_saved().then_some(profiles.active.as_str()),
+            config.as_ref(),
+        ),
+    };
+    if let Some(endpoint) = config.as_ref().and_then(|c| c.network.endpoint(&name)) {
+        return Ok(ResolvedNetwork {
+            rpc: endpoint.rpc.clone(),
+            name,
+        });
+    }
+
+    match profiles.profiles.get(&name) {
+        Some(profile) => Ok(ResolvedNetwork {
+            rpc: profile.rpc.clone(),
+            name,
+        }),
+        None => Err(SuiForgeError::InvalidNetwork(name)),
+    }
+}
+
+/// The network to use when none is named: the user's active profile, then
+/// the project's default, then devnet.
+fn default_network(active_profile: Option<&str>, config: Option<&SuiForgeConfig>) -> String {
+    active_profile
+        .or_else(|| config.map(|c| c.network.default.as_str()))
+        .unwrap_or("devnet")
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn network_config(json: &str) -> serde_json::Result<NetworkConfig> {
+        serde_json::from_str(json)
+    }
+
+    #[test]
+    fn extra_networks_come_from_the_networks_map() {
+        let config = network_config(
+            r#"{
+                "default": "localnet",
+                "devnet": {"rpc": "https://devnet"},
+                "testnet": {"rpc": "https://testnet"},
+                "networks": {"localnet": {"rpc": "http://127.0.0.1:9000"}}
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.endpoint("localnet").map(|e| e.rpc.as_str()),
+            Some("http://127.0.0.1:9000")
+        );
+        assert_eq!(
+            config.endpoint("testnet").map(|e| e.rpc.as_str()),
+            Some("https://testnet")
+        );
+        assert!(config.endpoint("mainnet").is_none());
+        assert!(config.endpoint("networks").is_none());
+    }
+
+    #[test]
+    fn misspelled_network_keys_are_rejected() {
+        let error = network_config(
+            r#"{
+                "default": "devnet",
+                "devnet": {"rpc": "https://devnet"},
+                "testnet": {"rpc": "https://testnet"},
+                "mainet": {"rpc": "https://mainnet"}
+            }"#,
+        )
+        .unwrap_err();
+
+        assert!(
+            error.to_string().contains("unknown field `mainet`"),
+            "{}",
+            error
+        );
+    }
+
+    #[test]
+    fn default_network_prefers_the_active_profile_then_the_project() {
+        let mut config = SuiForgeConfig::default();
+        config.network.default = "testnet".to_string();
+
+        assert_eq!(default_network(Some("mainnet"), Some(&config)), "mainnet");
+        assert_eq!(default_network(None, Some(&config)), "testnet");
+        assert_eq!(default_network(None, None), "devnet");
+    }
+}
@@ -13,9 +13,15 @@ pub enum SuiForgeError {
     #[error("TOML error: {0}")]
     Toml(#[from] toml::de::Error),
 
+    #[error("YAML error: {0}")]
+    Yaml(#[from] serde_yaml::Error),
+
     #[error("HTTP request error: {0}")]
     Reqwest(#[from] reqwest::Error),
 
+    #[error("RPC error {code}: {message}")]
+    Rpc { code: i64, message: String },
+
     #[error("Template error: {0}")]
     Template(#[from] handlebars::RenderError),
 
@@ -44,11 +50,19 @@ pub enum SuiForgeError {
     #[error("Deployment failed: {0}")]
     DeploymentFailed(String),
 
+    #[error("Verification failed: {0}")]
+    VerificationFailed(String),
+
+    #[error("Pre-flight check failed: {0}")]
+    PreflightFailed(String),
+
+    #[error("Move parse error: {0}")]
+    MoveParse(String),
+
     #[error("Code generation failed: {0}")]
     CodegenFailed(String),
 
     #[error("Configuration error: {0}")]
-    #[allow(dead_code)]
     ConfigError(String),
 
     #[error("{0}")]
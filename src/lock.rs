@@ -0,0 +1,355 @@
+//! `suiforge.lock.json`: per-network deployment state and history.
+//!
+//! Each network keeps the current package, its UpgradeCap and the objects
+//! created at publish time, plus an append-only history of every publish and
+//! upgrade. Lock files written before the schema was versioned held a single
+//! flat deployment; they are migrated on load and rewritten in the current
+//! format on the next save.
+
+use crate::error::{Result, SuiForgeError};
+use crate::rpc::types::Owner;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+pub const LOCK_FILE: &str = "suiforge.lock.json";
+pub const SCHEMA_VERSION: u32 = 1;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LockFile {
+    pub schema_version: u32,
+    pub networks: BTreeMap<String, NetworkDeployment>,
+}
+
+/// Current state of the package on one network.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct NetworkDeployment {
+    /// Latest package ID; changes with every upgrade.
+    pub package_id: String,
+    /// Package ID of the first publish, which types and events keep using.
+    pub original_package_id: String,
+    pub upgrade_cap_id: Option<String>,
+    pub deployer: String,
+    /// Objects created by the most recent publish, including the UpgradeCap
+    /// and anything created by `init` functions.
+    #[serde(default)]
+    pub objects: Vec<DeployedObject>,
+    #[serde(default)]
+    pub history: Vec<DeploymentRecord>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DeployedObject {
+    pub object_id: String,
+    pub object_type: String,
+    pub owner: Owner,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum DeploymentKind {
+    Publish,
+    Upgrade,
+}
+
+impl DeploymentKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DeploymentKind::Publish => "publish",
+            DeploymentKind::Upgrade => "upgrade",
+        }
+    }
+}
+
+/// One publish or upgrade transaction.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DeploymentRecord {
+    pub kind: DeploymentKind,
+    /// On-chain package version: 1 for a publish, incremented by each upgrade.
+    pub version: u64,
+    pub package_id: String,
+    pub digest: String,
+    /// `HEAD` of the project repository at deploy time, if it is a git repo.
+    pub git_commit: Option<String>,
+    pub timestamp: String,
+    pub deployer: String,
+    #[serde(default)]
+    pub gas_used: GasUsed,
+    /// Pre-flight checks run before deploying to a protected network.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preflight: Option<PreflightAudit>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PreflightAudit {
+    pub timestamp: String,
+    pub checks: Vec<PreflightCheck>,
+    /// Address the operator typed back at the confirmation prompt.
+    pub confirmed_by: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PreflightCheck {
+    pub name: String,
+    pub outcome: CheckOutcome,
+    pub detail: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckOutcome {
+    Passed,
+    Skipped,
+}
+
+/// Gas charged for a transaction, in MIST.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct GasUsed {
+    pub computation_cost: u64,
+    pub storage_cost: u64,
+    pub storage_rebate: u64,
+    /// `computation + storage - rebate`; negative when the rebate exceeds the cost.
+    pub total: i64,
+}
+
+/// The single-deployment format written before `schemaVersion` existed.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LegacyLock {
+    package_id: String,
+    network: String,
+    timestamp: String,
+    #[serde(default)]
+    digest: String,
+    deployer: Option<String>,
+    upgrade_cap_id: Option<String>,
+    #[serde(default)]
+    objects: Vec<DeployedObject>,
+    #[serde(default)]
+    gas_used: GasUsed,
+}
+
+impl Default for LockFile {
+    fn default() -> Self {
+        Self {
+            schema_version: SCHEMA_VERSION,
+            networks: BTreeMap::new(),
+        }
+    }
+}
+
+impl LockFile {
+    /// Load the lock file from `root`, migrating older formats. A missing
+    /// file yields an empty lock.
+    pub fn load(root: &Path) -> Result<Self> {
+        let path = root.join(LOCK_FILE);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let value: Value = serde_json::from_str(&fs::read_to_string(&path)?)?;
+        match value.get("schemaVersion").and_then(Value::as_u64) {
+            Some(version) if version > SCHEMA_VERSION as u64 => {
+                Err(SuiForgeError::ConfigError(format!(
+                    "{} has schema version {}, but this version of suiforge only understands up to {}",
+                    LOCK_FILE, version, SCHEMA_VERSION
+                )))
+            }
+            Some(_) => Ok(serde_json::from_value(value)?),
+            None => Ok(Self::migrate_legacy(serde_json::from_value(value)?)),
+        }
+    }
+
+    /// Every package ID the project has been deployed at, on any network.
+    pub fn package_ids(&self) -> Vec<String> {
+        let mut ids = Vec::new();
+        for deployment in self.networks.values() {
+            ids.push(deployment.original_package_id.clone());
+            ids.push(deployment.package_id.clone());
+            ids.extend(deployment.history.iter().map(|r| r.package_id.clone()));
+        }
+        ids.sort();
+        ids.dedup();
+        ids
+    }
+
+    pub fn save(&self, root: &Path) -> Result<()> {
+        let content = serde_json::to_string_pretty(self)?;
+        fs::write(root.join(LOCK_FILE), content)?;
+        Ok(())
+    }
+
+    fn migrate_legacy(legacy: LegacyLock) -> Self {
+        let deployer = legacy.deployer.unwrap_or_default();
+        let record = DeploymentRecord {
+            kind: DeploymentKind::Publish,
+            version: 1,
+            package_id: legacy.package_id.clone(),
+            digest: legacy.digest,
+            git_commit: None,
+            timestamp: legacy.timestamp,
+            deployer: deployer.clone(),
+            gas_used: legacy.gas_used,
+            preflight: None,
+        };
+
+        let mut lock = Self::default();
+        lock.networks.insert(
+            legacy.network,
+            NetworkDeployment {
+                original_package_id: legacy.package_id.clone(),
+                package_id: legacy.package_id,
+                upgrade_cap_id: legacy.upgrade_cap_id,
+                deployer,
+                objects: legacy.objects,
+                history: vec![record],
+            },
+        );
+        lock
+    }
+
+    pub fn network(&self, name: &str) -> Option<&NetworkDeployment> {
+        self.networks.get(name)
+    }
+
+    /// Record a fresh publish. The network's current package is replaced,
+    /// while earlier history entries are kept.
+    pub fn record_publish(
+        &mut self,
+        network: &str,
+        record: DeploymentRecord,
+        upgrade_cap_id: Option<String>,
+        objects: Vec<DeployedObject>,
+    ) {
+        let mut history = self
+            .networks
+            .remove(network)
+            .map(|d| d.history)
+            .unwrap_or_default();
+        let package_id = record.package_id.clone();
+        let deployer = record.deployer.clone();
+        history.push(record);
+
+        self.networks.insert(
+            network.to_string(),
+            NetworkDeployment {
+                original_package_id: package_id.clone(),
+                package_id,
+                upgrade_cap_id,
+                deployer,
+                objects,
+                history,
+            },
+        );
+    }
+
+    /// Record an upgrade of the network's current package. The UpgradeCap,
+    /// original package ID and publish-time objects are unchanged.
+    pub fn record_upgrade(&mut self, network: &str, record: DeploymentRecord) -> Result<()> {
+        let deployment = self.networks.get_mut(network).ok_or_else(|| {
+            SuiForgeError::Custom(format!("No deployment recorded for network {}", network))
+        })?;
+        deployment.package_id = record.package_id.clone();
+        deployment.history.push(record);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LEGACY_LOCK: &str = r#"{
+  "packageId": "0x5a1e",
+  "network": "testnet",
+  "timestamp": "2024-03-01T12:00:00Z",
+  "digest": "9Xk2digest",
+  "deployer": "0xd3p10y3r",
+  "upgradeCapId": "0xcap",
+  "objects": [
+    {
+      "objectId": "0xcap",
+      "objectType": "0x2::package::UpgradeCap",
+      "owner": { "AddressOwner": "0xd3p10y3r" }
+    },
+    {
+      "objectId": "0xreg",
+      "objectType": "0x5a1e::registry::Registry",
+      "owner": { "Shared": { "initial_shared_version": 3 } }
+    }
+  ],
+  "gasUsed": {
+    "computationCost": 1000,
+    "storageCost": 2000,
+    "storageRebate": 500,
+    "total": 2500
+  }
+}"#;
+
+    #[test]
+    fn legacy_lock_is_migrated_and_survives_a_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join(LOCK_FILE), LEGACY_LOCK).unwrap();
+
+        let lock = LockFile::load(dir.path()).unwrap();
+        assert_eq!(lock.schema_version, SCHEMA_VERSION);
+        assert_eq!(lock.networks.keys().collect::<Vec<_>>(), ["testnet"]);
+
+        let testnet = lock.network("testnet").unwrap();
+        assert_eq!(testnet.package_id, "0x5a1e");
+        assert_eq!(testnet.original_package_id, "0x5a1e");
+        assert_eq!(testnet.upgrade_cap_id.as_deref(), Some("0xcap"));
+        assert_eq!(testnet.deployer, "0xd3p10y3r");
+        assert_eq!(testnet.objects.len(), 2);
+        assert_eq!(
+            testnet.objects[0].owner,
+            Owner::AddressOwner("0xd3p10y3r".into())
+        );
+        assert_eq!(testnet.history.len(), 1);
+        assert_eq!(testnet.history[0].kind, DeploymentKind::Publish);
+        assert_eq!(testnet.history[0].version, 1);
+        assert_eq!(testnet.history[0].digest, "9Xk2digest");
+        assert_eq!(testnet.history[0].gas_used.total, 2500);
+        assert_eq!(lock.package_ids(), ["0x5a1e"]);
+
+        lock.save(dir.path()).unwrap();
+        let saved = fs::read_to_string(dir.path().join(LOCK_FILE)).unwrap();
+        assert!(saved.contains("\"schemaVersion\": 1"));
+
+        let reloaded = LockFile::load(dir.path()).unwrap();
+        assert_eq!(
+            serde_json::to_value(&reloaded).unwrap(),
+            serde_json::to_value(&lock).unwrap()
+        );
+    }
+
+    #[test]
+    fn newer_schema_versions_are_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join(LOCK_FILE),
+            r#"{"schemaVersion": 2, "networks": {}}"#,
+        )
+        .unwrap();
+
+        let err = LockFile::load(dir.path()).unwrap_err();
+        assert!(err.to_string().contains("schema version 2"));
+    }
+
+    #[test]
+    fn missing_lock_file_is_empty() {
+        let dir = tempfile::tempdir().unwrap();
+        let lock = LockFile::load(dir.path()).unwrap();
+        assert_eq!(lock.schema_version, SCHEMA_VERSION);
+        assert!(lock.networks.is_empty());
+    }
+}
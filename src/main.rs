@@ -1,11 +1,21 @@
+mod abi;
+mod aborts;
+mod attestation;
 mod cli;
 mod codegen;
 mod commands;
 mod config;
 mod error;
+mod lock;
+mod manifest;
+mod move_lock;
+mod move_syntax;
+mod rpc;
+mod safety;
 mod sui;
 mod templates;
 mod utils;
+mod verification;
 
 use clap::Parser;
 use cli::{Cli, Commands};
@@ -48,11 +58,39 @@ async fn run() -> Result<()> {
             network,
             gas_budget,
             skip_verify,
+            dry_run,
         } => {
-            commands::deploy::execute(network, gas_budget, skip_verify).await?;
+            commands::deploy::execute(network, gas_budget, skip_verify, dry_run).await?;
         }
-        Commands::Generate { target, output } => {
-            commands::generate::execute(target, output).await?;
+        Commands::Upgrade {
+            network,
+            gas_budget,
+        } => {
+            commands::upgrade::execute(network, gas_budget).await?;
+        }
+        Commands::Compat {
+            network,
+            package,
+            policy,
+            strict,
+            format,
+        } => {
+            commands::compat::execute(network, package, policy, strict, format).await?;
+        }
+        Commands::Deployments {
+            action,
+            network,
+            format,
+        } => {
+            commands::deployments::execute(action, network, format).await?;
+        }
+        Commands::Generate {
+            target,
+            output,
+            abi,
+            network,
+        } => {
+            commands::generate::execute(target, output, abi, network).await?;
         }
         Commands::Node { action } => {
             commands::node::execute(action).await?;
@@ -66,8 +104,10 @@ async fn run() -> Result<()> {
         Commands::Verify {
             package_id,
             network,
+            report,
+            attestation,
         } => {
-            commands::verify::execute(package_id, network).await?;
+            commands::verify::execute(package_id, network, report, attestation).await?;
         }
         Commands::Gas { action, function } => {
             commands::gas::execute(action, function).await?;
@@ -82,11 +122,48 @@ async fn run() -> Result<()> {
             commands::dashboard::execute(port).await?;
         }
         Commands::Inspect {
-            object_id,
+            target,
+            id,
+            network,
+            format,
+            depth,
+            limit,
+            history,
+            diff,
+        } => {
+            commands::inspect::execute(
+                target, id, network, format, depth, limit, history, diff,
+            )
+            .await?;
+        }
+        Commands::Events {
+            network,
+            package,
+            module,
+            event_type,
+            sender,
+            tx,
+            follow,
+            interval,
+            limit,
+            format,
+        } => {
+            commands::events::execute(
+                network, package, module, event_type, sender, tx, follow, interval, limit, format,
+            )
+            .await?;
+        }
+        Commands::Objects {
+            address,
             network,
+            struct_type,
+            package,
+            group,
+            limit,
             format,
         } => {
-            commands::inspect::execute(object_id, network, format).await?;
+            commands::objects::execute(address, network, struct_type, package, group, limit, format)
+                .await?;
         }
         Commands::Coverage { format, output } => {
             commands::coverage::execute(format, output).await?;
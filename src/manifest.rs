@@ -0,0 +1,163 @@
+//! Reading and minimally editing the project's `Move.toml`.
+//!
+//! Edits are made on the text rather than by re-serializing the parsed
+//! TOML, so comments, ordering and formatting in the manifest survive.
+
+use crate::error::{Result, SuiForgeError};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const MANIFEST_FILE: &str = "Move.toml";
+
+pub struct Manifest {
+    path: PathBuf,
+    content: String,
+}
+
+impl Manifest {
+    pub fn load(root: &Path) -> Result<Self> {
+        let path = root.join(MANIFEST_FILE);
+        let content = fs::read_to_string(&path).map_err(|e| {
+            SuiForgeError::ConfigError(format!("Could not read {}: {}", path.display(), e))
+        })?;
+        Ok(Self { path, content })
+    }
+
+    pub fn save(&self) -> Result<()> {
+        fs::write(&self.path, &self.content)?;
+        Ok(())
+    }
+
+    fn parsed(&self) -> Result<toml::Value> {
+        Ok(toml::from_str(&self.content)?)
+    }
+
+    fn package_field(&self, key: &str) -> Result<Option<String>> {
+        Ok(self
+            .parsed()?
+            .get("package")
+            .and_then(|p| p.get(key))
+            .and_then(|v| v.as_str())
+            .map(str::to_string))
+    }
+
+    /// Named addresses from the `[addresses]` table.
+    pub fn addresses(&self) -> Result<BTreeMap<String, String>> {
+        Ok(self
+            .parsed()?
+            .get("addresses")
+            .and_then(|a| a.as_table())
+            .map(|table| {
+                table
+                    .iter()
+                    .filter_map(|(name, value)| Some((name.clone(), value.as_str()?.to_string())))
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
+    pub fn package_name(&self) -> Result<Option<String>> {
+        self.package_field("name")
+    }
+
+    pub fn published_at(&self) -> Result<Option<String>> {
+        self.package_field("published-at")
+    }
+
+    /// Set `published-at` in the `[package]` table, replacing an existing
+    /// entry (keeping its trailing comment) or adding one after the table's
+    /// last key.
+    pub fn set_published_at(&mut self, package_id: &str) -> Result<()> {
+        let entry = format!("published-at = \"{}\"", package_id);
+        let mut lines: Vec<String> = self.content.lines().map(str::to_string).collect();
+
+        let start = lines
+            .iter()
+            .position(|l| l.trim() == "[package]")
+            .ok_or_else(|| {
+                SuiForgeError::ConfigError(format!("{} has no [package] table", MANIFEST_FILE))
+            })?;
+        let end = lines[start + 1..]
+            .iter()
+            .position(|l| l.trim_start().starts_with('['))
+            .map(|i| start + 1 + i)
+            .unwrap_or(lines.len());
+
+        let existing = lines[start + 1..end].iter().position(|l| {
+            l.trim_start()
+                .strip_prefix("published-at")
+                .is_some_and(|rest| rest.trim_start().starts_with('='))
+        });
+
+        match existing {
+            Some(i) => {
+                // Package IDs never contain '#', so the first one starts a comment
+                let line = &lines[start + 1 + i];
+                let comment = line.find('#').map(|at| format!(" {}", &line[at..]));
+                lines[start + 1 + i] = entry + comment.as_deref().unwrap_or("");
+            }
+            None => {
+                // Insert after the table's last key so blank lines and
+                // comments leading into the next table stay with it.
+                let last = (start..end)
+                    .rev()
+                    .find(|&i| {
+                        let line = lines[i].trim();
+                        !line.is_empty() && !line.starts_with('#')
+                    })
+                    .unwrap_or(start);
+                lines.insert(last + 1, entry);
+            }
+        }
+
+        let mut content = lines.join("\n");
+        if self.content.ends_with('\n') {
+            content.push('\n');
+        }
+        self.content = content;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(content: &str, package_id: &str) -> Result<String> {
+        let mut manifest = Manifest {
+            path: PathBuf::from(MANIFEST_FILE),
+            content: content.to_string(),
+        };
+        manifest.set_published_at(package_id)?;
+        assert_eq!(manifest.published_at()?.as_deref(), Some(package_id));
+        Ok(manifest.content)
+    }
+
+    #[test]
+    fn set_published_at_replaces_an_existing_entry() {
+        let content = "[package]\nname = \"shop\"\npublished-at = \"0x1\"   # v1\nedition = \"2024.beta\"\n\n[addresses]\nshop = \"0x0\"\n";
+        assert_eq!(
+            set(content, "0x5a1e").unwrap(),
+            "[package]\nname = \"shop\"\npublished-at = \"0x5a1e\" # v1\nedition = \"2024.beta\"\n\n[addresses]\nshop = \"0x0\"\n"
+        );
+    }
+
+    #[test]
+    fn set_published_at_adds_after_the_last_key() {
+        let content = "# Shop package\n[package]\nname = \"shop\"\n# published-at = \"0x1\"\nedition = \"2024.beta\"\n\n# Framework\n[dependencies]\nSui = { local = \"../sui\" }";
+        assert_eq!(
+            set(content, "0x5a1e").unwrap(),
+            "# Shop package\n[package]\nname = \"shop\"\n# published-at = \"0x1\"\nedition = \"2024.beta\"\npublished-at = \"0x5a1e\"\n\n# Framework\n[dependencies]\nSui = { local = \"../sui\" }"
+        );
+    }
+
+    #[test]
+    fn set_published_at_requires_a_package_table() {
+        let content = "[addresses]\nshop = \"0x0\"\n";
+        assert!(matches!(
+            set(content, "0x5a1e"),
+            Err(SuiForgeError::ConfigError(_))
+        ));
+    }
+}
@@ -0,0 +1,299 @@
+//! Reading `Move.lock`, which pins the package's resolved dependencies and
+//! records the toolchain and per-environment publication info.
+
+use crate::abi::{canonical_address, is_hex_address};
+use crate::error::{Result, SuiForgeError};
+use crate::manifest::{Manifest, MANIFEST_FILE};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const MOVE_LOCK_FILE: &str = "Move.lock";
+
+/// Framework packages live at fixed addresses and are never published by
+/// users.
+const SYSTEM_PACKAGES: &[(&str, &str)] = &[
+    ("MoveStdlib", "0x1"),
+    ("Sui", "0x2"),
+    ("SuiSystem", "0x3"),
+    ("Bridge", "0xb"),
+    ("DeepBook", "0xdee9"),
+];
+
+/// Whether `address` is one of the framework packages.
+pub fn is_system_address(address: &str) -> bool {
+    SYSTEM_PACKAGES
+        .iter()
+        .any(|(_, system)| canonical_address(system) == canonical_address(address))
+}
+
+pub struct MoveLock {
+    value: toml::Value,
+}
+
+/// Where a locked dependency's sources come from.
+pub enum PackageSource {
+    Git {
+        url: String,
+        rev: String,
+        subdir: String,
+    },
+    /// Relative to the root package.
+    Local(PathBuf),
+    Other,
+}
+
+pub struct LockedPackage {
+    pub id: String,
+    pub source: PackageSource,
+}
+
+/// A dependency and the addresses it resolves to on one environment.
+#[derive(Debug, Clone)]
+pub struct ResolvedDependency {
+    pub name: String,
+    /// The address its types and modules were first published at.
+    pub original_id: Option<String>,
+    /// The version of it this package links against.
+    pub published_at: Option<String>,
+}
+
+impl MoveLock {
+    /// Load `Move.lock` from `dir`, or `None` if there is none.
+    pub fn load(dir: &Path) -> Result<Option<Self>> {
+        let path = dir.join(MOVE_LOCK_FILE);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = fs::read_to_string(&path)?;
+        let value = toml::from_str(&content).map_err(|e| {
+            SuiForgeError::ConfigError(format!("Could not parse {}: {}", path.display(), e))
+        })?;
+        Ok(Some(Self { value }))
+    }
+
+    /// The compiler version that last wrote the lock file.
+    pub fn compiler_version(&self) -> Option<String> {
+        self.value
+            .get("move")?
+            .get("toolchain-version")?
+            .get("compiler-version")?
+            .as_str()
+            .map(str::to_string)
+    }
+
+    /// `(original-published-id, latest-published-id)` recorded for `env`.
+    pub fn published_ids(&self, env: &str) -> Option<(String, String)> {
+        let env = self.value.get("env")?.get(env)?;
+        let original = env.get("original-published-id")?.as_str()?;
+        let latest = env
+            .get("latest-published-id")
+            .and_then(|v| v.as_str())
+            .unwrap_or(original);
+        Some((original.to_string(), latest.to_string()))
+    }
+
+    /// Every package in the resolved dependency graph.
+    pub fn packages(&self) -> Vec<LockedPackage> {
+        let Some(packages) = self
+            .value
+            .get("move")
+            .and_then(|m| m.get("package"))
+            .and_then(|p| p.as_array())
+        else {
+            return Vec::new();
+        };
+
+        packages
+            .iter()
+            .filter_map(|package| {
+                let id = package.get("id").or_else(|| package.get("name"))?;
+                let source = package.get("source");
+                let field = |key: &str| {
+                    source
+                        .and_then(|s| s.get(key))
+                        .and_then(|v| v.as_str())
+                        .map(str::to_string)
+                };
+                let source = match (field("git"), field("local")) {
+                    (Some(url), _) => PackageSource::Git {
+                        url,
+                        rev: field("rev").unwrap_or_default(),
+                        subdir: field("subdir").unwrap_or_default(),
+                    },
+                    (None, Some(path)) => PackageSource::Local(PathBuf::from(path)),
+                    _ => PackageSource::Other,
+                };
+                Some(LockedPackage {
+                    id: id.as_str()?.to_string(),
+                    source,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Resolve the published address of every dependency of the package at
+/// `root` on `env`, reading each dependency's own `Move.lock` and
+/// `Move.toml` from where the CLI fetched it.
+pub fn resolve_dependencies(root: &Path, env: &str) -> Result<Vec<ResolvedDependency>> {
+    let Some(lock) = MoveLock::load(root)? else {
+        return Ok(Vec::new());
+    };
+
+    Ok(lock
+        .packages()
+        .into_iter()
+        .map(|package| {
+            if let Some((_, address)) = SYSTEM_PACKAGES.iter().find(|(n, _)| *n == package.id) {
+                return ResolvedDependency {
+                    name: package.id,
+                    original_id: Some(address.to_string()),
+                    published_at: Some(address.to_string()),
+                };
+            }
+
+            let (original_id, published_at) = source_dir(root, &package.source)
+                .map(|dir| published_addresses(&dir, env))
+                .unwrap_or((None, None));
+            ResolvedDependency {
+                name: package.id,
+                published_at: published_at.or_else(|| original_id.clone()),
+                original_id,
+            }
+        })
+        .collect())
+}
+
+fn source_dir(root: &Path, source: &PackageSource) -> Option<PathBuf> {
+    match source {
+        PackageSource::Local(path) => Some(root.join(path)),
+        PackageSource::Git { url, rev, subdir } => {
+            Some(move_home()?.join(git_checkout_name(url, rev)).join(subdir))
+        }
+        PackageSource::Other => None,
+    }
+}
+
+/// Directory name of a git dependency's checkout under `MOVE_HOME`, as the
+/// Move package resolver names it: `/`, `:`, `.` and `@` in the URL become
+/// `_`, and `/` in the revision becomes `__`.
+fn git_checkout_name(url: &str, rev: &str) -> String {
+    let url: String = url
+        .chars()
+        .map(|c| {
+            if matches!(c, '/' | ':' | '.' | '@') {
+                '_'
+            } else {
+                c
+            }
+        })
+        .collect();
+    format!("{}_{}", url, rev.replace('/', "__"))
+}
+
+fn move_home() -> Option<PathBuf> {
+    match std::env::var_os("MOVE_HOME") {
+        Some(dir) => Some(PathBuf::from(dir)),
+        None => home::home_dir().map(|home| home.join(".move")),
+    }
+}
+
+/// `(original, latest)` addresses of the package in `dir`, preferring its
+/// `Move.lock` entry for `env` over `Move.toml`.
+fn published_addresses(dir: &Path, env: &str) -> (Option<String>, Option<String>) {
+    if let Ok(Some(lock)) = MoveLock::load(dir) {
+        if let Some((original, latest)) = lock.published_ids(env) {
+            return (Some(original), Some(latest));
+        }
+    }
+
+    if !dir.join(MANIFEST_FILE).exists() {
+        return (None, None);
+    }
+    let Ok(manifest) = Manifest::load(dir) else {
+        return (None, None);
+    };
+
+    let published_at = manifest.published_at().ok().flatten();
+    // The package's own named address, by convention its lowercased name.
+    let addresses = manifest.addresses().unwrap_or_default();
+    let own = manifest
+        .package_name()
+        .ok()
+        .flatten()
+        .and_then(|name| addresses.get(&name.to_lowercase()).cloned());
+    let original =
+        own.filter(|a| is_hex_address(a) && canonical_address(a) != canonical_address("0x0"));
+
+    (original, published_at)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn git_checkouts_are_named_like_the_package_resolver() {
+        assert_eq!(
+            git_checkout_name("https://github.com/MystenLabs/sui.git", "framework/testnet"),
+            "https___github_com_MystenLabs_sui_git_framework__testnet"
+        );
+        assert_eq!(
+            git_checkout_name("git@github.com:acme/move-oracle.git", "v1.2"),
+            "git_github_com_acme_move-oracle_git_v1.2"
+        );
+    }
+
+    #[test]
+    fn local_dependencies_resolve_from_their_own_lock_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("app");
+        let oracle = dir.path().join("oracle");
+        fs::create_dir_all(&root).unwrap();
+        fs::create_dir_all(&oracle).unwrap();
+        fs::write(
+            root.join(MOVE_LOCK_FILE),
+            r#"
+[move]
+version = 3
+
+[[move.package]]
+id = "Sui"
+source = { git = "https://github.com/MystenLabs/sui.git", rev = "framework/testnet", subdir = "crates/sui-framework/packages/sui-framework" }
+
+[[move.package]]
+id = "Oracle"
+source = { local = "../oracle" }
+"#,
+        )
+        .unwrap();
+        fs::write(
+            oracle.join(MOVE_LOCK_FILE),
+            r#"
+[env.testnet]
+original-published-id = "0xa"
+latest-published-id = "0xa2"
+"#,
+        )
+        .unwrap();
+
+        let dependencies = resolve_dependencies(&root, "testnet").unwrap();
+        let resolved: Vec<(&str, Option<&str>, Option<&str>)> = dependencies
+            .iter()
+            .map(|d| {
+                (
+                    d.name.as_str(),
+                    d.original_id.as_deref(),
+                    d.published_at.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            resolved,
+            [
+                ("Sui", Some("0x2"), Some("0x2")),
+                ("Oracle", Some("0xa"), Some("0xa2")),
+            ]
+        );
+    }
+}
@@ -0,0 +1,264 @@
+use std::fmt;
+use std::path::PathBuf;
+
+/// Every module parsed from a directory of `.move` files.
+#[derive(Debug, Clone, Default)]
+pub struct Package {
+    pub modules: Vec<Module>,
+    /// Files that could not be parsed, with the reason.
+    pub errors: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Module {
+    /// Named or numeric address, exactly as written (`suiforge`, `0x2`).
+    pub address: String,
+    pub name: String,
+    pub path: PathBuf,
+    pub attributes: Vec<String>,
+    pub uses: Vec<UseDecl>,
+    pub constants: Vec<Constant>,
+    pub structs: Vec<StructDef>,
+    pub functions: Vec<Function>,
+}
+
+/// One imported name. `use sui::coin::{Self, Coin};` yields two entries:
+/// `coin` with no member and `Coin` with member `Coin`.
+#[derive(Debug, Clone)]
+pub struct UseDecl {
+    pub address: String,
+    pub module: String,
+    pub member: Option<String>,
+    pub alias: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct Constant {
+    pub name: String,
+    pub ty: Type,
+    /// Source text of the initializer, e.g. `1` or `b"hello"`.
+    pub value: String,
+    pub line: usize,
+    pub doc: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Ability {
+    Copy,
+    Drop,
+    Store,
+    Key,
+}
+
+impl Ability {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "copy" => Some(Ability::Copy),
+            "drop" => Some(Ability::Drop),
+            "store" => Some(Ability::Store),
+            "key" => Some(Ability::Key),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Ability::Copy => "copy",
+            Ability::Drop => "drop",
+            Ability::Store => "store",
+            Ability::Key => "key",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TypeParam {
+    pub name: String,
+    pub is_phantom: bool,
+    pub constraints: Vec<Ability>,
+}
+
+#[derive(Debug, Clone)]
+pub struct StructDef {
+    pub name: String,
+    pub abilities: Vec<Ability>,
+    pub type_params: Vec<TypeParam>,
+    /// Positional structs (`struct S(u64)`) get fields named `pos0`, `pos1`, ...
+    pub fields: Vec<Field>,
+    pub doc: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Field {
+    pub name: String,
+    pub ty: Type,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Type {
+    /// A (possibly qualified) type name with type arguments:
+    /// `u64`, `Coin<SUI>`, `sui::table::Table<address, Listing>`.
+    Named {
+        path: Vec<String>,
+        args: Vec<Type>,
+    },
+    Ref {
+        mutable: bool,
+        inner: Box<Type>,
+    },
+    Tuple(Vec<Type>),
+}
+
+impl Type {
+    /// Last path segment of a named type, looking through references.
+    pub fn base_name(&self) -> Option<&str> {
+        match self {
+            Type::Named { path, .. } => path.last().map(|s| s.as_str()),
+            Type::Ref { inner, .. } => inner.base_name(),
+            Type::Tuple(_) => None,
+        }
+    }
+
+    pub fn is_mut_ref(&self) -> bool {
+        matches!(self, Type::Ref { mutable: true, .. })
+    }
+}
+
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Type::Named { path, args } => {
+                write!(f, "{}", path.join("::"))?;
+                if !args.is_empty() {
+                    let args: Vec<String> = args.iter().map(|a| a.to_string()).collect();
+                    write!(f, "<{}>", args.join(", "))?;
+                }
+                Ok(())
+            }
+            Type::Ref { mutable, inner } => {
+                write!(f, "&{}{}", if *mutable { "mut " } else { "" }, inner)
+            }
+            Type::Tuple(items) => {
+                let items: Vec<String> = items.iter().map(|a| a.to_string()).collect();
+                write!(f, "({})", items.join(", "))
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Visibility {
+    Private,
+    Public,
+    /// `public(package)`
+    Package,
+    /// `public(friend)`
+    Friend,
+}
+
+#[derive(Debug, Clone)]
+pub struct Param {
+    pub name: String,
+    pub ty: Type,
+}
+
+#[derive(Debug, Clone)]
+pub struct Function {
+    pub name: String,
+    pub visibility: Visibility,
+    pub is_entry: bool,
+    pub is_native: bool,
+    pub is_macro: bool,
+    pub attributes: Vec<String>,
+    pub type_params: Vec<TypeParam>,
+    pub params: Vec<Param>,
+    pub returns: Vec<Type>,
+    pub body: Option<Body>,
+    pub line: usize,
+    pub end_line: usize,
+    pub doc: Option<String>,
+}
+
+impl Function {
+    pub fn is_test(&self) -> bool {
+        self.attributes
+            .iter()
+            .any(|a| a == "test" || a == "random_test")
+    }
+
+    pub fn is_test_only(&self) -> bool {
+        self.attributes.iter().any(|a| a == "test_only")
+    }
+
+    /// Number of source lines spanned by the declaration and body.
+    pub fn line_count(&self) -> usize {
+        self.end_line.saturating_sub(self.line) + 1
+    }
+}
+
+/// Facts extracted from a function body. Expressions are not modelled as a
+/// tree; instead every call, binding, assertion, abort and field write is
+/// recorded with its line, which is what the analysis commands need.
+#[derive(Debug, Clone, Default)]
+pub struct Body {
+    pub calls: Vec<CallSite>,
+    pub lets: Vec<LetBinding>,
+    pub asserts: Vec<AssertSite>,
+    pub aborts: Vec<AbortSite>,
+    /// Lines of `x.f = ...` style writes to fields.
+    pub field_writes: Vec<usize>,
+    /// Line ranges (inclusive) covered by `while`, `loop` and iteration macros.
+    pub loops: Vec<(usize, usize)>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CallSite {
+    /// Qualifier before the function name (`transfer`, `sui::transfer`),
+    /// empty for local and method calls.
+    pub module: Vec<String>,
+    pub function: String,
+    pub is_method: bool,
+    /// Receiver expression for method calls (`self.balance` in `self.balance.join(x)`).
+    pub receiver: Option<String>,
+    /// Source text of each top-level argument.
+    pub args: Vec<String>,
+    pub line: usize,
+}
+
+impl CallSite {
+    /// `module::function` for qualified calls, the bare name otherwise.
+    pub fn qualified_name(&self) -> String {
+        match self.module.last() {
+            Some(module) => format!("{}::{}", module, self.function),
+            None => self.function.clone(),
+        }
+    }
+
+    /// Whether this call targets `module::function`, whether written as a
+    /// qualified call or as a method call.
+    pub fn targets(&self, module: &str, function: &str) -> bool {
+        self.function == function
+            && (self.module.last().map(|m| m == module).unwrap_or(false) || self.is_method)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LetBinding {
+    pub line: usize,
+    /// Names that never appear again in the rest of the body.
+    pub unused: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct AssertSite {
+    pub condition: String,
+    /// Abort code expression, absent for `assert!(cond)`.
+    pub code: Option<String>,
+    pub line: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct AbortSite {
+    pub code: String,
+    pub line: usize,
+}
@@ -0,0 +1,198 @@
+use crate::error::{Result, SuiForgeError};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TokenKind {
+    Ident,
+    Number,
+    /// `b"..."` or `x"..."` literals.
+    ByteString,
+    Punct,
+}
+
+#[derive(Debug, Clone)]
+pub struct Token {
+    pub kind: TokenKind,
+    pub text: String,
+    pub line: usize,
+    /// `///` doc comment lines immediately preceding this token.
+    pub doc: Option<String>,
+}
+
+impl Token {
+    pub fn is(&self, text: &str) -> bool {
+        self.kind != TokenKind::ByteString && self.text == text
+    }
+
+    pub fn is_ident(&self) -> bool {
+        self.kind == TokenKind::Ident
+    }
+}
+
+const TWO_CHAR_PUNCT: [&str; 10] = ["::", "==", "!=", "<=", ">=", "&&", "||", "..", "->", "=>"];
+
+pub fn tokenize(source: &str) -> Result<Vec<Token>> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut pending_doc: Vec<String> = Vec::new();
+    let mut line = 1;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+
+        if c == '\n' {
+            line += 1;
+            i += 1;
+            continue;
+        }
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+
+        // Comments
+        if c == '/' && chars.get(i + 1) == Some(&'/') {
+            let start = i;
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+            let text: String = chars[start..i].iter().collect();
+            if let Some(doc) = text.strip_prefix("///") {
+                pending_doc.push(doc.trim().to_string());
+            }
+            continue;
+        }
+        if c == '/' && chars.get(i + 1) == Some(&'*') {
+            let start_line = line;
+            let mut depth = 0;
+            while i < chars.len() {
+                if chars[i] == '/' && chars.get(i + 1) == Some(&'*') {
+                    depth += 1;
+                    i += 2;
+                } else if chars[i] == '*' && chars.get(i + 1) == Some(&'/') {
+                    depth -= 1;
+                    i += 2;
+                    if depth == 0 {
+                        break;
+                    }
+                } else {
+                    if chars[i] == '\n' {
+                        line += 1;
+                    }
+                    i += 1;
+                }
+            }
+            if depth != 0 {
+                return Err(SuiForgeError::MoveParse(format!(
+                    "unterminated block comment starting on line {}",
+                    start_line
+                )));
+            }
+            continue;
+        }
+
+        let start = i;
+        let token_line = line;
+        let kind = if (c == 'b' || c == 'x') && chars.get(i + 1) == Some(&'"') {
+            i += 2;
+            while i < chars.len() && chars[i] != '"' {
+                if chars[i] == '\\' {
+                    i += 1;
+                }
+                if chars.get(i) == Some(&'\n') {
+                    line += 1;
+                }
+                i += 1;
+            }
+            if i >= chars.len() {
+                return Err(SuiForgeError::MoveParse(format!(
+                    "unterminated string literal on line {}",
+                    line
+                )));
+            }
+            i += 1;
+            TokenKind::ByteString
+        } else if c.is_ascii_alphabetic() || c == '_' {
+            while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            TokenKind::Ident
+        } else if c.is_ascii_digit() {
+            while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            TokenKind::Number
+        } else {
+            let two: String = chars[i..(i + 2).min(chars.len())].iter().collect();
+            if TWO_CHAR_PUNCT.contains(&two.as_str()) {
+                i += 2;
+            } else {
+                i += 1;
+            }
+            TokenKind::Punct
+        };
+
+        let doc = if pending_doc.is_empty() {
+            None
+        } else {
+            Some(std::mem::take(&mut pending_doc).join("\n"))
+        };
+
+        tokens.push(Token {
+            kind,
+            text: chars[start..i].iter().collect(),
+            line: token_line,
+            doc,
+        });
+    }
+
+    Ok(tokens)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texts(tokens: &[Token]) -> Vec<&str> {
+        tokens.iter().map(|t| t.text.as_str()).collect()
+    }
+
+    #[test]
+    fn line_and_block_comments_are_skipped() {
+        let tokens = tokenize("a // line comment\nb /* block\ncomment */ c").unwrap();
+        assert_eq!(texts(&tokens), vec!["a", "b", "c"]);
+        assert_eq!(
+            tokens.iter().map(|t| t.line).collect::<Vec<_>>(),
+            vec![1, 2, 3]
+        );
+    }
+
+    #[test]
+    fn nested_block_comments_end_at_the_outer_close() {
+        let tokens = tokenize("a /* outer /* inner */ still comment */ b").unwrap();
+        assert_eq!(texts(&tokens), vec!["a", "b"]);
+    }
+
+    #[test]
+    fn unterminated_block_comment_is_an_error() {
+        let error = tokenize("a\n/* outer /* inner */\nb").unwrap_err();
+        assert!(error.to_string().contains("line 2"), "{}", error);
+    }
+
+    #[test]
+    fn doc_comments_attach_to_the_next_token() {
+        let tokens = tokenize("/// First line.\n/// Second line.\n// plain\nfun f()").unwrap();
+        assert_eq!(tokens[0].doc.as_deref(), Some("First line.\nSecond line."));
+        assert!(tokens[1].doc.is_none());
+    }
+
+    #[test]
+    fn two_character_punctuation_is_one_token() {
+        let tokens = tokenize("0x2::coin::Coin<T> ==").unwrap();
+        assert_eq!(
+            texts(&tokens),
+            vec!["0x2", "::", "coin", "::", "Coin", "<", "T", ">", "=="]
+        );
+        assert_eq!(tokens[0].kind, TokenKind::Number);
+    }
+}
@@ -0,0 +1,98 @@
+//! Move source model shared by the analysis commands.
+//!
+//! Sources are tokenized and parsed into modules, structs, functions and a
+//! per-function summary of the body (calls, bindings, assertions, aborts,
+//! field writes and loops). The parser is tolerant: items it does not model
+//! (specs, enums, `use fun`) are skipped, and a file that fails to parse is
+//! reported in [`Package::errors`] without aborting the whole analysis.
+
+pub mod ast;
+mod lexer;
+mod parser;
+
+pub use ast::*;
+
+use crate::error::Result;
+use std::fs;
+use std::path::Path;
+
+/// Parse every module in a single source file.
+pub fn parse_source(path: &Path, source: &str) -> Result<Vec<Module>> {
+    let tokens = lexer::tokenize(source)?;
+    parser::Parser::new(&tokens, path).parse_file()
+}
+
+impl Package {
+    /// Parse all `.move` files under `dir`. A missing directory yields an
+    /// empty package.
+    pub fn load(dir: &Path) -> Result<Self> {
+        let mut package = Package::default();
+        if !dir.exists() {
+            return Ok(package);
+        }
+
+        let mut paths: Vec<_> = walkdir::WalkDir::new(dir)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.path().extension().is_some_and(|ext| ext == "move"))
+            .map(|e| e.into_path())
+            .collect();
+        paths.sort();
+
+        for path in paths {
+            let source = fs::read_to_string(&path)?;
+            match parse_source(&path, &source) {
+                Ok(modules) => package.modules.extend(modules),
+                Err(e) => package.errors.push(e.to_string()),
+            }
+        }
+
+        Ok(package)
+    }
+
+    /// All functions paired with the module that declares them.
+    pub fn functions(&self) -> impl Iterator<Item = (&Module, &Function)> {
+        self.modules
+            .iter()
+            .flat_map(|m| m.functions.iter().map(move |f| (m, f)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn load_keeps_parsing_after_a_broken_file() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("a_broken.move"),
+            "module demo::broken {\n    fun f( {\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("b_good.move"),
+            "module demo::good {\n    public fun ok(): u64 { 1 }\n}\n",
+        )
+        .unwrap();
+
+        let package = Package::load(dir.path()).unwrap();
+
+        assert_eq!(package.modules.len(), 1);
+        assert_eq!(package.modules[0].name, "good");
+        assert_eq!(package.errors.len(), 1);
+        assert!(
+            package.errors[0].contains("a_broken.move"),
+            "{}",
+            package.errors[0]
+        );
+    }
+
+    #[test]
+    fn load_of_a_missing_directory_is_empty() {
+        let dir = tempfile::tempdir().unwrap();
+        let package = Package::load(&dir.path().join("sources")).unwrap();
+        assert!(package.modules.is_empty());
+        assert!(package.errors.is_empty());
+    }
+}
@@ -0,0 +1,1173 @@
+use super::ast::*;
+use super::lexer::{Token, TokenKind};
+use crate::error::{Result, SuiForgeError};
+use std::path::{Path, PathBuf};
+
+/// Keywords that can be directly followed by `(` without being a call.
+const NON_CALL_KEYWORDS: [&str; 8] = [
+    "if", "while", "return", "abort", "loop", "match", "let", "else",
+];
+
+/// Iteration macros from `std::vector` and friends whose lambda bodies run
+/// once per element.
+const LOOP_MACROS: [&str; 8] = [
+    "do", "do_ref", "do_mut", "for_each", "map", "map_ref", "filter", "fold",
+];
+
+pub struct Parser<'a> {
+    tokens: &'a [Token],
+    pos: usize,
+    path: PathBuf,
+}
+
+impl<'a> Parser<'a> {
+    pub fn new(tokens: &'a [Token], path: &Path) -> Self {
+        Self {
+            tokens,
+            pos: 0,
+            path: path.to_path_buf(),
+        }
+    }
+
+    // -----------------------------------------------------------------------
+    // Token helpers
+    // -----------------------------------------------------------------------
+
+    fn peek(&self) -> Option<&'a Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn peek_is(&self, text: &str) -> bool {
+        self.peek().map(|t| t.is(text)).unwrap_or(false)
+    }
+
+    fn next(&mut self) -> Option<&'a Token> {
+        let token = self.tokens.get(self.pos);
+        if token.is_some() {
+            self.pos += 1;
+        }
+        token
+    }
+
+    fn eat(&mut self, text: &str) -> bool {
+        if self.peek_is(text) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn line(&self) -> usize {
+        self.peek()
+            .or_else(|| self.tokens.last())
+            .map(|t| t.line)
+            .unwrap_or(1)
+    }
+
+    fn error(&self, message: impl AsRef<str>) -> SuiForgeError {
+        SuiForgeError::MoveParse(format!(
+            "{}:{}: {}",
+            self.path.display(),
+            self.line(),
+            message.as_ref()
+        ))
+    }
+
+    fn expect(&mut self, text: &str) -> Result<&'a Token> {
+        match self.peek() {
+            Some(t) if t.is(text) => {
+                self.pos += 1;
+                Ok(t)
+            }
+            Some(t) => Err(self.error(format!("expected `{}`, found `{}`", text, t.text))),
+            None => Err(self.error(format!("expected `{}`, found end of file", text))),
+        }
+    }
+
+    fn expect_ident(&mut self) -> Result<String> {
+        match self.peek() {
+            Some(t) if t.is_ident() => {
+                self.pos += 1;
+                Ok(t.text.clone())
+            }
+            Some(t) => Err(self.error(format!("expected identifier, found `{}`", t.text))),
+            None => Err(self.error("expected identifier, found end of file")),
+        }
+    }
+
+    /// Index of the token closing the bracket opened at `open`.
+    fn matching(&self, open: usize) -> Result<usize> {
+        let (open_text, close_text) = match self.tokens[open].text.as_str() {
+            "{" => ("{", "}"),
+            "(" => ("(", ")"),
+            "[" => ("[", "]"),
+            "<" => ("<", ">"),
+            other => return Err(self.error(format!("`{}` is not a bracket", other))),
+        };
+        let mut depth = 0;
+        for (i, token) in self.tokens.iter().enumerate().skip(open) {
+            if token.is(open_text) {
+                depth += 1;
+            } else if token.is(close_text) {
+                depth -= 1;
+                if depth == 0 {
+                    return Ok(i);
+                }
+            }
+        }
+        Err(SuiForgeError::MoveParse(format!(
+            "{}:{}: unclosed `{}`",
+            self.path.display(),
+            self.tokens[open].line,
+            open_text
+        )))
+    }
+
+    /// Skip an item we do not model: up to and including the next `;` at
+    /// depth zero, or a balanced `{ ... }` block (plus a trailing `has ...;`).
+    fn skip_item(&mut self) -> Result<()> {
+        while let Some(token) = self.peek() {
+            if token.is(";") {
+                self.pos += 1;
+                return Ok(());
+            }
+            if token.is("{") || token.is("(") {
+                let close = self.matching(self.pos)?;
+                let was_brace = token.is("{");
+                self.pos = close + 1;
+                if was_brace {
+                    if self.peek_is("has") {
+                        while let Some(t) = self.next() {
+                            if t.is(";") {
+                                break;
+                            }
+                        }
+                    }
+                    return Ok(());
+                }
+                continue;
+            }
+            if token.is("}") {
+                return Ok(());
+            }
+            self.pos += 1;
+        }
+        Ok(())
+    }
+
+    fn attributes(&mut self) -> Result<Vec<String>> {
+        let mut attributes = Vec::new();
+        while self.peek_is("#") && self.tokens.get(self.pos + 1).map(|t| t.is("[")) == Some(true) {
+            let close = self.matching(self.pos + 1)?;
+            let mut depth = 0;
+            for token in &self.tokens[self.pos + 2..close] {
+                match token.text.as_str() {
+                    "(" => depth += 1,
+                    ")" => depth -= 1,
+                    _ if depth == 0 && token.is_ident() => attributes.push(token.text.clone()),
+                    _ => {}
+                }
+            }
+            self.pos = close + 1;
+        }
+        Ok(attributes)
+    }
+
+    // -----------------------------------------------------------------------
+    // Items
+    // -----------------------------------------------------------------------
+
+    pub fn parse_file(&mut self) -> Result<Vec<Module>> {
+        let mut modules = Vec::new();
+
+        while self.peek().is_some() {
+            let attributes = self.attributes()?;
+            if self.eat("module") {
+                modules.push(self.module(attributes, None)?);
+            } else if self.eat("address") {
+                let address = self.next().map(|t| t.text.clone()).unwrap_or_default();
+                self.expect("{")?;
+                while !self.eat("}") {
+                    if self.peek().is_none() {
+                        return Err(self.error("unclosed address block"));
+                    }
+                    let attributes = self.attributes()?;
+                    if self.eat("module") {
+                        modules.push(self.module(attributes, Some(address.clone()))?);
+                    } else {
+                        self.skip_item()?;
+                    }
+                }
+            } else if self.peek().is_some() {
+                self.skip_item()?;
+                // A stray `}` would otherwise stall the loop.
+                self.eat("}");
+            }
+        }
+
+        Ok(modules)
+    }
+
+    fn module(&mut self, attributes: Vec<String>, address: Option<String>) -> Result<Module> {
+        let first = self
+            .next()
+            .ok_or_else(|| self.error("expected module name"))?
+            .text
+            .clone();
+        let (address, name) = if self.eat("::") {
+            (first, self.expect_ident()?)
+        } else {
+            (address.unwrap_or_default(), first)
+        };
+
+        let mut module = Module {
+            address,
+            name,
+            path: self.path.clone(),
+            attributes,
+            uses: Vec::new(),
+            constants: Vec::new(),
+            structs: Vec::new(),
+            functions: Vec::new(),
+        };
+
+        if self.eat(";") {
+            // Move 2024 file-level module: members run to the end of the file.
+            self.members(&mut module, false)?;
+        } else {
+            self.expect("{")?;
+            self.members(&mut module, true)?;
+        }
+
+        Ok(module)
+    }
+
+    fn members(&mut self, module: &mut Module, braced: bool) -> Result<()> {
+        loop {
+            match self.peek() {
+                None if braced => return Err(self.error("unclosed module body")),
+                None => return Ok(()),
+                Some(t) if braced && t.is("}") => {
+                    self.pos += 1;
+                    return Ok(());
+                }
+                _ => {}
+            }
+
+            let doc = self.peek().and_then(|t| t.doc.clone());
+            let attributes = self.attributes()?;
+            let doc = doc.or_else(|| self.peek().and_then(|t| t.doc.clone()));
+            let start_line = self.line();
+
+            let mut visibility = Visibility::Private;
+            let mut is_entry = false;
+            let mut is_native = false;
+            let mut is_macro = false;
+            loop {
+                if self.eat("public") {
+                    visibility = Visibility::Public;
+                    if self.eat("(") {
+                        visibility = match self.expect_ident()?.as_str() {
+                            "package" => Visibility::Package,
+                            "friend" => Visibility::Friend,
+                            _ => Visibility::Public,
+                        };
+                        self.expect(")")?;
+                    }
+                } else if self.eat("entry") {
+                    is_entry = true;
+                } else if self.eat("native") {
+                    is_native = true;
+                } else if self.eat("macro") {
+                    is_macro = true;
+                } else {
+                    break;
+                }
+            }
+
+            let keyword = match self.peek() {
+                Some(t) => t.text.clone(),
+                None => continue,
+            };
+
+            match keyword.as_str() {
+                "use" => {
+                    self.pos += 1;
+                    if self.peek_is("fun") {
+                        self.skip_item()?;
+                    } else {
+                        self.use_tree(Vec::new(), &mut module.uses)?;
+                        self.expect(";")?;
+                    }
+                }
+                "const" => {
+                    self.pos += 1;
+                    let constant = self.constant(start_line, doc)?;
+                    module.constants.push(constant);
+                }
+                "struct" => {
+                    self.pos += 1;
+                    let def = self.struct_def(doc)?;
+                    module.structs.push(def);
+                }
+                "fun" => {
+                    self.pos += 1;
+                    let function = self.function(FunctionHeader {
+                        visibility,
+                        is_entry,
+                        is_native,
+                        is_macro,
+                        attributes,
+                        line: start_line,
+                        doc,
+                    })?;
+                    module.functions.push(function);
+                }
+                "module" if !braced => return Ok(()),
+                _ => {
+                    let before = self.pos;
+                    self.skip_item()?;
+                    if self.pos == before {
+                        self.pos += 1;
+                    }
+                }
+            }
+        }
+    }
+
+    fn use_tree(&mut self, prefix: Vec<String>, out: &mut Vec<UseDecl>) -> Result<()> {
+        if self.eat("{") {
+            while !self.eat("}") {
+                self.use_tree(prefix.clone(), out)?;
+                if !self.eat(",") && !self.peek_is("}") {
+                    return Err(self.error("expected `,` or `}` in use group"));
+                }
+            }
+            return Ok(());
+        }
+
+        let mut path = prefix;
+        path.push(
+            self.next()
+                .ok_or_else(|| self.error("expected use path"))?
+                .text
+                .clone(),
+        );
+        while self.eat("::") {
+            if self.peek_is("{") {
+                return self.use_tree(path, out);
+            }
+            path.push(self.expect_ident()?);
+        }
+        let alias = if self.eat("as") {
+            Some(self.expect_ident()?)
+        } else {
+            None
+        };
+
+        match path.as_slice() {
+            [address, module] => out.push(UseDecl {
+                address: address.clone(),
+                module: module.clone(),
+                member: None,
+                alias: alias.unwrap_or_else(|| module.clone()),
+            }),
+            [address, module, member] if member == "Self" => out.push(UseDecl {
+                address: address.clone(),
+                module: module.clone(),
+                member: None,
+                alias: alias.unwrap_or_else(|| module.clone()),
+            }),
+            [address, module, member] => out.push(UseDecl {
+                address: address.clone(),
+                module: module.clone(),
+                member: Some(member.clone()),
+                alias: alias.unwrap_or_else(|| member.clone()),
+            }),
+            _ => return Err(self.error(format!("unsupported use path `{}`", path.join("::")))),
+        }
+        Ok(())
+    }
+
+    fn constant(&mut self, line: usize, doc: Option<String>) -> Result<Constant> {
+        let name = self.expect_ident()?;
+        self.expect(":")?;
+        let ty = self.ty()?;
+        self.expect("=")?;
+        let start = self.pos;
+        let mut depth = 0i32;
+        while let Some(token) = self.peek() {
+            match token.text.as_str() {
+                "(" | "[" | "{" => depth += 1,
+                ")" | "]" | "}" => depth -= 1,
+                ";" if depth == 0 => break,
+                _ => {}
+            }
+            self.pos += 1;
+        }
+        let value = render(&self.tokens[start..self.pos]);
+        self.expect(";")?;
+        Ok(Constant {
+            name,
+            ty,
+            value,
+            line,
+            doc,
+        })
+    }
+
+    /// Ability list separated by `separator`: `,` after `has`, `+` in
+    /// type parameter constraints.
+    fn abilities(&mut self, separator: &str) -> Result<Vec<Ability>> {
+        let mut abilities = Vec::new();
+        loop {
+            let name = self.expect_ident()?;
+            match Ability::parse(&name) {
+                Some(ability) => abilities.push(ability),
+                None => return Err(self.error(format!("unknown ability `{}`", name))),
+            }
+            if !self.eat(separator) {
+                break;
+            }
+        }
+        Ok(abilities)
+    }
+
+    fn type_params(&mut self) -> Result<Vec<TypeParam>> {
+        let mut params = Vec::new();
+        if !self.eat("<") {
+            return Ok(params);
+        }
+        while !self.eat(">") {
+            let is_phantom = self.eat("phantom");
+            let mut name = String::new();
+            if self.eat("$") {
+                name.push('$');
+            }
+            name.push_str(&self.expect_ident()?);
+            let constraints = if self.eat(":") {
+                self.abilities("+")?
+            } else {
+                Vec::new()
+            };
+            params.push(TypeParam {
+                name,
+                is_phantom,
+                constraints,
+            });
+            if !self.eat(",") && !self.peek_is(">") {
+                return Err(self.error("expected `,` or `>` in type parameters"));
+            }
+        }
+        Ok(params)
+    }
+
+    fn struct_def(&mut self, doc: Option<String>) -> Result<StructDef> {
+        let name = self.expect_ident()?;
+        let type_params = self.type_params()?;
+        let mut abilities = if self.eat("has") {
+            self.abilities(",")?
+        } else {
+            Vec::new()
+        };
+
+        let mut fields = Vec::new();
+        if self.eat("{") {
+            while !self.eat("}") {
+                let field_name = self.expect_ident()?;
+                self.expect(":")?;
+                let ty = self.ty()?;
+                fields.push(Field {
+                    name: field_name,
+                    ty,
+                });
+                if !self.eat(",") && !self.peek_is("}") {
+                    return Err(self.error("expected `,` or `}` in struct fields"));
+                }
+            }
+        } else if self.eat("(") {
+            let mut index = 0;
+            while !self.eat(")") {
+                let ty = self.ty()?;
+                fields.push(Field {
+                    name: format!("pos{}", index),
+                    ty,
+                });
+                index += 1;
+                if !self.eat(",") && !self.peek_is(")") {
+                    return Err(self.error("expected `,` or `)` in positional fields"));
+                }
+            }
+        }
+
+        if self.eat("has") {
+            abilities.extend(self.abilities(",")?);
+        }
+        self.eat(";");
+
+        Ok(StructDef {
+            name,
+            abilities,
+            type_params,
+            fields,
+            doc,
+        })
+    }
+
+    fn function(&mut self, header: FunctionHeader) -> Result<Function> {
+        let name = self.expect_ident()?;
+        let type_params = self.type_params()?;
+
+        let params_open = self.pos;
+        self.expect("(")?;
+        let mut params = Vec::new();
+        if header.is_macro {
+            // Macro parameters may carry lambda types; they are not modelled.
+            self.pos = self.matching(params_open)? + 1;
+        } else {
+            while !self.eat(")") {
+                self.eat("mut");
+                let param_name = self.expect_ident()?;
+                self.expect(":")?;
+                let ty = self.ty()?;
+                params.push(Param {
+                    name: param_name,
+                    ty,
+                });
+                if !self.eat(",") && !self.peek_is(")") {
+                    return Err(self.error("expected `,` or `)` in parameters"));
+                }
+            }
+        }
+
+        let mut returns = Vec::new();
+        if self.eat(":") {
+            match self.ty()? {
+                Type::Tuple(items) => returns = items,
+                ty => returns.push(ty),
+            }
+        }
+
+        if self.eat("acquires") {
+            while self
+                .peek()
+                .map(|t| !t.is("{") && !t.is(";"))
+                .unwrap_or(false)
+            {
+                self.pos += 1;
+            }
+        }
+
+        let (body, end_line) = if self.peek_is("{") {
+            let open = self.pos;
+            let close = self.matching(open)?;
+            let body = analyze_body(&self.tokens[open + 1..close], self.tokens[close].line);
+            self.pos = close + 1;
+            (Some(body), self.tokens[close].line)
+        } else {
+            let end_line = self.line();
+            self.expect(";")?;
+            (None, end_line)
+        };
+
+        Ok(Function {
+            name,
+            visibility: header.visibility,
+            is_entry: header.is_entry,
+            is_native: header.is_native,
+            is_macro: header.is_macro,
+            attributes: header.attributes,
+            type_params,
+            params,
+            returns,
+            body,
+            line: header.line,
+            end_line,
+            doc: header.doc,
+        })
+    }
+
+    fn ty(&mut self) -> Result<Type> {
+        if self.eat("&") {
+            let mutable = self.eat("mut");
+            let inner = self.ty()?;
+            return Ok(Type::Ref {
+                mutable,
+                inner: Box::new(inner),
+            });
+        }
+
+        if self.eat("(") {
+            let mut items = Vec::new();
+            while !self.eat(")") {
+                items.push(self.ty()?);
+                if !self.eat(",") && !self.peek_is(")") {
+                    return Err(self.error("expected `,` or `)` in tuple type"));
+                }
+            }
+            return Ok(Type::Tuple(items));
+        }
+
+        if self.peek_is("|") {
+            // Lambda type in a macro signature.
+            self.pos += 1;
+            while let Some(token) = self.next() {
+                if token.is("|") {
+                    break;
+                }
+            }
+            if self.eat("->") {
+                self.ty()?;
+            }
+            return Ok(Type::Named {
+                path: vec!["|lambda|".to_string()],
+                args: Vec::new(),
+            });
+        }
+
+        let mut path = Vec::new();
+        loop {
+            let mut segment = String::new();
+            if self.eat("$") {
+                segment.push('$');
+            }
+            match self.peek() {
+                Some(t) if t.is_ident() || t.kind == TokenKind::Number => {
+                    segment.push_str(&t.text);
+                    self.pos += 1;
+                }
+                Some(t) => return Err(self.error(format!("expected type, found `{}`", t.text))),
+                None => return Err(self.error("expected type, found end of file")),
+            }
+            path.push(segment);
+            if !self.eat("::") {
+                break;
+            }
+        }
+
+        let mut args = Vec::new();
+        if self.eat("<") {
+            while !self.eat(">") {
+                args.push(self.ty()?);
+                if !self.eat(",") && !self.peek_is(">") {
+                    return Err(self.error("expected `,` or `>` in type arguments"));
+                }
+            }
+        }
+
+        Ok(Type::Named { path, args })
+    }
+}
+
+struct FunctionHeader {
+    visibility: Visibility,
+    is_entry: bool,
+    is_native: bool,
+    is_macro: bool,
+    attributes: Vec<String>,
+    line: usize,
+    doc: Option<String>,
+}
+
+// ---------------------------------------------------------------------------
+// Function bodies
+// ---------------------------------------------------------------------------
+
+/// Index of the token closing the bracket at `open` within `tokens`.
+fn close_of(tokens: &[Token], open: usize) -> Option<usize> {
+    let (open_text, close_text) = match tokens[open].text.as_str() {
+        "{" => ("{", "}"),
+        "(" => ("(", ")"),
+        "[" => ("[", "]"),
+        _ => return None,
+    };
+    let mut depth = 0;
+    for (i, token) in tokens.iter().enumerate().skip(open) {
+        if token.is(open_text) {
+            depth += 1;
+        } else if token.is(close_text) {
+            depth -= 1;
+            if depth == 0 {
+                return Some(i);
+            }
+        }
+    }
+    None
+}
+
+/// If `tokens[start]` is `<`, the index just past a plausible type-argument
+/// list. Bails out on tokens that only appear in expressions so that
+/// comparisons like `a < b` are not mistaken for generics.
+fn skip_type_args(tokens: &[Token], start: usize) -> Option<usize> {
+    if !tokens.get(start)?.is("<") {
+        return Some(start);
+    }
+    let mut depth = 0;
+    for (i, token) in tokens.iter().enumerate().skip(start) {
+        match token.text.as_str() {
+            "<" => depth += 1,
+            ">" => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i + 1);
+                }
+            }
+            "::" | "," | "&" | "mut" => {}
+            _ if token.is_ident() || token.kind == TokenKind::Number => {}
+            _ => return None,
+        }
+    }
+    None
+}
+
+/// Split the tokens between a pair of brackets on top-level commas.
+fn split_args(tokens: &[Token]) -> Vec<&[Token]> {
+    let mut args = Vec::new();
+    let mut depth = 0i32;
+    let mut start = 0;
+    for (i, token) in tokens.iter().enumerate() {
+        match token.text.as_str() {
+            "(" | "[" | "{" => depth += 1,
+            ")" | "]" | "}" => depth -= 1,
+            "," if depth == 0 && token.kind == TokenKind::Punct => {
+                args.push(&tokens[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    if start < tokens.len() {
+        args.push(&tokens[start..]);
+    }
+    args
+}
+
+/// Re-create readable source text from a token slice.
+pub fn render(tokens: &[Token]) -> String {
+    let mut out = String::new();
+    let mut prev: Option<&Token> = None;
+    for token in tokens {
+        if prev.map(|p| needs_space(p, token)).unwrap_or(false) {
+            out.push(' ');
+        }
+        out.push_str(&token.text);
+        prev = Some(token);
+    }
+    out
+}
+
+fn needs_space(prev: &Token, next: &Token) -> bool {
+    if matches!(next.text.as_str(), "::" | "." | "," | ")" | "]" | ";" | ">") {
+        return false;
+    }
+    if matches!(
+        prev.text.as_str(),
+        "::" | "." | "(" | "[" | "@" | "&" | "!" | "<"
+    ) {
+        return false;
+    }
+    if next.is("(") || next.is("<") || next.is("!") {
+        return !(prev.is_ident() || prev.is(">") || prev.is("!"));
+    }
+    true
+}
+
+fn analyze_body(tokens: &[Token], closing_line: usize) -> Body {
+    let mut body = Body::default();
+    let mut let_equals = Vec::new();
+
+    for (i, token) in tokens.iter().enumerate() {
+        let range_start = if token.is("while") || token.is("loop") || token.is("for") {
+            tokens[i..]
+                .iter()
+                .position(|t| t.is("{"))
+                .map(|offset| i + offset)
+        } else if token.is_ident()
+            && LOOP_MACROS.contains(&token.text.as_str())
+            && tokens.get(i + 1).map(|t| t.is("!")) == Some(true)
+            && tokens.get(i + 2).map(|t| t.is("(")) == Some(true)
+        {
+            Some(i + 2)
+        } else {
+            None
+        };
+
+        if let Some(open) = range_start {
+            let end_line = close_of(tokens, open)
+                .map(|close| tokens[close].line)
+                .unwrap_or(closing_line);
+            body.loops.push((token.line, end_line));
+        }
+    }
+
+    for (i, token) in tokens.iter().enumerate() {
+        if token.is("let") {
+            let mut depth = 0i32;
+            let mut end = i + 1;
+            while let Some(t) = tokens.get(end) {
+                match t.text.as_str() {
+                    "(" | "{" | "[" => depth += 1,
+                    ")" | "}" | "]" => depth -= 1,
+                    "=" | ";" | ":" if depth == 0 => break,
+                    _ => {}
+                }
+                end += 1;
+            }
+            let pattern = &tokens[i + 1..end.min(tokens.len())];
+            let names: Vec<String> = pattern
+                .iter()
+                .enumerate()
+                .filter(|(j, t)| {
+                    let next = pattern.get(j + 1);
+                    let prev = if *j > 0 { pattern.get(j - 1) } else { None };
+                    t.is_ident()
+                        && !t.is("mut")
+                        && t.text
+                            .starts_with(|c: char| c.is_ascii_lowercase() || c == '_')
+                        && !next
+                            .map(|n| n.is(":") || n.is("::") || n.is("<") || n.is("{") || n.is("("))
+                            .unwrap_or(false)
+                        && !prev.map(|p| p.is("::")).unwrap_or(false)
+                })
+                .map(|(_, t)| t.text.clone())
+                .collect();
+
+            // Find where this statement ends to decide which names are read later.
+            let mut stmt_end = end;
+            let mut depth = 0i32;
+            while let Some(t) = tokens.get(stmt_end) {
+                match t.text.as_str() {
+                    "(" | "{" | "[" => depth += 1,
+                    ")" | "}" | "]" => {
+                        if depth == 0 {
+                            break;
+                        }
+                        depth -= 1;
+                    }
+                    ";" if depth == 0 => break,
+                    _ => {}
+                }
+                stmt_end += 1;
+            }
+            if let Some(t) = tokens.get(end) {
+                if t.is("=") {
+                    let_equals.push(end);
+                } else if t.is(":") {
+                    if let Some(eq) = tokens[end..stmt_end.min(tokens.len())]
+                        .iter()
+                        .position(|t| t.is("="))
+                    {
+                        let_equals.push(end + eq);
+                    }
+                }
+            }
+
+            let rest = &tokens[stmt_end.min(tokens.len())..];
+            let unused = names
+                .iter()
+                .filter(|name| !name.starts_with('_'))
+                .filter(|name| !rest.iter().any(|t| t.is_ident() && t.text == **name))
+                .cloned()
+                .collect();
+
+            body.lets.push(LetBinding {
+                line: token.line,
+                unused,
+            });
+            continue;
+        }
+
+        if token.is("abort") {
+            let mut end = i + 1;
+            let mut depth = 0i32;
+            while let Some(t) = tokens.get(end) {
+                match t.text.as_str() {
+                    "(" | "[" | "{" => depth += 1,
+                    ")" | "]" | "}" if depth == 0 => break,
+                    ")" | "]" | "}" => depth -= 1,
+                    ";" if depth == 0 => break,
+                    _ => {}
+                }
+                end += 1;
+            }
+            body.aborts.push(AbortSite {
+                code: render(&tokens[i + 1..end]),
+                line: token.line,
+            });
+            continue;
+        }
+
+        if token.is("=") && !let_equals.contains(&i) {
+            // Walk back to the start of the statement and look for a field
+            // access or dereference on the left-hand side.
+            let mut j = i;
+            let mut writes_through = false;
+            while j > 0 {
+                let t = &tokens[j - 1];
+                if t.is(";") || t.is("{") || t.is("}") {
+                    break;
+                }
+                if t.is(".") || t.is("*") {
+                    writes_through = true;
+                }
+                j -= 1;
+            }
+            if writes_through {
+                body.field_writes.push(token.line);
+            }
+            continue;
+        }
+
+        if !token.is_ident() || NON_CALL_KEYWORDS.contains(&token.text.as_str()) {
+            continue;
+        }
+
+        // Macro invocations: `assert!(...)`, `assert_eq!(...)`.
+        if tokens.get(i + 1).map(|t| t.is("!")) == Some(true) {
+            if let Some(open) = tokens.get(i + 2).filter(|t| t.is("(")).map(|_| i + 2) {
+                if token.is("assert") || token.is("assert_eq") {
+                    if let Some(close) = close_of(tokens, open) {
+                        let args = split_args(&tokens[open + 1..close]);
+                        if token.is("assert") {
+                            body.asserts.push(AssertSite {
+                                condition: args.first().map(|a| render(a)).unwrap_or_default(),
+                                code: args.get(1).map(|a| render(a)),
+                                line: token.line,
+                            });
+                        }
+                    }
+                }
+            }
+            continue;
+        }
+
+        let open = match skip_type_args(tokens, i + 1) {
+            Some(open) if tokens.get(open).map(|t| t.is("(")) == Some(true) => open,
+            _ => continue,
+        };
+        let close = match close_of(tokens, open) {
+            Some(close) => close,
+            None => continue,
+        };
+
+        let mut module = Vec::new();
+        let mut j = i;
+        while j >= 2 && tokens[j - 1].is("::") {
+            let segment = &tokens[j - 2];
+            if !(segment.is_ident() || segment.kind == TokenKind::Number) {
+                break;
+            }
+            module.insert(0, segment.text.clone());
+            j -= 2;
+        }
+
+        let is_method = module.is_empty() && j >= 1 && tokens[j - 1].is(".");
+        let receiver = if is_method {
+            let mut k = j - 1;
+            while k >= 1 && (tokens[k - 1].is_ident() || tokens[k - 1].is(".")) {
+                k -= 1;
+            }
+            Some(render(&tokens[k..j - 1])).filter(|r| !r.is_empty())
+        } else {
+            None
+        };
+
+        body.calls.push(CallSite {
+            module,
+            function: token.text.clone(),
+            is_method,
+            receiver,
+            args: split_args(&tokens[open + 1..close])
+                .into_iter()
+                .map(render)
+                .collect(),
+            line: token.line,
+        });
+    }
+
+    body
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::move_syntax::{parse_source, Function, Module, Type, Visibility};
+    use std::path::Path;
+
+    fn parse(source: &str) -> Vec<Module> {
+        parse_source(Path::new("test.move"), source).unwrap()
+    }
+
+    fn function<'a>(module: &'a Module, name: &str) -> &'a Function {
+        module
+            .functions
+            .iter()
+            .find(|f| f.name == name)
+            .unwrap_or_else(|| panic!("no function `{}`", name))
+    }
+
+    #[test]
+    fn parses_function_visibility_and_entry() {
+        let modules = parse(
+            r#"
+            module demo::market {
+                entry fun list(x: u64) {}
+                public entry fun buy() {}
+                public(package) fun settle() {}
+                public(friend) fun legacy() {}
+                public fun price(): u64 { 1 }
+                fun helper() {}
+            }
+            "#,
+        );
+        let module = &modules[0];
+        assert_eq!(module.address, "demo");
+        assert_eq!(module.name, "market");
+
+        let cases = [
+            ("list", Visibility::Private, true),
+            ("buy", Visibility::Public, true),
+            ("settle", Visibility::Package, false),
+            ("legacy", Visibility::Friend, false),
+            ("price", Visibility::Public, false),
+            ("helper", Visibility::Private, false),
+        ];
+        for (name, visibility, is_entry) in cases {
+            let f = function(module, name);
+            assert_eq!(f.visibility, visibility, "{}", name);
+            assert_eq!(f.is_entry, is_entry, "{}", name);
+        }
+    }
+
+    #[test]
+    fn parses_multi_line_generic_signatures() {
+        let modules = parse(
+            r#"
+            module demo::pool;
+
+            public fun swap<
+                A: store,
+                phantom B,
+            >(
+                pool: &mut Pool<A, B>,
+                coin: Coin<A>,
+                amounts: vector<vector<u64>>,
+                ctx: &mut TxContext,
+            ): (Coin<B>, u64) {
+                abort 0
+            }
+            "#,
+        );
+        let f = function(&modules[0], "swap");
+
+        assert_eq!(f.line, 4);
+        assert_eq!(f.end_line, 14);
+        assert_eq!(f.type_params.len(), 2);
+        assert_eq!(f.type_params[0].name, "A");
+        assert!(!f.type_params[0].is_phantom);
+        assert_eq!(f.type_params[1].name, "B");
+        assert!(f.type_params[1].is_phantom);
+
+        let params: Vec<(String, String)> = f
+            .params
+            .iter()
+            .map(|p| (p.name.clone(), p.ty.to_string()))
+            .collect();
+        assert_eq!(
+            params,
+            vec![
+                ("pool".to_string(), "&mut Pool<A, B>".to_string()),
+                ("coin".to_string(), "Coin<A>".to_string()),
+                ("amounts".to_string(), "vector<vector<u64>>".to_string()),
+                ("ctx".to_string(), "&mut TxContext".to_string()),
+            ]
+        );
+        assert!(f.params[0].ty.is_mut_ref());
+        assert_eq!(
+            f.returns,
+            vec![
+                Type::Named {
+                    path: vec!["Coin".to_string()],
+                    args: vec![Type::Named {
+                        path: vec!["B".to_string()],
+                        args: Vec::new(),
+                    }],
+                },
+                Type::Named {
+                    path: vec!["u64".to_string()],
+                    args: Vec::new(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn comments_do_not_disturb_items() {
+        let modules = parse(
+            r#"
+            module demo::docs {
+                /// A listed item.
+                /// Spans two lines.
+                public struct Item has key { id: UID /* inline */, price: u64 }
+
+                // Not documentation.
+                /* A block comment /* with a nested one */ before the function. */
+                /// Buy an item.
+                #[allow(lint(self_transfer))]
+                public fun buy(item: Item /* by value */): u64 {
+                    // abort 1
+                    let Item { id, price } = item;
+                    id.delete();
+                    price
+                }
+            }
+            "#,
+        );
+        let module = &modules[0];
+
+        let item = &module.structs[0];
+        assert_eq!(
+            item.doc.as_deref(),
+            Some("A listed item.\nSpans two lines.")
+        );
+        assert_eq!(item.fields.len(), 2);
+
+        let buy = function(module, "buy");
+        assert_eq!(buy.doc.as_deref(), Some("Buy an item."));
+        assert_eq!(buy.attributes, vec!["allow".to_string()]);
+        assert_eq!(buy.params.len(), 1);
+        let body = buy.body.as_ref().unwrap();
+        assert!(body.aborts.is_empty());
+        assert_eq!(body.calls.len(), 1);
+        assert_eq!(body.calls[0].function, "delete");
+        assert!(body.calls[0].is_method);
+    }
+
+    #[test]
+    fn skips_items_it_does_not_model() {
+        let modules = parse(
+            r#"
+            module demo::skip {
+                use fun id as Item.uid;
+                public enum Color has copy, drop { Red, Green { shade: u8 } }
+                spec module { pragma verify = false; }
+                public fun after(): u64 { 1 }
+            }
+            "#,
+        );
+        let module = &modules[0];
+        assert!(module.structs.is_empty());
+        assert_eq!(module.functions.len(), 1);
+        assert_eq!(module.functions[0].name, "after");
+    }
+
+    #[test]
+    fn reports_the_line_of_a_syntax_error() {
+        let error = parse_source(
+            Path::new("bad.move"),
+            "module demo::bad {\n    public fun broken(x u64) {}\n}\n",
+        )
+        .unwrap_err();
+        assert!(error.to_string().contains("bad.move:2"), "{}", error);
+    }
+}
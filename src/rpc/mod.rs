@@ -0,0 +1,594 @@
+pub mod types;
+
+use crate::config::ResolvedNetwork;
+use crate::error::{Result, SuiForgeError};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+use types::*;
+
+/// Most object IDs the fullnode accepts in one `sui_multiGetObjects` call.
+pub const MULTI_GET_OBJECTS_LIMIT: usize = 50;
+
+/// Retry behaviour for transient transport failures (connection errors,
+/// timeouts, HTTP 429 and 5xx). JSON-RPC errors returned by the node are
+/// never retried.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    pub max_retries: u32,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(250),
+            max_backoff: Duration::from_secs(4),
+        }
+    }
+}
+
+impl RetryPolicy {
+    fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt);
+        self.initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
+}
+
+#[derive(Deserialize)]
+struct JsonRpcResponse {
+    id: Option<Value>,
+    result: Option<Value>,
+    error: Option<JsonRpcError>,
+}
+
+#[derive(Deserialize)]
+struct JsonRpcError {
+    code: i64,
+    message: String,
+}
+
+/// Typed client for the Sui fullnode JSON-RPC API.
+pub struct RpcClient {
+    http: reqwest::Client,
+    url: String,
+    retry: RetryPolicy,
+    next_id: AtomicU64,
+}
+
+impl RpcClient {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            url: url.into(),
+            retry: RetryPolicy::default(),
+            next_id: AtomicU64::new(1),
+        }
+    }
+
+    pub fn for_network(network: &ResolvedNetwork) -> Self {
+        Self::new(network.rpc.clone())
+    }
+
+    /// Perform a raw JSON-RPC call and deserialize its `result`.
+    pub async fn call<T: DeserializeOwned>(&self, method: &str, params: Value) -> Result<T> {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let body = json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": method,
+            "params": params,
+        });
+
+        let mut attempt = 0;
+        let response: JsonRpcResponse = loop {
+            match self.send(&body).await {
+                Ok(response) => break response,
+                Err(e) if attempt < self.retry.max_retries && is_transient(&e) => {
+                    tracing::debug!("{} failed ({}), retrying", method, e);
+                    tokio::time::sleep(self.retry.backoff(attempt)).await;
+                    attempt += 1;
+                }
+                Err(e) => return Err(e.into()),
+            }
+        };
+
+        if let Some(error) = response.error {
+            return Err(SuiForgeError::Rpc {
+                code: error.code,
+                message: error.message,
+            });
+        }
+
+        if response.id.as_ref().and_then(|v| v.as_u64()) != Some(id) {
+            return Err(SuiForgeError::Rpc {
+                code: -32603,
+                message: format!("response id does not match request id {}", id),
+            });
+        }
+
+        let result = response.result.unwrap_or(Value::Null);
+        Ok(serde_json::from_value(result)?)
+    }
+
+    async fn send(&self, body: &Value) -> std::result::Result<JsonRpcResponse, reqwest::Error> {
+        self.http
+            .post(&self.url)
+            .json(body)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await
+    }
+
+    pub async fn get_object(
+        &self,
+        object_id: &str,
+        options: ObjectDataOptions,
+    ) -> Result<SuiObjectData> {
+        let response: SuiObjectResponse = self
+            .call("sui_getObject", json!([object_id, options]))
+            .await?;
+        object_data(object_id, response)
+    }
+
+    async fn multi_get_objects(
+        &self,
+        object_ids: &[String],
+        options: ObjectDataOptions,
+    ) -> Result<Vec<SuiObjectResponse>> {
+        self.call("sui_multiGetObjects", json!([object_ids, options]))
+            .await
+    }
+
+    /// Fetch any number of objects, [`MULTI_GET_OBJECTS_LIMIT`] per request.
+    /// Responses are in the order of `object_ids`.
+    pub async fn get_objects(
+        &self,
+        object_ids: &[String],
+        options: ObjectDataOptions,
+    ) -> Result<Vec<SuiObjectResponse>> {
+        let mut responses = Vec::with_capacity(object_ids.len());
+        for chunk in object_ids.chunks(MULTI_GET_OBJECTS_LIMIT) {
+            responses.extend(self.multi_get_objects(chunk, options.clone()).await?);
+        }
+        Ok(responses)
+    }
+
+    /// A page of the objects owned by `owner`. `filter` is a
+    /// `SuiObjectDataFilter`, such as `{"StructType": "0x2::coin::Coin"}`.
+    pub async fn get_owned_objects(
+        &self,
+        owner: &str,
+        filter: Option<Value>,
+        options: ObjectDataOptions,
+        cursor: Option<String>,
+        limit: Option<usize>,
+    ) -> Result<Page<SuiObjectResponse, String>> {
+        self.call(
+            "suix_getOwnedObjects",
+            json!([owner, { "filter": filter, "options": options }, cursor, limit]),
+        )
+        .await
+    }
+
+    pub async fn try_get_past_object(
+        &self,
+        object_id: &str,
+        version: u64,
+        options: ObjectDataOptions,
+    ) -> Result<SuiPastObjectResponse> {
+        self.call("sui_tryGetPastObject", json!([object_id, version, options]))
+            .await
+    }
+
+    pub async fn get_transaction_block(
+        &self,
+        digest: &str,
+        options: TransactionBlockResponseOptions,
+    ) -> Result<SuiTransactionBlockResponse> {
+        self.call("sui_getTransactionBlock", json!([digest, options]))
+            .await
+    }
+
+    pub async fn query_events(
+        &self,
+        filter: Value,
+        cursor: Option<EventId>,
+        limit: Option<usize>,
+        descending: bool,
+    ) -> Result<Page<SuiEvent, EventId>> {
+        self.call(
+            "suix_queryEvents",
+            json!([filter, cursor, limit, descending]),
+        )
+        .await
+    }
+
+    pub async fn get_dynamic_fields(
+        &self,
+        parent_id: &str,
+        cursor: Option<String>,
+        limit: Option<usize>,
+    ) -> Result<Page<DynamicFieldInfo, String>> {
+        self.call("suix_getDynamicFields", json!([parent_id, cursor, limit]))
+            .await
+    }
+
+    pub async fn get_normalized_move_modules_by_package(
+        &self,
+        package_id: &str,
+    ) -> Result<BTreeMap<String, NormalizedModule>> {
+        self.call("sui_getNormalizedMoveModulesByPackage", json!([package_id]))
+            .await
+    }
+
+    pub async fn get_all_balances(&self, owner: &str) -> Result<Vec<Balance>> {
+        self.call("suix_getAllBalances", json!([owner])).await
+    }
+}
+
+fn object_data(object_id: &str, response: SuiObjectResponse) -> Result<SuiObjectData> {
+    match (response.data, response.error) {
+        (Some(data), _) => Ok(data),
+        (None, Some(error)) => Err(SuiForgeError::Custom(format!(
+            "Object {} unavailable: {}",
+            object_id, error
+        ))),
+        (None, None) => Err(SuiForgeError::Custom(format!(
+            "Object {} not found",
+            object_id
+        ))),
+    }
+}
+
+fn is_transient(error: &reqwest::Error) -> bool {
+    if error.is_timeout() || error.is_connect() {
+        return true;
+    }
+    match error.status() {
+        Some(status) => status.as_u16() == 429 || status.is_server_error(),
+        None => false,
+    }
+}
+
+/// Drain a cursor-based query, following `nextCursor` until the node reports
+/// no further pages or `max_items` have been collected.
+pub async fn collect_pages<T, C, F, Fut>(mut fetch: F, max_items: Option<usize>) -> Result<Vec<T>>
+where
+    F: FnMut(Option<C>) -> Fut,
+    Fut: Future<Output = Result<Page<T, C>>>,
+{
+    let mut items = Vec::new();
+    let mut cursor = None;
+
+    loop {
+        let page = fetch(cursor).await?;
+        items.extend(page.data);
+
+        if let Some(max) = max_items {
+            if items.len() >= max {
+                items.truncate(max);
+                break;
+            }
+        }
+
+        match page.next_cursor {
+            Some(next) if page.has_next_page => cursor = Some(next),
+            _ => break,
+        }
+    }
+
+    Ok(items)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use std::collections::VecDeque;
+    use std::sync::{Arc, Mutex};
+    use std::time::Instant;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// What the mock node answers to the next request.
+    pub(crate) enum Reply {
+        Status(u16),
+        Result(Value),
+        Error(i64, &'static str),
+        WrongId,
+    }
+
+    /// A fullnode stand-in on a local port, answering one request per
+    /// connection from a script and recording the JSON-RPC bodies it gets.
+    pub(crate) struct MockNode {
+        url: String,
+        requests: Arc<Mutex<Vec<Value>>>,
+    }
+
+    impl MockNode {
+        pub(crate) async fn start(replies: Vec<Reply>) -> Self {
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let url = format!("http://{}", listener.local_addr().unwrap());
+            let requests = Arc::new(Mutex::new(Vec::new()));
+            let recorded = requests.clone();
+            let mut replies = VecDeque::from(replies);
+            tokio::spawn(async move {
+                while let Some(reply) = replies.pop_front() {
+                    let (mut socket, _) = listener.accept().await.unwrap();
+                    let body = read_request(&mut socket).await;
+                    let id = body["id"].clone();
+                    recorded.lock().unwrap().push(body);
+                    let (status, payload) = match reply {
+                        Reply::Status(status) => (status, String::new()),
+                        Reply::Result(result) => (
+                            200,
+                            json!({"jsonrpc": "2.0", "id": id, "result": result}).to_string(),
+                        ),
+                        Reply::Error(code, message) => (
+                            200,
+                            json!({"jsonrpc": "2.0", "id": id, "error": {"code": code, "message": message}})
+                                .to_string(),
+                        ),
+                        Reply::WrongId => (
+                            200,
+                            json!({"jsonrpc": "2.0", "id": 9999, "result": null}).to_string(),
+                        ),
+                    };
+                    let response = format!(
+                        "HTTP/1.1 {} Mock\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        status,
+                        payload.len(),
+                        payload
+                    );
+                    socket.write_all(response.as_bytes()).await.unwrap();
+                    socket.shutdown().await.ok();
+                }
+            });
+            Self { url, requests }
+        }
+
+        pub(crate) fn client(&self) -> RpcClient {
+            RpcClient {
+                retry: RetryPolicy {
+                    max_retries: 3,
+                    initial_backoff: Duration::from_millis(20),
+                    max_backoff: Duration::from_millis(40),
+                },
+                ..RpcClient::new(self.url.clone())
+            }
+        }
+
+        pub(crate) fn requests(&self) -> Vec<Value> {
+            self.requests.lock().unwrap().clone()
+        }
+    }
+
+    async fn read_request(socket: &mut tokio::net::TcpStream) -> Value {
+        let mut data = Vec::new();
+        let mut buf = [0u8; 4096];
+        loop {
+            let n = socket.read(&mut buf).await.unwrap();
+            assert!(n > 0, "connection closed mid-request");
+            data.extend_from_slice(&buf[..n]);
+            let Some(end) = data.windows(4).position(|w| w == b"\r\n\r\n") else {
+                continue;
+            };
+            let head = String::from_utf8_lossy(&data[..end]).to_lowercase();
+            let length: usize = head
+                .lines()
+                .find_map(|line| line.strip_prefix("content-length:"))
+                .map(|value| value.trim().parse().unwrap())
+                .unwrap_or(0);
+            if data.len() >= end + 4 + length {
+                return serde_json::from_slice(&data[end + 4..end + 4 + length]).unwrap();
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn call_sends_json_rpc_request_and_returns_result() {
+        let node = MockNode::start(vec![Reply::Result(json!({"answer": 42}))]).await;
+        let result: Value = node
+            .client()
+            .call("sui_test", json!(["0x2", 1]))
+            .await
+            .unwrap();
+
+        assert_eq!(result, json!({"answer": 42}));
+        let requests = node.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0]["jsonrpc"], "2.0");
+        assert_eq!(requests[0]["method"], "sui_test");
+        assert_eq!(requests[0]["params"], json!(["0x2", 1]));
+    }
+
+    #[tokio::test]
+    async fn get_objects_splits_ids_into_chunks() {
+        let ids: Vec<String> = (0..120).map(|i| format!("0x{:x}", i)).collect();
+        let page = |ids: &[String]| {
+            Reply::Result(Value::Array(
+                ids.iter()
+                    .map(|id| json!({"data": {"objectId": id, "version": "1", "digest": "d"}}))
+                    .collect(),
+            ))
+        };
+        let node = MockNode::start(vec![
+            page(&ids[..50]),
+            page(&ids[50..100]),
+            page(&ids[100..]),
+        ])
+        .await;
+
+        let responses = node
+            .client()
+            .get_objects(&ids, ObjectDataOptions::default())
+            .await
+            .unwrap();
+
+        let returned: Vec<&str> = responses
+            .iter()
+            .map(|r| r.data.as_ref().unwrap().object_id.as_str())
+            .collect();
+        assert_eq!(returned, ids);
+        let sizes: Vec<usize> = node
+            .requests()
+            .iter()
+            .map(|r| r["params"][0].as_array().unwrap().len())
+            .collect();
+        assert_eq!(sizes, [50, 50, 20]);
+    }
+
+    #[tokio::test]
+    async fn call_rejects_mismatched_response_id() {
+        let node = MockNode::start(vec![Reply::WrongId]).await;
+        let error = node
+            .client()
+            .call::<Value>("sui_test", json!([]))
+            .await
+            .unwrap_err();
+
+        assert!(
+            matches!(&error, SuiForgeError::Rpc { code: -32603, message } if message.contains("does not match")),
+            "unexpected error: {}",
+            error
+        );
+    }
+
+    #[tokio::test]
+    async fn call_maps_json_rpc_errors_without_retrying() {
+        let node = MockNode::start(vec![Reply::Error(-32602, "Invalid params")]).await;
+        let error = node
+            .client()
+            .call::<Value>("sui_test", json!([]))
+            .await
+            .unwrap_err();
+
+        assert!(
+            matches!(&error, SuiForgeError::Rpc { code: -32602, message } if message == "Invalid params"),
+            "unexpected error: {}",
+            error
+        );
+        assert_eq!(node.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn call_retries_429_and_5xx_with_backoff() {
+        let node = MockNode::start(vec![
+            Reply::Status(429),
+            Reply::Status(503),
+            Reply::Result(json!("ok")),
+        ])
+        .await;
+        let client = node.client();
+        let started = Instant::now();
+        let result: String = client.call("sui_test", json!([])).await.unwrap();
+
+        assert_eq!(result, "ok");
+        assert_eq!(node.requests().len(), 3);
+        assert!(started.elapsed() >= client.retry.backoff(0) + client.retry.backoff(1));
+    }
+
+    #[tokio::test]
+    async fn call_gives_up_after_max_retries() {
+        let node = MockNode::start((0..4).map(|_| Reply::Status(500)).collect()).await;
+        let error = node
+            .client()
+            .call::<Value>("sui_test", json!([]))
+            .await
+            .unwrap_err();
+
+        assert!(matches!(error, SuiForgeError::Reqwest(_)));
+        assert_eq!(node.requests().len(), 4);
+    }
+
+    #[tokio::test]
+    async fn call_does_not_retry_4xx() {
+        let node = MockNode::start(vec![Reply::Status(404), Reply::Result(json!("ok"))]).await;
+        let error = node
+            .client()
+            .call::<Value>("sui_test", json!([]))
+            .await
+            .unwrap_err();
+
+        match error {
+            SuiForgeError::Reqwest(e) => assert_eq!(e.status().map(|s| s.as_u16()), Some(404)),
+            other => panic!("unexpected error: {}", other),
+        }
+        assert_eq!(node.requests().len(), 1);
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_the_cap() {
+        let retry = RetryPolicy {
+            max_retries: 5,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_millis(350),
+        };
+        let backoffs: Vec<u128> = (0..4).map(|i| retry.backoff(i).as_millis()).collect();
+        assert_eq!(backoffs, vec![100, 200, 350, 350]);
+    }
+
+    fn page(data: &[u64], next_cursor: Option<&str>, has_next_page: bool) -> Reply {
+        Reply::Result(json!({
+            "data": data,
+            "nextCursor": next_cursor,
+            "hasNextPage": has_next_page,
+        }))
+    }
+
+    #[tokio::test]
+    async fn collect_pages_follows_next_cursor() {
+        let node = MockNode::start(vec![
+            page(&[1, 2], Some("c1"), true),
+            page(&[3, 4], Some("c2"), true),
+            // The last page still carries a cursor; `hasNextPage` ends it.
+            page(&[5], Some("c3"), false),
+        ])
+        .await;
+        let client = node.client();
+        let items: Vec<u64> = collect_pages(
+            |cursor: Option<String>| client.call("suix_test", json!([cursor])),
+            None,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(items, vec![1, 2, 3, 4, 5]);
+        let cursors: Vec<Value> = node
+            .requests()
+            .iter()
+            .map(|r| r["params"][0].clone())
+            .collect();
+        assert_eq!(cursors, vec![Value::Null, json!("c1"), json!("c2")]);
+    }
+
+    #[tokio::test]
+    async fn collect_pages_stops_at_max_items() {
+        let node = MockNode::start(vec![
+            page(&[1, 2], Some("c1"), true),
+            page(&[3, 4], Some("c2"), true),
+            page(&[5, 6], None, false),
+        ])
+        .await;
+        let client = node.client();
+        let items: Vec<u64> = collect_pages(
+            |cursor: Option<String>| client.call("suix_test", json!([cursor])),
+            Some(3),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(items, vec![1, 2, 3]);
+        assert_eq!(node.requests().len(), 2);
+    }
+}
@@ -0,0 +1,510 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// A single page of results from a cursor-based `suix_*` query.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Page<T, C> {
+    pub data: Vec<T>,
+    pub next_cursor: Option<C>,
+    pub has_next_page: bool,
+}
+
+// ---------------------------------------------------------------------------
+// Objects
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ObjectDataOptions {
+    pub show_type: bool,
+    pub show_owner: bool,
+    pub show_previous_transaction: bool,
+    pub show_display: bool,
+    pub show_content: bool,
+    pub show_bcs: bool,
+    pub show_storage_rebate: bool,
+}
+
+impl ObjectDataOptions {
+    /// Everything except BCS, which is what the inspectors render.
+    pub fn full_content() -> Self {
+        Self {
+            show_type: true,
+            show_owner: true,
+            show_previous_transaction: true,
+            show_display: false,
+            show_content: true,
+            show_bcs: false,
+            show_storage_rebate: true,
+        }
+    }
+
+    pub fn with_bcs(mut self) -> Self {
+        self.show_bcs = true;
+        self
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SuiObjectResponse {
+    pub data: Option<SuiObjectData>,
+    pub error: Option<Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SuiObjectData {
+    pub object_id: String,
+    pub version: String,
+    pub digest: String,
+    #[serde(rename = "type")]
+    pub type_: Option<String>,
+    pub owner: Option<Owner>,
+    pub previous_transaction: Option<String>,
+    pub storage_rebate: Option<String>,
+    pub display: Option<Value>,
+    pub content: Option<SuiParsedData>,
+    pub bcs: Option<SuiRawData>,
+}
+
+// Variant names are the wire format, hence the repeated `Owner` suffix.
+#[allow(clippy::enum_variant_names)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub enum Owner {
+    AddressOwner(String),
+    ObjectOwner(String),
+    Shared { initial_shared_version: u64 },
+    Immutable,
+    ConsensusAddressOwner { start_version: u64, owner: String },
+}
+
+impl fmt::Display for Owner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Owner::AddressOwner(address) => write!(f, "{}", address),
+            Owner::ObjectOwner(id) => write!(f, "object {}", id),
+            Owner::Shared {
+                initial_shared_version,
+            } => write!(f, "shared (since v{})", initial_shared_version),
+            Owner::Immutable => write!(f, "immutable"),
+            Owner::ConsensusAddressOwner { owner, .. } => write!(f, "{} (consensus)", owner),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(tag = "dataType", rename_all = "camelCase")]
+pub enum SuiParsedData {
+    #[serde(rename_all = "camelCase")]
+    MoveObject {
+        #[serde(rename = "type")]
+        type_: String,
+        has_public_transfer: bool,
+        fields: Value,
+    },
+    Package {
+        disassembled: BTreeMap<String, Value>,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(tag = "dataType", rename_all = "camelCase")]
+pub enum SuiRawData {
+    #[serde(rename_all = "camelCase")]
+    MoveObject {
+        #[serde(rename = "type")]
+        type_: String,
+        has_public_transfer: bool,
+        version: u64,
+        bcs_bytes: String,
+    },
+    #[serde(rename_all = "camelCase")]
+    Package {
+        id: String,
+        version: u64,
+        module_map: BTreeMap<String, String>,
+        type_origin_table: Vec<TypeOrigin>,
+        linkage_table: BTreeMap<String, UpgradeInfo>,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TypeOrigin {
+    pub module_name: String,
+    #[serde(alias = "struct_name", alias = "datatype_name")]
+    pub datatype_name: String,
+    pub package: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct UpgradeInfo {
+    pub upgraded_id: String,
+    pub upgraded_version: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(tag = "status", content = "details")]
+pub enum SuiPastObjectResponse {
+    VersionFound(Box<SuiObjectData>),
+    ObjectNotExists(Value),
+    ObjectDeleted(Value),
+    VersionNotFound(Value),
+    VersionTooHigh(Value),
+}
+
+// ---------------------------------------------------------------------------
+// Dynamic fields
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DynamicFieldName {
+    #[serde(rename = "type")]
+    pub type_: String,
+    pub value: Value,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum DynamicFieldType {
+    /// Stored inline in a `0x2::dynamic_field::Field` wrapper object.
+    DynamicField,
+    /// A standalone object, as in `ObjectTable` and `ObjectBag`.
+    DynamicObject,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DynamicFieldInfo {
+    pub name: DynamicFieldName,
+    #[serde(rename = "type")]
+    pub type_: DynamicFieldType,
+    pub object_type: String,
+    /// The `Field` wrapper for [`DynamicFieldType::DynamicField`], the
+    /// child object itself for [`DynamicFieldType::DynamicObject`].
+    pub object_id: String,
+    pub version: u64,
+    pub digest: String,
+}
+
+// ---------------------------------------------------------------------------
+// Transactions
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionBlockResponseOptions {
+    pub show_input: bool,
+    pub show_raw_input: bool,
+    pub show_effects: bool,
+    pub show_events: bool,
+    pub show_object_changes: bool,
+    pub show_balance_changes: bool,
+}
+
+impl TransactionBlockResponseOptions {
+    pub fn full() -> Self {
+        Self {
+            show_input: true,
+            show_raw_input: false,
+            show_effects: true,
+            show_events: true,
+            show_object_changes: true,
+            show_balance_changes: true,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SuiTransactionBlockResponse {
+    pub digest: String,
+    pub transaction: Option<Value>,
+    pub effects: Option<TransactionEffects>,
+    pub events: Option<Vec<SuiEvent>>,
+    pub object_changes: Option<Vec<ObjectChange>>,
+    pub balance_changes: Option<Vec<BalanceChange>>,
+    pub timestamp_ms: Option<String>,
+    pub checkpoint: Option<String>,
+}
+
+/// Result of simulating a transaction without signing or executing it.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DryRunTransactionBlockResponse {
+    pub effects: TransactionEffects,
+    #[serde(default)]
+    pub events: Vec<SuiEvent>,
+    #[serde(default)]
+    pub object_changes: Vec<ObjectChange>,
+    #[serde(default)]
+    pub balance_changes: Vec<BalanceChange>,
+    pub input: Option<Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionEffects {
+    pub status: ExecutionStatus,
+    pub gas_used: GasCostSummary,
+    pub transaction_digest: String,
+    #[serde(default)]
+    pub created: Vec<OwnedObjectRef>,
+    #[serde(default)]
+    pub mutated: Vec<OwnedObjectRef>,
+    #[serde(default)]
+    pub deleted: Vec<ObjectRef>,
+    pub gas_object: Option<OwnedObjectRef>,
+    /// The version each mutated or deleted input object had before the
+    /// transaction.
+    #[serde(default)]
+    pub modified_at_versions: Vec<ModifiedAtVersion>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ModifiedAtVersion {
+    pub object_id: String,
+    pub sequence_number: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ExecutionStatus {
+    pub status: String,
+    pub error: Option<String>,
+}
+
+impl ExecutionStatus {
+    pub fn is_success(&self) -> bool {
+        self.status == "success"
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct GasCostSummary {
+    pub computation_cost: String,
+    pub storage_cost: String,
+    pub storage_rebate: String,
+    pub non_refundable_storage_fee: String,
+}
+
+impl GasCostSummary {
+    pub fn computation(&self) -> u64 {
+        self.computation_cost.parse().unwrap_or(0)
+    }
+
+    pub fn storage(&self) -> u64 {
+        self.storage_cost.parse().unwrap_or(0)
+    }
+
+    pub fn rebate(&self) -> u64 {
+        self.storage_rebate.parse().unwrap_or(0)
+    }
+
+    /// Net charge: computation plus storage minus the storage rebate.
+    pub fn net(&self) -> i64 {
+        self.computation() as i64 + self.storage() as i64 - self.rebate() as i64
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ObjectRef {
+    pub object_id: String,
+    pub version: u64,
+    pub digest: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OwnedObjectRef {
+    pub owner: Owner,
+    pub reference: ObjectRef,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum ObjectChange {
+    #[serde(rename_all = "camelCase")]
+    Published {
+        package_id: String,
+        version: String,
+        digest: String,
+        modules: Vec<String>,
+    },
+    #[serde(rename_all = "camelCase")]
+    Transferred {
+        sender: String,
+        recipient: Owner,
+        object_type: String,
+        object_id: String,
+        version: String,
+        digest: String,
+    },
+    #[serde(rename_all = "camelCase")]
+    Mutated {
+        sender: String,
+        owner: Owner,
+        object_type: String,
+        object_id: String,
+        version: String,
+        previous_version: String,
+        digest: String,
+    },
+    #[serde(rename_all = "camelCase")]
+    Deleted {
+        sender: String,
+        object_type: String,
+        object_id: String,
+        version: String,
+    },
+    #[serde(rename_all = "camelCase")]
+    Wrapped {
+        sender: String,
+        object_type: String,
+        object_id: String,
+        version: String,
+    },
+    #[serde(rename_all = "camelCase")]
+    Created {
+        sender: String,
+        owner: Owner,
+        object_type: String,
+        object_id: String,
+        version: String,
+        digest: String,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BalanceChange {
+    pub owner: Owner,
+    pub coin_type: String,
+    pub amount: String,
+}
+
+// ---------------------------------------------------------------------------
+// Events
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct EventId {
+    pub tx_digest: String,
+    pub event_seq: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SuiEvent {
+    pub id: EventId,
+    pub package_id: String,
+    pub transaction_module: String,
+    pub sender: String,
+    #[serde(rename = "type")]
+    pub type_: String,
+    pub parsed_json: Value,
+    pub timestamp_ms: Option<String>,
+}
+
+// ---------------------------------------------------------------------------
+// Coins
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Balance {
+    pub coin_type: String,
+    pub coin_object_count: u64,
+    pub total_balance: String,
+}
+
+// ---------------------------------------------------------------------------
+// Normalized Move modules
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct NormalizedModule {
+    pub file_format_version: u32,
+    pub address: String,
+    pub name: String,
+    #[serde(default)]
+    pub friends: Vec<NormalizedModuleId>,
+    #[serde(default)]
+    pub structs: BTreeMap<String, NormalizedStruct>,
+    #[serde(default)]
+    pub exposed_functions: BTreeMap<String, NormalizedFunction>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct NormalizedModuleId {
+    pub address: String,
+    pub name: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct AbilitySet {
+    pub abilities: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct NormalizedStruct {
+    pub abilities: AbilitySet,
+    pub type_parameters: Vec<StructTypeParameter>,
+    pub fields: Vec<NormalizedField>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct StructTypeParameter {
+    pub constraints: AbilitySet,
+    pub is_phantom: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct NormalizedField {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub type_: NormalizedType,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct NormalizedFunction {
+    pub visibility: String,
+    pub is_entry: bool,
+    pub type_parameters: Vec<AbilitySet>,
+    pub parameters: Vec<NormalizedType>,
+    #[serde(rename = "return")]
+    pub return_: Vec<NormalizedType>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub enum NormalizedType {
+    Bool,
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    U256,
+    Address,
+    Signer,
+    Struct {
+        address: String,
+        module: String,
+        name: String,
+        #[serde(rename = "typeArguments")]
+        type_arguments: Vec<NormalizedType>,
+    },
+    Vector(Box<NormalizedType>),
+    TypeParameter(u16),
+    Reference(Box<NormalizedType>),
+    MutableReference(Box<NormalizedType>),
+}
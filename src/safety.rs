@@ -0,0 +1,328 @@
+//! Pre-flight checks for deployments to protected networks.
+//!
+//! Every check configured in `deploy.safety` must pass before a publish or
+//! upgrade is submitted. The outcome of each check, and who confirmed the
+//! deployment, is returned as an audit entry for the lock file.
+
+use crate::commands::compat;
+use crate::commands::scan::{scan_sources, SecurityIssue, Severity};
+use crate::commands::upgrade::UpgradePolicy;
+use crate::config::SafetyConfig;
+use crate::error::{Result, SuiForgeError};
+use crate::lock::{CheckOutcome, PreflightAudit, PreflightCheck};
+use crate::rpc::RpcClient;
+use crate::sui::SuiCli;
+use crate::utils;
+use chrono::Utc;
+use colored::Colorize;
+use dialoguer::Input;
+use std::io::IsTerminal;
+use std::path::Path;
+use std::process::Command;
+
+/// The deployed package an upgrade will replace, for the compatibility check.
+pub struct UpgradeTarget<'a> {
+    pub client: &'a RpcClient,
+    pub package_id: &'a str,
+    pub policy: UpgradePolicy,
+}
+
+/// Run the configured checks if `network` is protected. Returns `None` for
+/// unprotected networks, and an error as soon as a check fails.
+pub async fn preflight(
+    config: &SafetyConfig,
+    root: &Path,
+    network: &str,
+    deployer: &str,
+    upgrade: Option<UpgradeTarget<'_>>,
+) -> Result<Option<PreflightAudit>> {
+    if !config.protects(network) {
+        return Ok(None);
+    }
+
+    utils::info(&format!(
+        "{} is a protected network; running pre-flight checks",
+        network.red().bold()
+    ));
+
+    let mut checks = Vec::new();
+
+    checks.push(if config.require_clean_git {
+        check_clean_git(root)?
+    } else {
+        skipped("clean-git", "disabled in config")
+    });
+
+    checks.push(if config.require_tests {
+        check_tests(root)?
+    } else {
+        skipped("tests", "disabled in config")
+    });
+
+    checks.push(match &config.scan_threshold {
+        Some(threshold) => check_scan(root, threshold)?,
+        None => skipped("scan", "disabled in config"),
+    });
+
+    checks.push(match (config.require_compat, upgrade) {
+        (false, _) => skipped("compat", "disabled in config"),
+        (true, None) => skipped("compat", "fresh publish, nothing to compare against"),
+        (true, Some(target)) => check_compat(root, target).await?,
+    });
+
+    let confirmed_by = if config.require_confirmation {
+        let address = confirm(network, deployer)?;
+        checks.push(passed("confirmation", &format!("confirmed by {}", address)));
+        Some(address)
+    } else {
+        checks.push(skipped("confirmation", "disabled in config"));
+        None
+    };
+
+    utils::success("Pre-flight checks passed");
+
+    Ok(Some(PreflightAudit {
+        timestamp: Utc::now().to_rfc3339(),
+        checks,
+        confirmed_by,
+    }))
+}
+
+fn passed(name: &str, detail: &str) -> PreflightCheck {
+    utils::success(&format!("{}: {}", name, detail));
+    PreflightCheck {
+        name: name.to_string(),
+        outcome: CheckOutcome::Passed,
+        detail: detail.to_string(),
+    }
+}
+
+fn skipped(name: &str, detail: &str) -> PreflightCheck {
+    utils::info(&format!("{}: skipped ({})", name, detail));
+    PreflightCheck {
+        name: name.to_string(),
+        outcome: CheckOutcome::Skipped,
+        detail: detail.to_string(),
+    }
+}
+
+fn failed(name: &str, detail: impl Into<String>) -> SuiForgeError {
+    SuiForgeError::PreflightFailed(format!("{}: {}", name, detail.into()))
+}
+
+fn check_clean_git(root: &Path) -> Result<PreflightCheck> {
+    let output = Command::new("git")
+        .args(["status", "--porcelain"])
+        .current_dir(root)
+        .output()?;
+    if !output.status.success() {
+        return Err(failed("clean-git", "project is not a git repository"));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let dirty: Vec<&str> = stdout.lines().collect();
+    if !dirty.is_empty() {
+        for line in dirty.iter().take(10) {
+            println!("    {}", line.dimmed());
+        }
+        return Err(failed(
+            "clean-git",
+            format!("{} uncommitted change(s); commit or stash them first", dirty.len()),
+        ));
+    }
+
+    let commit = utils::git_commit(root).unwrap_or_default();
+    Ok(passed("clean-git", &format!("working tree clean at {}", commit)))
+}
+
+fn check_tests(root: &Path) -> Result<PreflightCheck> {
+    let spinner = utils::create_spinner("Running Move tests...");
+    let output = SuiCli::test(root, None)?;
+    spinner.finish_and_clear();
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        println!("\n{}", stderr);
+        return Err(failed("tests", "Move tests failed"));
+    }
+    Ok(passed("tests", "all Move tests passed"))
+}
+
+fn check_scan(root: &Path, threshold: &str) -> Result<PreflightCheck> {
+    let threshold = Severity::parse(threshold).ok_or_else(|| {
+        SuiForgeError::ConfigError(format!("Unknown scan threshold: {}", threshold))
+    })?;
+
+    let issues = scan_sources(&root.join("sources"), "standard")?;
+    let blocking = at_or_above(&issues, threshold);
+
+    if !blocking.is_empty() {
+        for issue in &blocking {
+            println!(
+                "    [{}] {} at {}",
+                issue.severity.as_str(),
+                issue.title,
+                issue.location
+            );
+        }
+        return Err(failed(
+            "scan",
+            format!(
+                "{} finding(s) at or above {}",
+                blocking.len(),
+                threshold.as_str()
+            ),
+        ));
+    }
+
+    Ok(passed(
+        "scan",
+        &format!(
+            "no findings at or above {} ({} below)",
+            threshold.as_str(),
+            issues.len()
+        ),
+    ))
+}
+
+fn at_or_above(issues: &[SecurityIssue], threshold: Severity) -> Vec<&SecurityIssue> {
+    issues
+        .iter()
+        .filter(|issue| issue.severity.rank() >= threshold.rank())
+        .collect()
+}
+
+async fn check_compat(root: &Path, target: UpgradeTarget<'_>) -> Result<PreflightCheck> {
+    let changes = compat::check(target.client, root, target.package_id, target.policy).await?;
+    let breaking: Vec<_> = changes.iter().filter(|c| c.breaking).collect();
+
+    if !breaking.is_empty() {
+        for change in &breaking {
+            println!("    {}  {}", change.item, change.description);
+        }
+        return Err(failed(
+            "compat",
+            format!(
+                "{} breaking change(s) under the {} policy",
+                breaking.len(),
+                target.policy
+            ),
+        ));
+    }
+
+    let unknown = changes
+        .iter()
+        .filter(|c| c.kind == compat::ChangeKind::Unknown)
+        .count();
+    let mut detail = format!(
+        "{} change(s), compatible under the {} policy",
+        changes.len(),
+        target.policy
+    );
+    if unknown > 0 {
+        detail.push_str(&format!(", {} could not be checked from source", unknown));
+    }
+    Ok(passed("compat", &detail))
+}
+
+/// Have the operator type the network name and deployer address back.
+fn confirm(network: &str, deployer: &str) -> Result<String> {
+    if !std::io::stdin().is_terminal() {
+        return Err(failed(
+            "confirmation",
+            "an interactive terminal is required to confirm a protected deployment",
+        ));
+    }
+
+    println!();
+    println!(
+        "  You are about to deploy to {} from {}",
+        network.red().bold(),
+        deployer.yellow()
+    );
+
+    let typed_network: String = Input::new()
+        .with_prompt("Type the network name to confirm")
+        .interact_text()
+        .map_err(|e| failed("confirmation", e.to_string()))?;
+    if typed_network.trim() != network {
+        return Err(failed("confirmation", "network name did not match"));
+    }
+
+    let typed_address: String = Input::new()
+        .with_prompt("Type the deployer address to confirm")
+        .interact_text()
+        .map_err(|e| failed("confirmation", e.to_string()))?;
+    if !typed_address.trim().eq_ignore_ascii_case(deployer) {
+        return Err(failed("confirmation", "deployer address did not match"));
+    }
+
+    Ok(deployer.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn issue(severity: Severity) -> SecurityIssue {
+        SecurityIssue {
+            severity,
+            title: format!("{:?} finding", severity),
+            description: String::new(),
+            location: "sources/pool.move:1".to_string(),
+            recommendation: String::new(),
+        }
+    }
+
+    #[test]
+    fn scan_blocks_findings_at_or_above_the_threshold() {
+        let issues = [
+            issue(Severity::High),
+            issue(Severity::Medium),
+            issue(Severity::Info),
+        ];
+        let blocking = |threshold| {
+            at_or_above(&issues, threshold)
+                .iter()
+                .map(|i| i.severity)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(blocking(Severity::Critical), []);
+        assert_eq!(blocking(Severity::High), [Severity::High]);
+        assert_eq!(
+            blocking(Severity::Medium),
+            [Severity::High, Severity::Medium]
+        );
+        assert_eq!(blocking(Severity::Info).len(), 3);
+    }
+
+    fn git(dir: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .unwrap()
+            .status;
+        assert!(status.success(), "git {:?} failed", args);
+    }
+
+    #[test]
+    fn clean_git_requires_a_repository_without_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let error = check_clean_git(dir.path()).unwrap_err();
+        assert!(error.to_string().contains("not a git repository"));
+
+        git(dir.path(), &["init", "-q"]);
+        std::fs::write(dir.path().join("Move.toml"), "[package]\n").unwrap();
+        let error = check_clean_git(dir.path()).unwrap_err();
+        assert!(error.to_string().contains("1 uncommitted change(s)"));
+
+        git(dir.path(), &["add", "-A"]);
+        git(dir.path(), &["commit", "-q", "-m", "init"]);
+        let check = check_clean_git(dir.path()).unwrap();
+        assert_eq!(check.outcome, CheckOutcome::Passed);
+        assert!(check.detail.starts_with("working tree clean at "));
+    }
+}
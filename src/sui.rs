@@ -1,4 +1,8 @@
+use crate::config::ResolvedNetwork;
 use crate::error::{Result, SuiForgeError};
+use serde::de::DeserializeOwned;
+use serde_yaml::{Mapping, Value};
+use std::path::{Path, PathBuf};
 use std::process::{Command, Output};
 use which::which;
 
@@ -11,7 +15,6 @@ impl SuiCli {
         Ok(())
     }
 
-    #[allow(dead_code)]
     pub fn version() -> Result<String> {
         let output = Command::new("sui").arg("--version").output()?;
 
@@ -36,9 +39,10 @@ impl SuiCli {
         Ok(output)
     }
 
-    pub fn test(filter: Option<String>) -> Result<Output> {
+    /// Run the Move tests of the package at `root`.
+    pub fn test(root: &Path, filter: Option<String>) -> Result<Output> {
         let mut cmd = Command::new("sui");
-        cmd.arg("move").arg("test");
+        cmd.arg("move").arg("test").current_dir(root);
 
         if let Some(f) = filter {
             cmd.arg("--filter").arg(f);
@@ -48,25 +52,59 @@ impl SuiCli {
         Ok(output)
     }
 
-    pub fn publish(network: &str, gas_budget: u64) -> Result<Output> {
+    /// Publish the package, or only simulate it with `dry_run`. Without a
+    /// gas budget the CLI estimates one itself.
+    pub fn publish(
+        network: &ResolvedNetwork,
+        gas_budget: Option<u64>,
+        dry_run: bool,
+    ) -> Result<Output> {
+        let config = ClientConfig::for_network(network)?;
+
         let mut cmd = Command::new("sui");
         cmd.arg("client")
-            .arg("publish")
-            .arg("--gas-budget")
-            .arg(gas_budget.to_string());
-
-        // Add network-specific flags
-        match network {
-            "devnet" | "testnet" | "mainnet" => {
-                cmd.arg("--network").arg(network);
-            }
-            _ => return Err(SuiForgeError::InvalidNetwork(network.to_string())),
-        }
+            .arg("--client.config")
+            .arg(config.path())
+            .arg("publish");
+        transaction_args(&mut cmd, gas_budget, dry_run);
 
         let output = cmd.output()?;
         Ok(output)
     }
 
+    pub fn upgrade(
+        network: &ResolvedNetwork,
+        upgrade_cap_id: &str,
+        gas_budget: Option<u64>,
+        dry_run: bool,
+    ) -> Result<Output> {
+        let config = ClientConfig::for_network(network)?;
+
+        let mut cmd = Command::new("sui");
+        cmd.arg("client")
+            .arg("--client.config")
+            .arg(config.path())
+            .arg("upgrade")
+            .arg("--upgrade-capability")
+            .arg(upgrade_cap_id);
+        transaction_args(&mut cmd, gas_budget, dry_run);
+
+        let output = cmd.output()?;
+        Ok(output)
+    }
+
+    /// Parse the JSON document printed by a `--json` client command.
+    ///
+    /// The CLI may print build progress or warnings ahead of the JSON
+    /// document, so everything before the first `{` is ignored.
+    pub fn parse_json_output<T: DeserializeOwned>(stdout: &[u8]) -> Result<T> {
+        let stdout = String::from_utf8_lossy(stdout);
+        let start = stdout.find('{').ok_or_else(|| {
+            SuiForgeError::Custom("sui client did not print a JSON response".to_string())
+        })?;
+        Ok(serde_json::from_str(&stdout[start..])?)
+    }
+
     pub fn get_active_address() -> Result<String> {
         let output = Command::new("sui")
             .arg("client")
@@ -102,3 +140,182 @@ impl SuiCli {
         }
     }
 }
+
+/// A copy of the user's sui client config whose active env is one network,
+/// handed to the CLI with `--client.config` so the user's own active env is
+/// never switched. The copy is removed on drop.
+pub struct ClientConfig {
+    path: PathBuf,
+}
+
+impl ClientConfig {
+    /// Write a client config for `network`. An env with the same RPC URL is
+    /// reused; otherwise one is added under `suiforge-<network>`.
+    pub fn for_network(network: &ResolvedNetwork) -> Result<Self> {
+        let source = client_config_path()?;
+        let yaml = std::fs::read_to_string(&source).map_err(|e| {
+            SuiForgeError::Custom(format!(
+                "Failed to read sui client config {}: {}. Run `sui client` once to create it",
+                source.display(),
+                e
+            ))
+        })?;
+
+        let mut config: Value = serde_yaml::from_str(&yaml)?;
+        if let Some(dir) = source.parent() {
+            anchor_keystore(&mut config, dir);
+        }
+        select_env(&mut config, network)?;
+
+        let path =
+            std::env::temp_dir().join(format!("suiforge-client-{}.yaml", std::process::id()));
+        std::fs::write(&path, serde_yaml::to_string(&config)?)?;
+        Ok(Self { path })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for ClientConfig {
+    fn drop(&mut self) {
+        if let Err(e) = std::fs::remove_file(&self.path) {
+            tracing::warn!("Could not remove {}: {}", self.path.display(), e);
+        }
+    }
+}
+
+/// The user's `client.yaml`, honouring `SUI_CONFIG_DIR` like the sui CLI.
+fn client_config_path() -> Result<PathBuf> {
+    let dir = match std::env::var_os("SUI_CONFIG_DIR") {
+        Some(dir) => PathBuf::from(dir),
+        None => home::home_dir()
+            .ok_or_else(|| SuiForgeError::Custom("Could not find home directory".to_string()))?
+            .join(".sui")
+            .join("sui_config"),
+    };
+    Ok(dir.join("client.yaml"))
+}
+
+/// Make a relative keystore path absolute, since the copy lives elsewhere.
+fn anchor_keystore(config: &mut Value, dir: &Path) {
+    if let Some(file) = config.get_mut("keystore").and_then(|k| k.get_mut("File")) {
+        if let Some(path) = file.as_str().filter(|p| Path::new(p).is_relative()) {
+            *file = Value::from(dir.join(path).to_string_lossy().into_owned());
+        }
+    }
+}
+
+/// Make `network` the active env of a parsed client config.
+fn select_env(config: &mut Value, network: &ResolvedNetwork) -> Result<()> {
+    let envs = config
+        .get_mut("envs")
+        .and_then(Value::as_sequence_mut)
+        .ok_or_else(|| SuiForgeError::Custom("sui client config has no envs".to_string()))?;
+    let rpc = network.rpc.trim_end_matches('/');
+
+    let existing = envs
+        .iter()
+        .find(|env| {
+            env.get("rpc")
+                .and_then(Value::as_str)
+                .map(|r| r.trim_end_matches('/'))
+                == Some(rpc)
+        })
+        .and_then(|env| env.get("alias"))
+        .and_then(Value::as_str)
+        .map(str::to_string);
+
+    let alias = match existing {
+        Some(alias) => alias,
+        None => {
+            let alias = format!("suiforge-{}", network.name);
+            envs.retain(|env| env.get("alias").and_then(Value::as_str) != Some(alias.as_str()));
+
+            let mut env = Mapping::new();
+            env.insert("alias".into(), alias.clone().into());
+            env.insert("rpc".into(), rpc.into());
+            env.insert("ws".into(), Value::Null);
+            env.insert("basic_auth".into(), Value::Null);
+            envs.push(Value::Mapping(env));
+            alias
+        }
+    };
+
+    let config = config
+        .as_mapping_mut()
+        .ok_or_else(|| SuiForgeError::Custom("sui client config is not a mapping".to_string()))?;
+    config.insert("active_env".into(), alias.into());
+    Ok(())
+}
+
+fn transaction_args(cmd: &mut Command, gas_budget: Option<u64>, dry_run: bool) {
+    if let Some(budget) = gas_budget {
+        cmd.arg("--gas-budget").arg(budget.to_string());
+    }
+    if dry_run {
+        cmd.arg("--dry-run");
+    }
+    cmd.arg("--json");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CLIENT_YAML: &str = r#"
+keystore:
+  File: sui.keystore
+envs:
+  - alias: devnet
+    rpc: "https://fullnode.devnet.sui.io:443"
+    ws: ~
+    basic_auth: ~
+active_env: devnet
+active_address: "0x1"
+"#;
+
+    fn network(name: &str, rpc: &str) -> ResolvedNetwork {
+        ResolvedNetwork {
+            name: name.to_string(),
+            rpc: rpc.to_string(),
+        }
+    }
+
+    #[test]
+    fn select_env_reuses_an_env_with_the_same_rpc() {
+        let mut config: Value = serde_yaml::from_str(CLIENT_YAML).unwrap();
+        select_env(
+            &mut config,
+            &network("dev", "https://fullnode.devnet.sui.io:443/"),
+        )
+        .unwrap();
+
+        assert_eq!(config["active_env"], Value::from("devnet"));
+        assert_eq!(config["envs"].as_sequence().unwrap().len(), 1);
+        assert_eq!(config["active_address"], Value::from("0x1"));
+    }
+
+    #[test]
+    fn select_env_adds_an_env_for_a_new_rpc() {
+        let mut config: Value = serde_yaml::from_str(CLIENT_YAML).unwrap();
+        select_env(&mut config, &network("localnet", "http://127.0.0.1:9000")).unwrap();
+
+        assert_eq!(config["active_env"], Value::from("suiforge-localnet"));
+        let envs = config["envs"].as_sequence().unwrap();
+        assert_eq!(envs.len(), 2);
+        assert_eq!(envs[1]["rpc"], Value::from("http://127.0.0.1:9000"));
+    }
+
+    #[test]
+    fn relative_keystore_paths_are_anchored_to_the_config_dir() {
+        let mut config: Value = serde_yaml::from_str(CLIENT_YAML).unwrap();
+        anchor_keystore(&mut config, Path::new("/home/me/.sui/sui_config"));
+
+        assert_eq!(
+            config["keystore"]["File"],
+            Value::from("/home/me/.sui/sui_config/sui.keystore")
+        );
+    }
+}
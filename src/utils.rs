@@ -1,5 +1,7 @@
 use colored::Colorize;
 use indicatif::{ProgressBar, ProgressStyle};
+use std::path::Path;
+use std::process::Command;
 use std::time::Duration;
 
 pub fn success(msg: &str) {
@@ -43,3 +45,17 @@ pub fn create_progress_bar(len: u64, msg: &str) -> ProgressBar {
     pb.set_message(msg.to_string());
     pb
 }
+
+/// Commit hash of `HEAD` in the repository containing `dir`, if any.
+pub fn git_commit(dir: &Path) -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "HEAD"])
+        .current_dir(dir)
+        .output()
+        .ok()?;
+    if output.status.success() {
+        Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    } else {
+        None
+    }
+}
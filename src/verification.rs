@@ -0,0 +1,578 @@
+//! Checking that what is on chain matches the local build.
+//!
+//! Publishing rewrites a package's self address from `0x0` to the new
+//! package ID, so module bytecode is compared with the package's own
+//! addresses zeroed out of the modules' address tables on both sides.
+
+use crate::abi::{canonical_address, is_hex_address, same_address, short_address};
+use crate::error::{Result, SuiForgeError};
+use crate::lock::DeployedObject;
+use crate::manifest::Manifest;
+use crate::move_lock::ResolvedDependency;
+use crate::rpc::types::{ObjectDataOptions, Owner, SuiRawData, UpgradeInfo};
+use crate::rpc::RpcClient;
+use base64::Engine;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Where `sui move build` writes its output, relative to the project root.
+pub const BUILD_DIR: &str = "build";
+
+/// First bytes of every Move module.
+const MOVE_MAGIC: [u8; 4] = [0xA1, 0x1C, 0xEB, 0x0B];
+/// Table kind of the address identifiers that module handles refer to.
+const ADDRESS_IDENTIFIERS: u8 = 0x08;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ModuleStatus {
+    Match,
+    Differs,
+    /// Built locally but not part of the on-chain package.
+    MissingOnChain,
+    /// Part of the on-chain package but not built locally.
+    MissingLocally,
+}
+
+impl ModuleStatus {
+    pub fn as_str(&self) -> &str {
+        match self {
+            ModuleStatus::Match => "match",
+            ModuleStatus::Differs => "differs",
+            ModuleStatus::MissingOnChain => "missing on chain",
+            ModuleStatus::MissingLocally => "missing locally",
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ModuleCheck {
+    pub name: String,
+    pub status: ModuleStatus,
+}
+
+/// A package's modules and linkage as stored on chain.
+pub struct OnChainPackage {
+    pub id: String,
+    pub modules: BTreeMap<String, Vec<u8>>,
+    /// The version of each dependency the package links against, keyed by
+    /// the dependency's original ID.
+    pub linkage_table: BTreeMap<String, UpgradeInfo>,
+    /// Every version of this package that defined one of its types. The
+    /// original package ID, which upgraded modules keep as their self
+    /// address, is among them whenever the package defines a type.
+    pub type_origins: Vec<String>,
+}
+
+impl OnChainPackage {
+    /// Addresses that stand for the package itself inside its modules.
+    pub fn self_addresses(&self) -> Vec<String> {
+        let mut addresses = vec![self.id.clone()];
+        for origin in &self.type_origins {
+            if !addresses.iter().any(|a| same_address(a, origin)) {
+                addresses.push(origin.clone());
+            }
+        }
+        addresses
+    }
+}
+
+/// The build output directory for the package at `root`.
+pub fn bytecode_dir(root: &Path) -> Result<PathBuf> {
+    let name = Manifest::load(root)?
+        .package_name()?
+        .ok_or_else(|| SuiForgeError::ConfigError("Move.toml has no package name".to_string()))?;
+    Ok(root.join(BUILD_DIR).join(name).join("bytecode_modules"))
+}
+
+/// Bytecode of every module in the local build, by module name.
+pub fn local_modules(root: &Path) -> Result<BTreeMap<String, Vec<u8>>> {
+    let dir = bytecode_dir(root)?;
+    let entries = fs::read_dir(&dir).map_err(|e| {
+        SuiForgeError::Custom(format!(
+            "Could not read build output at {}: {}. Build the package first.",
+            dir.display(),
+            e
+        ))
+    })?;
+
+    let mut modules = BTreeMap::new();
+    for entry in entries {
+        let path = entry?.path();
+        if path.extension().is_some_and(|ext| ext == "mv") {
+            if let Some(name) = path.file_stem().and_then(|s| s.to_str()) {
+                modules.insert(name.to_string(), fs::read(&path)?);
+            }
+        }
+    }
+    Ok(modules)
+}
+
+/// Fetch a package object with its raw module bytecode.
+pub async fn fetch_package(client: &RpcClient, package_id: &str) -> Result<OnChainPackage> {
+    let object = client
+        .get_object(package_id, ObjectDataOptions::default().with_bcs())
+        .await?;
+
+    let Some(SuiRawData::Package {
+        id,
+        module_map,
+        type_origin_table,
+        linkage_table,
+        ..
+    }) = object.bcs
+    else {
+        return Err(SuiForgeError::Custom(format!(
+            "{} is not a package",
+            package_id
+        )));
+    };
+
+    let mut modules = BTreeMap::new();
+    for (name, encoded) in module_map {
+        let bytes = base64::engine::general_purpose::STANDARD
+            .decode(encoded.as_bytes())
+            .map_err(|e| {
+                SuiForgeError::Custom(format!("Module {} has invalid bytecode: {}", name, e))
+            })?;
+        modules.insert(name, bytes);
+    }
+
+    let mut type_origins: Vec<String> = type_origin_table
+        .into_iter()
+        .map(|origin| origin.package)
+        .collect();
+    type_origins.sort();
+    type_origins.dedup();
+
+    Ok(OnChainPackage {
+        id,
+        modules,
+        linkage_table,
+        type_origins,
+    })
+}
+
+/// SHA-256 of a module's bytecode with `self_addresses` zeroed.
+pub fn bytecode_hash(bytecode: &[u8], self_addresses: &[String]) -> String {
+    let addresses: Vec<[u8; 32]> = self_addresses
+        .iter()
+        .filter_map(|a| address_bytes(a))
+        .collect();
+    hex::encode(Sha256::digest(strip_addresses(bytecode, &addresses)))
+}
+
+/// Compare local and on-chain modules, ignoring `self_addresses`.
+pub fn compare_modules(
+    local: &BTreeMap<String, Vec<u8>>,
+    onchain: &BTreeMap<String, Vec<u8>>,
+    self_addresses: &[String],
+) -> Vec<ModuleCheck> {
+    let addresses: Vec<[u8; 32]> = self_addresses
+        .iter()
+        .filter_map(|a| address_bytes(a))
+        .collect();
+
+    let mut checks = Vec::new();
+    for (name, local_bytes) in local {
+        let status = match onchain.get(name) {
+            None => ModuleStatus::MissingOnChain,
+            Some(onchain_bytes) => {
+                if strip_addresses(local_bytes, &addresses)
+                    == strip_addresses(onchain_bytes, &addresses)
+                {
+                    ModuleStatus::Match
+                } else {
+                    ModuleStatus::Differs
+                }
+            }
+        };
+        checks.push(ModuleCheck {
+            name: name.clone(),
+            status,
+        });
+    }
+    for name in onchain.keys() {
+        if !local.contains_key(name) {
+            checks.push(ModuleCheck {
+                name: name.clone(),
+                status: ModuleStatus::MissingLocally,
+            });
+        }
+    }
+    checks
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LinkStatus {
+    /// Linked against the version the local build resolves.
+    Linked,
+    /// Linked against a different version than the local build resolves.
+    Drift,
+    /// A local dependency the package does not link against.
+    NotLinked,
+    /// Linked on chain but not a dependency of the local build.
+    Unexpected,
+    /// The local dependency's published address could not be found.
+    Unresolved,
+}
+
+impl LinkStatus {
+    pub fn as_str(&self) -> &str {
+        match self {
+            LinkStatus::Linked => "linked",
+            LinkStatus::Drift => "version drift",
+            LinkStatus::NotLinked => "not linked on chain",
+            LinkStatus::Unexpected => "linked on chain only",
+            LinkStatus::Unresolved => "address unresolved",
+        }
+    }
+
+    /// Whether the on-chain package was built against other dependencies.
+    pub fn is_drift(&self) -> bool {
+        matches!(self, LinkStatus::Drift | LinkStatus::Unexpected)
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct DependencyCheck {
+    pub name: String,
+    pub original_id: Option<String>,
+    /// The version the local build resolves.
+    pub expected: Option<String>,
+    /// The version the on-chain package links against.
+    pub linked: Option<String>,
+    pub linked_version: Option<u64>,
+    pub status: LinkStatus,
+}
+
+/// Compare resolved local dependencies with a package's linkage table.
+pub fn compare_linkage(
+    dependencies: &[ResolvedDependency],
+    linkage: &BTreeMap<String, UpgradeInfo>,
+) -> Vec<DependencyCheck> {
+    let mut seen = Vec::new();
+    let mut checks = Vec::new();
+
+    for dependency in dependencies {
+        let entry = dependency
+            .original_id
+            .as_deref()
+            .and_then(|original| linkage.iter().find(|(id, _)| same_address(original, id)));
+        if let Some((id, _)) = entry {
+            seen.push(id.clone());
+        }
+
+        let status = match (&dependency.published_at, entry) {
+            (None, _) => LinkStatus::Unresolved,
+            (Some(_), None) => LinkStatus::NotLinked,
+            (Some(expected), Some((_, info))) if same_address(expected, &info.upgraded_id) => {
+                LinkStatus::Linked
+            }
+            (Some(_), Some(_)) => LinkStatus::Drift,
+        };
+        checks.push(DependencyCheck {
+            name: dependency.name.clone(),
+            original_id: dependency.original_id.clone(),
+            expected: dependency.published_at.clone(),
+            linked: entry.map(|(_, info)| info.upgraded_id.clone()),
+            linked_version: entry.map(|(_, info)| info.upgraded_version),
+            status,
+        });
+    }
+
+    for (id, info) in linkage {
+        if !seen.contains(id) {
+            checks.push(DependencyCheck {
+                name: short_address(id),
+                original_id: Some(id.clone()),
+                expected: None,
+                linked: Some(info.upgraded_id.clone()),
+                linked_version: Some(info.upgraded_version),
+                status: LinkStatus::Unexpected,
+            });
+        }
+    }
+    checks
+}
+
+/// Result of looking up one object a deployment created.
+#[derive(Debug, Serialize)]
+pub struct ObjectCheck {
+    pub object_id: String,
+    pub object_type: String,
+    /// The owner the object should have, for capabilities.
+    pub expected_owner: Option<Owner>,
+    /// `None` if the object could not be found on chain.
+    pub owner: Option<Owner>,
+    pub problem: Option<String>,
+}
+
+/// Whether an object type is a capability, whose owner must not change.
+pub fn is_capability(object_type: &str) -> bool {
+    let name = object_type.split('<').next().unwrap_or(object_type);
+    let name = name.rsplit("::").next().unwrap_or(name);
+    name.ends_with("Cap") || object_type == "0x2::package::Publisher"
+}
+
+/// Look up `objects` on chain, checking each still has its recorded type
+/// and that capabilities still have the owner the publish gave them.
+pub async fn check_objects(
+    client: &RpcClient,
+    objects: &[DeployedObject],
+) -> Result<Vec<ObjectCheck>> {
+    if objects.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let ids: Vec<String> = objects.iter().map(|o| o.object_id.clone()).collect();
+    let options = ObjectDataOptions {
+        show_type: true,
+        show_owner: true,
+        ..Default::default()
+    };
+    let responses = client.get_objects(&ids, options).await?;
+
+    let mut checks = Vec::new();
+    for (object, response) in objects.iter().zip(responses) {
+        let expected_owner = is_capability(&object.object_type).then(|| object.owner.clone());
+        let data = response.data;
+
+        let problem = match &data {
+            None => Some("not found on chain".to_string()),
+            Some(data) if data.type_.as_deref() != Some(object.object_type.as_str()) => {
+                Some(format!(
+                    "type is {}, expected {}",
+                    data.type_.as_deref().unwrap_or("unknown"),
+                    object.object_type
+                ))
+            }
+            Some(data) => match (&expected_owner, &data.owner) {
+                (Some(expected), Some(owner)) if same_owner(expected, owner) => None,
+                (Some(expected), owner) => Some(format!(
+                    "owned by {}, expected {}",
+                    owner
+                        .as_ref()
+                        .map(|o| o.to_string())
+                        .unwrap_or_else(|| "nobody".to_string()),
+                    expected
+                )),
+                (None, _) => None,
+            },
+        };
+
+        checks.push(ObjectCheck {
+            object_id: object.object_id.clone(),
+            object_type: object.object_type.clone(),
+            expected_owner,
+            owner: data.and_then(|d| d.owner),
+            problem,
+        });
+    }
+    Ok(checks)
+}
+
+fn same_owner(a: &Owner, b: &Owner) -> bool {
+    match (a, b) {
+        (Owner::AddressOwner(a), Owner::AddressOwner(b))
+        | (Owner::ObjectOwner(a), Owner::ObjectOwner(b)) => same_address(a, b),
+        (
+            Owner::ConsensusAddressOwner { owner: a, .. },
+            Owner::ConsensusAddressOwner { owner: b, .. },
+        ) => same_address(a, b),
+        _ => a == b,
+    }
+}
+
+fn address_bytes(address: &str) -> Option<[u8; 32]> {
+    if !is_hex_address(address) {
+        return None;
+    }
+    let bytes = hex::decode(&canonical_address(address)[2..]).ok()?;
+    bytes.try_into().ok()
+}
+
+/// Zero the entries of the module's address identifier table that are one
+/// of `addresses`. Publishing only rewrites that table, so equal bytes
+/// elsewhere, in a constant for instance, are real differences and kept.
+/// Bytecode whose table headers cannot be read is returned unchanged.
+fn strip_addresses(bytecode: &[u8], addresses: &[[u8; 32]]) -> Vec<u8> {
+    let mut bytes = bytecode.to_vec();
+    if let Some(table) = address_table(bytecode) {
+        for entry in bytes[table].chunks_exact_mut(32) {
+            if addresses.iter().any(|a| entry == &a[..]) {
+                entry.fill(0);
+            }
+        }
+    }
+    bytes
+}
+
+/// Byte range of the address identifier table, read from the table headers
+/// that follow the magic and the version: a ULEB128 table count, then per
+/// table its kind, and the ULEB128 offset and length of its contents.
+fn address_table(bytecode: &[u8]) -> Option<std::ops::Range<usize>> {
+    if bytecode.get(..4)? != MOVE_MAGIC {
+        return None;
+    }
+    let mut pos = 8;
+    let count = read_uleb128(bytecode, &mut pos)?;
+    let mut found = None;
+    for _ in 0..count {
+        let kind = *bytecode.get(pos)?;
+        pos += 1;
+        let offset = read_uleb128(bytecode, &mut pos)?;
+        let len = read_uleb128(bytecode, &mut pos)?;
+        if kind == ADDRESS_IDENTIFIERS {
+            found = Some((offset, len));
+        }
+    }
+    let (offset, len) = found?;
+    let start = pos.checked_add(offset)?;
+    let end = start.checked_add(len)?;
+    (end <= bytecode.len() && len % 32 == 0).then_some(start..end)
+}
+
+fn read_uleb128(bytes: &[u8], pos: &mut usize) -> Option<usize> {
+    let mut value = 0usize;
+    for shift in (0..35).step_by(7) {
+        let byte = *bytes.get(*pos)?;
+        *pos += 1;
+        value |= usize::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return Some(value);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PKG: &str = "0x5a1e";
+
+    fn address(a: &str) -> [u8; 32] {
+        address_bytes(a).unwrap()
+    }
+
+    /// A module with a one-entry module handle table pointing at the first
+    /// address, a constant pool holding one `address` constant, and the
+    /// address identifier table.
+    fn module(constant: [u8; 32], addresses: &[[u8; 32]]) -> Vec<u8> {
+        let mut bytes = MOVE_MAGIC.to_vec();
+        bytes.extend([6, 0, 0, 0, 3]);
+        bytes.extend([0x01, 0, 2]);
+        bytes.extend([0x06, 2, 34]);
+        bytes.extend([ADDRESS_IDENTIFIERS, 36, 32 * addresses.len() as u8]);
+        bytes.extend([0, 0]);
+        bytes.extend([0x05, 32]);
+        bytes.extend(constant);
+        for address in addresses {
+            bytes.extend(address);
+        }
+        bytes.push(0);
+        bytes
+    }
+
+    fn modules(bytecode: Vec<u8>) -> BTreeMap<String, Vec<u8>> {
+        BTreeMap::from([("pool".to_string(), bytecode)])
+    }
+
+    #[test]
+    fn self_address_in_the_address_table_is_ignored() {
+        let local = module(address("0x7"), &[address("0x0"), address("0x2")]);
+        let onchain = module(address("0x7"), &[address(PKG), address("0x2")]);
+
+        let checks = compare_modules(&modules(local), &modules(onchain), &[PKG.to_string()]);
+        assert_eq!(checks.len(), 1);
+        assert_eq!(checks[0].status, ModuleStatus::Match);
+    }
+
+    #[test]
+    fn self_address_bytes_in_constants_are_kept() {
+        let onchain = module(address(PKG), &[address(PKG), address("0x2")]);
+        let stripped = strip_addresses(&onchain, &[address(PKG)]);
+        assert_eq!(stripped, module(address(PKG), &[[0; 32], address("0x2")]));
+
+        let local = module(address("0x0"), &[address("0x0"), address("0x2")]);
+        let checks = compare_modules(&modules(local), &modules(onchain), &[PKG.to_string()]);
+        assert_eq!(checks[0].status, ModuleStatus::Differs);
+    }
+
+    #[test]
+    fn unreadable_bytecode_is_left_as_is() {
+        let pkg = address(PKG);
+        let mut not_a_module = vec![0u8; 8];
+        not_a_module.extend(pkg);
+        assert_eq!(strip_addresses(&not_a_module, &[pkg]), not_a_module);
+
+        let mut truncated = module(address("0x7"), &[pkg]);
+        truncated.truncate(50);
+        assert_eq!(strip_addresses(&truncated, &[pkg]), truncated);
+    }
+
+    #[test]
+    fn modules_missing_on_either_side_are_reported() {
+        let bytecode = module(address("0x7"), &[address("0x0")]);
+        let local = BTreeMap::from([("a".to_string(), bytecode.clone())]);
+        let onchain = BTreeMap::from([("b".to_string(), bytecode)]);
+
+        let statuses: Vec<(String, ModuleStatus)> = compare_modules(&local, &onchain, &[])
+            .into_iter()
+            .map(|c| (c.name, c.status))
+            .collect();
+        assert_eq!(
+            statuses,
+            [
+                ("a".to_string(), ModuleStatus::MissingOnChain),
+                ("b".to_string(), ModuleStatus::MissingLocally),
+            ]
+        );
+    }
+
+    #[test]
+    fn linkage_is_compared_by_original_id() {
+        let dependency =
+            |name: &str, original: Option<&str>, published: Option<&str>| ResolvedDependency {
+                name: name.to_string(),
+                original_id: original.map(str::to_string),
+                published_at: published.map(str::to_string),
+            };
+        let dependencies = [
+            dependency("Sui", Some("0x2"), Some("0x2")),
+            dependency("Oracle", Some("0xa"), Some("0xa2")),
+            dependency("Math", Some("0xb"), Some("0xb")),
+            dependency("Local", None, None),
+        ];
+        let link = |id: &str, version| UpgradeInfo {
+            upgraded_id: id.to_string(),
+            upgraded_version: version,
+        };
+        let linkage = BTreeMap::from([
+            (canonical_address("0x2"), link("0x2", 1)),
+            (canonical_address("0xa"), link("0xa3", 3)),
+            (canonical_address("0xe"), link("0xe", 1)),
+        ]);
+
+        let statuses: Vec<(String, LinkStatus)> = compare_linkage(&dependencies, &linkage)
+            .into_iter()
+            .map(|c| (c.name, c.status))
+            .collect();
+        assert_eq!(
+            statuses,
+            [
+                ("Sui".to_string(), LinkStatus::Linked),
+                ("Oracle".to_string(), LinkStatus::Drift),
+                ("Math".to_string(), LinkStatus::NotLinked),
+                ("Local".to_string(), LinkStatus::Unresolved),
+                ("0xe".to_string(), LinkStatus::Unexpected),
+            ]
+        );
+        assert!(statuses[1].1.is_drift() && statuses[4].1.is_drift());
+    }
+}